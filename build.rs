@@ -0,0 +1,5 @@
+// Recompilando quando uma migração for adicionada ou alterada,
+// já que elas são embutidas no binário por sqlx::migrate!.
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
@@ -0,0 +1,10 @@
+CREATE TABLE
+    IF NOT EXISTS messages (
+        id UUID PRIMARY KEY NOT NULL DEFAULT (uuid_generate_v4()),
+        likes INTEGER NOT NULL DEFAULT 0,
+        user_id UUID NOT NULL REFERENCES rust_user (id) ON DELETE CASCADE,
+        content TEXT NOT NULL,
+        sended_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    );
+
+CREATE INDEX IF NOT EXISTS messages_user_id_idx ON messages (user_id);
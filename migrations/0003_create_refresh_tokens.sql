@@ -0,0 +1,11 @@
+CREATE TABLE
+    IF NOT EXISTS refresh_tokens (
+        id UUID PRIMARY KEY NOT NULL DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL REFERENCES rust_user (id) ON DELETE CASCADE,
+        token_hash VARCHAR(64) NOT NULL UNIQUE,
+        expires_at TIMESTAMPTZ NOT NULL,
+        revoked_at TIMESTAMPTZ,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    );
+
+CREATE INDEX IF NOT EXISTS refresh_tokens_user_id_idx ON refresh_tokens (user_id);
@@ -0,0 +1,2 @@
+ALTER TABLE rust_user
+ADD COLUMN IF NOT EXISTS created_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS messages_user_id_idx;
+
+CREATE INDEX IF NOT EXISTS messages_sended_at_id_idx ON messages (sended_at DESC, id DESC);
+
+CREATE INDEX IF NOT EXISTS messages_user_id_sended_at_id_idx ON messages (user_id, sended_at DESC, id DESC);
+
+CREATE INDEX IF NOT EXISTS rust_user_name_id_idx ON rust_user (name, id);
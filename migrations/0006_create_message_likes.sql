@@ -0,0 +1,31 @@
+CREATE TABLE
+    IF NOT EXISTS message_likes (
+        message_id UUID NOT NULL REFERENCES messages (id) ON DELETE CASCADE,
+        user_id UUID NOT NULL REFERENCES rust_user (id) ON DELETE CASCADE,
+        liked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (message_id, user_id)
+    );
+
+CREATE INDEX IF NOT EXISTS message_likes_message_id_liked_at_idx ON message_likes (message_id, liked_at DESC, user_id DESC);
+
+CREATE INDEX IF NOT EXISTS message_likes_user_id_idx ON message_likes (user_id);
+
+-- Mantendo messages.likes igual ao número de linhas em message_likes,
+-- inclusive quando as curtidas são removidas em cascata.
+CREATE OR REPLACE FUNCTION message_likes_count() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE messages SET likes = likes + 1 WHERE id = NEW.message_id;
+    ELSE
+        UPDATE messages SET likes = likes - 1 WHERE id = OLD.message_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER message_likes_count
+AFTER INSERT OR DELETE ON message_likes
+FOR EACH ROW EXECUTE FUNCTION message_likes_count();
+
+UPDATE messages
+SET likes = (SELECT COUNT(*) FROM message_likes WHERE message_id = messages.id);
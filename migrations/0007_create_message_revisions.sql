@@ -0,0 +1,12 @@
+ALTER TABLE messages ADD COLUMN IF NOT EXISTS edited_at TIMESTAMPTZ;
+
+CREATE TABLE
+    IF NOT EXISTS message_revisions (
+        id UUID PRIMARY KEY NOT NULL DEFAULT (uuid_generate_v4()),
+        message_id UUID NOT NULL REFERENCES messages (id) ON DELETE CASCADE,
+        content TEXT NOT NULL,
+        written_at TIMESTAMPTZ NOT NULL,
+        replaced_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    );
+
+CREATE INDEX IF NOT EXISTS message_revisions_message_id_replaced_at_idx ON message_revisions (message_id, replaced_at DESC, id DESC);
@@ -0,0 +1,8 @@
+-- Vetor de busca textual com os lexemas em português e em inglês,
+-- permitindo escolher o idioma da consulta sem reindexar as mensagens.
+ALTER TABLE messages
+ADD COLUMN IF NOT EXISTS search_vector TSVECTOR GENERATED ALWAYS AS (
+    to_tsvector('portuguese'::regconfig, content) || to_tsvector('english'::regconfig, content)
+) STORED;
+
+CREATE INDEX IF NOT EXISTS messages_search_vector_idx ON messages USING GIN (search_vector);
@@ -0,0 +1,4 @@
+ALTER TABLE rust_user
+ADD COLUMN IF NOT EXISTS display_name VARCHAR(64),
+ADD COLUMN IF NOT EXISTS bio VARCHAR(280),
+ADD COLUMN IF NOT EXISTS avatar_url VARCHAR(2048);
@@ -0,0 +1,13 @@
+CREATE TABLE
+    IF NOT EXISTS follows (
+        follower_id UUID NOT NULL REFERENCES rust_user (id) ON DELETE CASCADE,
+        followed_id UUID NOT NULL REFERENCES rust_user (id) ON DELETE CASCADE,
+        followed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (follower_id, followed_id),
+        CHECK (follower_id <> followed_id)
+    );
+
+-- Listas de seguidores e de seguidos, das mais novas para as mais antigas.
+CREATE INDEX IF NOT EXISTS follows_followed_id_followed_at_idx ON follows (followed_id, followed_at DESC, follower_id DESC);
+
+CREATE INDEX IF NOT EXISTS follows_follower_id_followed_at_idx ON follows (follower_id, followed_at DESC, followed_id DESC);
@@ -0,0 +1,33 @@
+-- Respostas: cada mensagem pode responder a outra. Ao remover uma
+-- conta as respostas de outros usuários às suas mensagens passam a
+-- ser raízes de novas conversas.
+ALTER TABLE messages ADD COLUMN IF NOT EXISTS parent_id UUID REFERENCES messages (id) ON DELETE SET NULL;
+
+ALTER TABLE messages ADD COLUMN IF NOT EXISTS reply_count INTEGER NOT NULL DEFAULT 0;
+
+-- Mensagens removidas que ainda têm respostas são mantidas sem
+-- conteúdo (tombstones) para não quebrar a árvore da conversa.
+ALTER TABLE messages ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS messages_parent_id_sended_at_idx ON messages (parent_id, sended_at, id)
+WHERE parent_id IS NOT NULL;
+
+-- Mantendo messages.reply_count igual ao número de respostas diretas,
+-- inclusive quando as respostas são removidas em cascata. O tombstone
+-- que fica sem respostas é removido, subindo pela conversa.
+CREATE OR REPLACE FUNCTION message_replies_count() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE messages SET reply_count = reply_count + 1 WHERE id = NEW.parent_id;
+    ELSE
+        UPDATE messages SET reply_count = reply_count - 1 WHERE id = OLD.parent_id;
+        DELETE FROM messages
+        WHERE id = OLD.parent_id AND deleted_at IS NOT NULL AND reply_count = 0;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER message_replies_count
+AFTER INSERT OR DELETE ON messages
+FOR EACH ROW EXECUTE FUNCTION message_replies_count();
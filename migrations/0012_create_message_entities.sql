@@ -0,0 +1,25 @@
+-- Hashtags e menções extraídas do conteúdo com as posições em bytes.
+-- Mensagens anteriores ficam com NULL até serem processadas ao iniciar.
+ALTER TABLE messages ADD COLUMN IF NOT EXISTS entities JSONB;
+
+-- Linhas do tempo das hashtags e das menções, com a data da mensagem
+-- repetida para que a página seja lida diretamente do índice.
+CREATE TABLE
+    IF NOT EXISTS message_tags (
+        message_id UUID NOT NULL REFERENCES messages (id) ON DELETE CASCADE,
+        tag VARCHAR(100) NOT NULL,
+        sended_at TIMESTAMPTZ NOT NULL,
+        PRIMARY KEY (message_id, tag)
+    );
+
+CREATE INDEX IF NOT EXISTS message_tags_tag_sended_at_idx ON message_tags (tag, sended_at DESC, message_id DESC);
+
+CREATE TABLE
+    IF NOT EXISTS message_mentions (
+        message_id UUID NOT NULL REFERENCES messages (id) ON DELETE CASCADE,
+        user_id UUID NOT NULL REFERENCES rust_user (id) ON DELETE CASCADE,
+        sended_at TIMESTAMPTZ NOT NULL,
+        PRIMARY KEY (message_id, user_id)
+    );
+
+CREATE INDEX IF NOT EXISTS message_mentions_user_id_sended_at_idx ON message_mentions (user_id, sended_at DESC, message_id DESC);
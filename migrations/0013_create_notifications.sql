@@ -0,0 +1,143 @@
+-- Notificações das curtidas, respostas, menções e follows recebidos.
+-- message_id é a mensagem que agrupa as notificações: a mensagem curtida,
+-- a mensagem respondida ou a mensagem com a menção. source_id é a resposta.
+-- O tipo não aceita IF NOT EXISTS, então a duplicata é ignorada no bloco.
+DO $$
+BEGIN
+    CREATE TYPE notification_kind AS ENUM ('like', 'reply', 'mention', 'follow');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END;
+$$;
+
+CREATE TABLE
+    IF NOT EXISTS notifications (
+        id UUID PRIMARY KEY NOT NULL DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL REFERENCES rust_user (id) ON DELETE CASCADE,
+        kind notification_kind NOT NULL,
+        actor_id UUID NOT NULL REFERENCES rust_user (id) ON DELETE CASCADE,
+        message_id UUID REFERENCES messages (id) ON DELETE CASCADE,
+        source_id UUID REFERENCES messages (id) ON DELETE CASCADE,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        read_at TIMESTAMPTZ,
+        CHECK (user_id <> actor_id)
+    );
+
+-- Grupos do usuário (tipo e mensagem), lidos do mais novo para o mais antigo.
+CREATE INDEX IF NOT EXISTS notifications_user_id_kind_message_id_idx ON notifications (user_id, kind, message_id, created_at DESC, id DESC);
+
+CREATE INDEX IF NOT EXISTS notifications_actor_id_idx ON notifications (actor_id);
+
+CREATE INDEX IF NOT EXISTS notifications_message_id_idx ON notifications (message_id);
+
+CREATE INDEX IF NOT EXISTS notifications_source_id_idx ON notifications (source_id);
+
+-- Tipos registrados para cada usuário. Sem linha, todos são registrados.
+CREATE TABLE
+    IF NOT EXISTS notification_preferences (
+        user_id UUID PRIMARY KEY REFERENCES rust_user (id) ON DELETE CASCADE,
+        likes BOOLEAN NOT NULL DEFAULT TRUE,
+        replies BOOLEAN NOT NULL DEFAULT TRUE,
+        mentions BOOLEAN NOT NULL DEFAULT TRUE,
+        follows BOOLEAN NOT NULL DEFAULT TRUE
+    );
+
+-- Registrando a notificação quando o usuário não é o autor do evento
+-- e não desativou o tipo nas preferências.
+CREATE OR REPLACE FUNCTION add_notification(
+    recipient UUID,
+    actor UUID,
+    notification notification_kind,
+    message UUID,
+    source UUID
+) RETURNS VOID AS $$
+    INSERT INTO notifications (user_id, kind, actor_id, message_id, source_id)
+    SELECT recipient, notification, actor, message, source
+    WHERE recipient <> actor AND COALESCE((
+        SELECT CASE notification
+            WHEN 'like' THEN likes
+            WHEN 'reply' THEN replies
+            WHEN 'mention' THEN mentions
+            ELSE follows
+        END
+        FROM notification_preferences WHERE user_id = recipient
+    ), TRUE);
+$$ LANGUAGE sql;
+
+-- As notificações acompanham as curtidas, os follows e as menções,
+-- sendo removidas junto a eles.
+CREATE OR REPLACE FUNCTION message_likes_notification() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        PERFORM add_notification(user_id, NEW.user_id, 'like', id, NULL)
+        FROM messages WHERE id = NEW.message_id;
+    ELSE
+        DELETE FROM notifications
+        WHERE kind = 'like' AND message_id = OLD.message_id AND actor_id = OLD.user_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER message_likes_notification
+AFTER INSERT OR DELETE ON message_likes
+FOR EACH ROW EXECUTE FUNCTION message_likes_notification();
+
+CREATE OR REPLACE FUNCTION follows_notification() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        PERFORM add_notification(NEW.followed_id, NEW.follower_id, 'follow', NULL, NULL);
+    ELSE
+        DELETE FROM notifications
+        WHERE kind = 'follow' AND user_id = OLD.followed_id AND actor_id = OLD.follower_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER follows_notification
+AFTER INSERT OR DELETE ON follows
+FOR EACH ROW EXECUTE FUNCTION follows_notification();
+
+-- As menções das mensagens anteriores, ainda sem entidades, são
+-- indexadas ao iniciar sem gerar notificações.
+CREATE OR REPLACE FUNCTION message_mentions_notification() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        PERFORM add_notification(NEW.user_id, user_id, 'mention', id, NULL)
+        FROM messages WHERE id = NEW.message_id AND entities IS NOT NULL;
+    ELSE
+        DELETE FROM notifications
+        WHERE kind = 'mention' AND message_id = OLD.message_id AND user_id = OLD.user_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER message_mentions_notification
+AFTER INSERT OR DELETE ON message_mentions
+FOR EACH ROW EXECUTE FUNCTION message_mentions_notification();
+
+-- As respostas notificam o autor da mensagem respondida, e as
+-- notificações de uma mensagem que virou tombstone são removidas.
+CREATE OR REPLACE FUNCTION messages_notification() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        PERFORM add_notification(user_id, NEW.user_id, 'reply', id, NEW.id)
+        FROM messages WHERE id = NEW.parent_id;
+    ELSE
+        DELETE FROM notifications WHERE message_id = NEW.id OR source_id = NEW.id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER messages_reply_notification
+AFTER INSERT ON messages
+FOR EACH ROW WHEN (NEW.parent_id IS NOT NULL)
+EXECUTE FUNCTION messages_notification();
+
+CREATE TRIGGER messages_tombstone_notification
+AFTER UPDATE OF deleted_at ON messages
+FOR EACH ROW WHEN (OLD.deleted_at IS NULL AND NEW.deleted_at IS NOT NULL)
+EXECUTE FUNCTION messages_notification();
@@ -0,0 +1,37 @@
+-- Conversas privadas entre dois usuários ou em pequenos grupos. As
+-- conversas diretas guardam o par de usuários ordenado em direct_key,
+-- para que cada par tenha uma única conversa.
+CREATE TABLE
+    IF NOT EXISTS conversations (
+        id UUID PRIMARY KEY NOT NULL DEFAULT (uuid_generate_v4()),
+        direct_key VARCHAR(73) UNIQUE,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        last_message_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    );
+
+-- Participantes com a confirmação de leitura: as mensagens enviadas
+-- até last_read_at foram lidas pelo participante.
+CREATE TABLE
+    IF NOT EXISTS conversation_members (
+        conversation_id UUID NOT NULL REFERENCES conversations (id) ON DELETE CASCADE,
+        user_id UUID NOT NULL REFERENCES rust_user (id) ON DELETE CASCADE,
+        joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        last_read_at TIMESTAMPTZ,
+        PRIMARY KEY (conversation_id, user_id)
+    );
+
+CREATE INDEX IF NOT EXISTS conversation_members_user_id_idx ON conversation_members (user_id);
+
+-- Mensagens diretas, fora de messages para que nunca apareçam nas rotas públicas.
+CREATE TABLE
+    IF NOT EXISTS direct_messages (
+        id UUID PRIMARY KEY NOT NULL DEFAULT (uuid_generate_v4()),
+        conversation_id UUID NOT NULL REFERENCES conversations (id) ON DELETE CASCADE,
+        user_id UUID NOT NULL REFERENCES rust_user (id) ON DELETE CASCADE,
+        content TEXT NOT NULL,
+        sended_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    );
+
+CREATE INDEX IF NOT EXISTS direct_messages_conversation_id_sended_at_idx ON direct_messages (conversation_id, sended_at DESC, id DESC);
+
+CREATE INDEX IF NOT EXISTS direct_messages_user_id_idx ON direct_messages (user_id);
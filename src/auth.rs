@@ -0,0 +1,52 @@
+use actix_web::{dev::Payload, http::header::AUTHORIZATION, web::Data, FromRequest, HttpRequest};
+use std::future::{ready, Ready};
+use tracing::{field, Span};
+use uuid::Uuid;
+
+use crate::{error::AppError, AppState};
+
+// Extrator do usuário autenticado a partir do
+// cabeçalho "Authorization: Bearer <token>".
+pub struct AuthUser {
+    pub id: Uuid,
+}
+
+// Cria o erro de autenticação.
+fn unauthorized(message: &str) -> AppError {
+    AppError::Unauthorized(message.to_string())
+}
+
+// Resolve o usuário autenticado de uma requisição.
+pub fn authorize(req: &HttpRequest) -> Result<AuthUser, AppError> {
+    let header = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| unauthorized("Missing Authorization header!"))?;
+
+    let token = header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| unauthorized("The Authorization header must use the Bearer scheme!"))?;
+
+    let data = req
+        .app_data::<Data<AppState>>()
+        .expect("AppState must be registered in the App"); // Configuração feita em create::server.
+
+    match data.tokens.verify(token.trim()) {
+        Some(id) => {
+            // Identificando o usuário nos logs da requisição.
+            Span::current().record("user_id", field::display(id));
+            Ok(AuthUser { id })
+        }
+        None => Err(unauthorized("Invalid or expired access token!")),
+    }
+}
+
+impl FromRequest for AuthUser {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(authorize(req))
+    }
+}
@@ -0,0 +1,594 @@
+use std::{
+    collections::HashMap,
+    env,
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs,
+    str::FromStr,
+};
+use tracing::info;
+
+use crate::{migration, rate_limit, search, telemetry};
+
+// Chaves aceitas pela configuração. Nos arquivos TOML as chaves podem ser
+// agrupadas em tabelas ("[database] url" equivale a "database_url"), nas
+// variáveis de ambiente são escritas em maiúsculas ("DATABASE_URL") e nos
+// argumentos com hífens ("--database-url").
+const KEYS: &[&str] = &[
+    "address",
+    "unix_socket",
+    "workers",
+    "keep_alive",
+    "tls_cert",
+    "tls_key",
+    "tls_reload",
+    "database_url",
+    "database_max_connections",
+    "database_min_connections",
+    "database_acquire_timeout",
+    "database_idle_timeout",
+    "database_max_lifetime",
+    "argon2_memory_cost",
+    "argon2_time_cost",
+    "argon2_parallelism",
+    "token_secret",
+    "access_token_ttl",
+    "refresh_token_ttl",
+    "search_language",
+    "migrate",
+    "feed",
+    "shutdown_delay",
+    "shutdown_timeout",
+    "route_logs",
+    "metrics",
+    "rate_limit",
+    "rate_limit_auth",
+    "rate_limit_user",
+    "rate_limit_tech",
+    "rate_limit_trust_proxy",
+    "rate_limit_failures",
+    "rate_limit_backoff",
+    "rate_limit_backoff_max",
+    "log_format",
+    "log_level",
+];
+
+// Valores usados quando nenhuma outra camada define a chave.
+const DEFAULTS: &[(&str, &str)] = &[
+    ("address", "127.0.0.1:8080"),
+    ("unix_socket", ""),
+    ("workers", "0"),
+    ("keep_alive", "5"),
+    ("tls_cert", ""),
+    ("tls_key", ""),
+    ("tls_reload", "10"),
+    ("database_max_connections", "10"),
+    ("database_min_connections", "0"),
+    ("database_acquire_timeout", "30"),
+    ("database_idle_timeout", "600"),
+    ("database_max_lifetime", "1800"),
+    ("argon2_memory_cost", "19456"),
+    ("argon2_time_cost", "2"),
+    ("argon2_parallelism", "1"),
+    ("access_token_ttl", "900"),
+    ("refresh_token_ttl", "2592000"),
+    ("search_language", "portuguese"),
+    ("migrate", "apply"),
+    ("feed", "true"),
+    ("shutdown_delay", "5"),
+    ("shutdown_timeout", "30"),
+    ("route_logs", "true"),
+    ("metrics", "true"),
+    ("rate_limit", "true"),
+    ("rate_limit_auth", "10/60"),
+    ("rate_limit_user", "120/60"),
+    ("rate_limit_tech", "120/60"),
+    ("rate_limit_trust_proxy", "false"),
+    ("rate_limit_failures", "5"),
+    ("rate_limit_backoff", "1"),
+    ("rate_limit_backoff_max", "900"),
+    ("log_format", "human"),
+    ("log_level", "info,sqlx=warn"),
+];
+
+// Chaves secretas, que também podem ser lidas de um arquivo indicado
+// pela variante "_FILE" ("TOKEN_SECRET_FILE=/run/secrets/token").
+// Seus valores nunca aparecem nos logs.
+const SECRETS: &[&str] = &["database_url", "token_secret"];
+
+// Limite utilizado quando o valor configurado é inválido, apenas para
+// continuar validando as demais chaves.
+const DEFAULT_RATE: rate_limit::Rate = rate_limit::Rate {
+    requests: 1,
+    period: 1,
+};
+
+// Nomes aceitos pelas chaves que mudaram de nome.
+const ALIASES: &[(&str, &str)] = &[("max_connections", "database_max_connections")];
+
+// Camada de onde um valor foi carregado, da menor para a maior precedência.
+#[derive(Clone, Copy)]
+enum Source {
+    Default,
+    File,
+    Env,
+    Cli,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(match self {
+            Source::Default => "default",
+            Source::File => "file",
+            Source::Env => "env",
+            Source::Cli => "cli",
+        })
+    }
+}
+
+// Configuração completa do servidor.
+pub struct Config {
+    pub addresses: Vec<String>, // Endereços TCP, separados por vírgula em "address".
+    pub unix_socket: Option<String>, // Caminho do socket Unix, None desabilita.
+    pub workers: Option<usize>, // None utiliza a quantidade de núcleos.
+    pub keep_alive: Option<u64>, // Segundos, None desabilita.
+    pub tls: Option<Tls>,
+    pub database: Database,
+    pub argon2: Argon2,
+    pub token_secret: String,
+    pub access_token_ttl: i64,  // Segundos.
+    pub refresh_token_ttl: i64, // Segundos.
+    pub search_language: search::Language,
+    pub migrate: migration::Mode,
+    pub feed: bool,            // Habilita o feed em tempo real ("api/tech/ws" e SSE).
+    pub shutdown_delay: u64,   // Segundos indisponível antes de parar de aceitar conexões.
+    pub shutdown_timeout: u64, // Segundos aguardando as requisições em andamento.
+    pub route_logs: bool,      // Lista as rotas carregadas ao iniciar.
+    pub metrics: bool,         // Expõe as métricas do Prometheus em "/metrics".
+    pub rate_limit: Option<RateLimit>, // None desabilita o limite de requisições.
+    pub log_format: telemetry::Format,
+    pub log_level: String, // Filtro de níveis no formato do RUST_LOG.
+    loaded: Vec<Loaded>,
+}
+
+// Valor carregado, registrado nos logs após o tracing ser iniciado.
+struct Loaded {
+    key: String,
+    value: Option<String>, // None para os segredos.
+    source: Source,
+}
+
+// Certificado e chave privada em PEM utilizados pelo HTTPS.
+pub struct Tls {
+    pub cert: String,
+    pub key: String,
+    pub reload: Option<u64>, // Segundos entre as verificações dos arquivos, None desabilita.
+}
+
+// Limites de requisições por escopo de rotas, aplicados a cada endereço
+// IP e a cada usuário autenticado, e o bloqueio após falhas de autenticação.
+#[derive(Clone)]
+pub struct RateLimit {
+    pub auth: rate_limit::Rate,
+    pub user: rate_limit::Rate,
+    pub tech: rate_limit::Rate,
+    pub trust_proxy: bool, // Utiliza o endereço do cabeçalho X-Forwarded-For.
+    pub failures: u32,     // Falhas toleradas antes do bloqueio.
+    pub backoff: u64,      // Segundos do primeiro bloqueio, dobrados a cada nova falha.
+    pub backoff_max: u64,  // Segundos máximos de um bloqueio.
+}
+
+// Configuração do pool de conexões com o PostgreSQL.
+pub struct Database {
+    pub url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: u64,      // Segundos.
+    pub idle_timeout: Option<u64>, // Segundos, None desabilita.
+    pub max_lifetime: Option<u64>, // Segundos, None desabilita.
+}
+
+// Custos do Argon2id utilizados nos novos hashes de senha.
+pub struct Argon2 {
+    pub memory_cost: u32,
+    pub time_cost: u32,
+    pub parallelism: u32,
+}
+
+// Valores brutos de todas as camadas, junto aos erros encontrados.
+#[derive(Default)]
+struct Layers {
+    values: HashMap<String, (String, Source)>,
+    loaded: Vec<Loaded>,
+    errors: Vec<String>,
+}
+
+impl Layers {
+    // Define o valor de uma chave, validando o nome da chave.
+    fn set(&mut self, key: &str, value: String, source: Source, origin: &str) {
+        let key = ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == key)
+            .map_or(key, |(_, key)| key);
+
+        if let Some(secret) = key.strip_suffix("_file").filter(|k| SECRETS.contains(k)) {
+            match fs::read_to_string(&value) {
+                Ok(content) => {
+                    self.values
+                        .insert(secret.to_string(), (content.trim().to_string(), source));
+                }
+                Err(err) => self.errors.push(format!(
+                    "{}: cannot read the file '{}': {}",
+                    origin, value, err
+                )),
+            }
+        } else if KEYS.contains(&key) {
+            self.values.insert(key.to_string(), (value, source));
+        } else {
+            self.errors.push(format!("{}: unknown setting", origin));
+        }
+    }
+
+    // Carrega o arquivo TOML, achatando as tabelas em chaves com "_".
+    fn file(&mut self, path: &str) {
+        let table = match fs::read_to_string(path) {
+            Ok(content) => match content.parse::<toml::Table>() {
+                Ok(table) => table,
+                Err(err) => {
+                    self.errors
+                        .push(format!("{}: invalid TOML: {}", path, err.message()));
+                    return;
+                }
+            },
+            Err(err) => {
+                self.errors
+                    .push(format!("{}: cannot read the file: {}", path, err));
+                return;
+            }
+        };
+
+        self.table(path, "", table);
+    }
+
+    fn table(&mut self, path: &str, prefix: &str, table: toml::Table) {
+        for (name, value) in table {
+            let key = format!("{}{}", prefix, name);
+            let origin = format!("{}: '{}'", path, key);
+            match value {
+                toml::Value::Table(table) => self.table(path, &format!("{}_", key), table),
+                toml::Value::String(value) => self.set(&key, value, Source::File, &origin),
+                toml::Value::Integer(value) => {
+                    self.set(&key, value.to_string(), Source::File, &origin)
+                }
+                toml::Value::Boolean(value) => {
+                    self.set(&key, value.to_string(), Source::File, &origin)
+                }
+                _ => self
+                    .errors
+                    .push(format!("{}: expected a string, integer or boolean", origin)),
+            }
+        }
+    }
+
+    // Carrega as variáveis de ambiente das chaves conhecidas.
+    fn env(&mut self, vars: &HashMap<String, String>) {
+        // Os nomes antigos são lidos antes para que os novos tenham precedência.
+        for key in ALIASES.iter().map(|(alias, _)| alias).chain(KEYS) {
+            let name = key.to_uppercase();
+            if let Some(value) = vars.get(&name) {
+                self.set(
+                    key,
+                    value.clone(),
+                    Source::Env,
+                    &format!("@ENV('{}')", name),
+                );
+            }
+        }
+
+        for key in SECRETS {
+            let name = format!("{}_FILE", key.to_uppercase());
+            if let Some(value) = vars.get(&name) {
+                if vars.contains_key(&key.to_uppercase()) {
+                    self.errors.push(format!(
+                        "@ENV('{}'): cannot be used together with {}",
+                        name,
+                        key.to_uppercase()
+                    ));
+                }
+                self.set(
+                    &format!("{}_file", key),
+                    value.clone(),
+                    Source::Env,
+                    &format!("@ENV('{}')", name),
+                );
+            }
+        }
+    }
+
+    // Obtém o valor final de uma chave convertido para o tipo esperado.
+    fn get<T: FromStr>(&mut self, key: &str) -> Option<T> {
+        let (value, source) = match self.values.get(key) {
+            Some(entry) => entry.clone(),
+            None => {
+                self.errors.push(format!("'{}': must be set", key));
+                return None;
+            }
+        };
+
+        self.loaded.push(Loaded {
+            key: key.to_string(),
+            value: (!SECRETS.contains(&key)).then(|| value.clone()),
+            source,
+        });
+
+        match value.trim().parse::<T>() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                self.errors
+                    .push(format!("'{}' ({}): invalid value '{}'", key, source, value));
+                None
+            }
+        }
+    }
+
+    // Registra um erro de validação quando a condição não é atendida.
+    fn check(&mut self, valid: bool, message: &str) {
+        if !valid {
+            self.errors.push(message.to_string());
+        }
+    }
+}
+
+// Argumentos da linha de comando, com o arquivo de configuração informado.
+struct Args {
+    config: Option<String>,
+    values: Vec<(String, String)>,
+    errors: Vec<String>,
+}
+
+// Método para ler os argumentos "--chave valor", "--chave=valor"
+// e os atalhos de migração "--migrate-only" e "--no-migrate".
+fn args(args: impl IntoIterator<Item = String>) -> Args {
+    let mut parsed = Args {
+        config: None,
+        values: Vec::new(),
+        errors: Vec::new(),
+    };
+    let mut migrate: Option<&str> = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let shortcut = match arg.as_str() {
+            "--migrate-only" => Some("only"),
+            "--no-migrate" => Some("skip"),
+            _ => None,
+        };
+        if let Some(mode) = shortcut {
+            if migrate.is_some_and(|current| current != mode) {
+                parsed
+                    .errors
+                    .push("--migrate-only and --no-migrate cannot be used together".to_string());
+            }
+            migrate = Some(mode);
+            continue;
+        }
+
+        let name = match arg.strip_prefix("--") {
+            Some(name) => name,
+            None => {
+                parsed.errors.push(format!("unknown argument '{}'", arg));
+                continue;
+            }
+        };
+
+        let (name, value) = match name.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (name.to_string(), args.next()),
+        };
+        let value = match value {
+            Some(value) => value,
+            None => {
+                parsed.errors.push(format!("--{}: missing value", name));
+                continue;
+            }
+        };
+
+        match name.as_str() {
+            "config" => parsed.config = Some(value),
+            _ => parsed.values.push((name.replace('-', "_"), value)),
+        }
+    }
+
+    if let Some(mode) = migrate {
+        parsed
+            .values
+            .push(("migrate".to_string(), mode.to_string()));
+    }
+
+    parsed
+}
+
+impl Config {
+    // Método para carregar a configuração das camadas padrão, arquivo TOML
+    // ("--config" ou CONFIG_FILE), variáveis de ambiente e argumentos,
+    // nessa ordem de precedência. Todos os erros encontrados são
+    // retornados juntos.
+    pub fn load() -> Result<Config, Vec<String>> {
+        // Variáveis com nome ou valor fora do UTF-8 são ignoradas.
+        let vars = env::vars_os()
+            .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+            .collect();
+
+        Config::from_sources(env::args().skip(1), &vars)
+    }
+
+    // Método para carregar a configuração a partir dos argumentos e das
+    // variáveis de ambiente informados.
+    pub fn from_sources(
+        arguments: impl IntoIterator<Item = String>,
+        vars: &HashMap<String, String>,
+    ) -> Result<Config, Vec<String>> {
+        let args = args(arguments);
+        let mut layers = Layers {
+            errors: args.errors,
+            ..Layers::default()
+        };
+
+        for (key, value) in DEFAULTS {
+            layers
+                .values
+                .insert(key.to_string(), (value.to_string(), Source::Default));
+        }
+
+        if let Some(path) = args.config.or_else(|| vars.get("CONFIG_FILE").cloned()) {
+            layers.file(&path);
+        }
+
+        layers.env(vars);
+
+        for (key, value) in args.values {
+            let origin = format!("--{}", key.replace('_', "-"));
+            layers.set(&key, value, Source::Cli, &origin);
+        }
+
+        let config = Config {
+            addresses: layers
+                .get::<String>("address")
+                .unwrap_or_default()
+                .split(',')
+                .map(|address| address.trim().to_string())
+                .filter(|address| !address.is_empty())
+                .collect(),
+            unix_socket: layers
+                .get::<String>("unix_socket")
+                .filter(|path| !path.is_empty()),
+            workers: layers.get::<usize>("workers").filter(|&n| n > 0),
+            keep_alive: layers.get::<u64>("keep_alive").filter(|&s| s > 0),
+            tls: match (
+                layers.get::<String>("tls_cert").unwrap_or_default(),
+                layers.get::<String>("tls_key").unwrap_or_default(),
+                layers.get::<u64>("tls_reload"),
+            ) {
+                (cert, key, reload) if !cert.is_empty() && !key.is_empty() => Some(Tls {
+                    cert,
+                    key,
+                    reload: reload.filter(|&s| s > 0),
+                }),
+                (cert, key, _) => {
+                    layers.check(
+                        cert.is_empty() && key.is_empty(),
+                        "'tls_cert' and 'tls_key': must be set together",
+                    );
+                    None
+                }
+            },
+            database: Database {
+                url: layers.get("database_url").unwrap_or_default(),
+                max_connections: layers.get("database_max_connections").unwrap_or(1),
+                min_connections: layers.get("database_min_connections").unwrap_or_default(),
+                acquire_timeout: layers.get("database_acquire_timeout").unwrap_or(1),
+                idle_timeout: layers
+                    .get::<u64>("database_idle_timeout")
+                    .filter(|&s| s > 0),
+                max_lifetime: layers
+                    .get::<u64>("database_max_lifetime")
+                    .filter(|&s| s > 0),
+            },
+            argon2: Argon2 {
+                memory_cost: layers.get("argon2_memory_cost").unwrap_or_default(),
+                time_cost: layers.get("argon2_time_cost").unwrap_or_default(),
+                parallelism: layers.get("argon2_parallelism").unwrap_or_default(),
+            },
+            token_secret: layers.get("token_secret").unwrap_or_default(),
+            access_token_ttl: layers.get("access_token_ttl").unwrap_or(1),
+            refresh_token_ttl: layers.get("refresh_token_ttl").unwrap_or(1),
+            search_language: layers
+                .get("search_language")
+                .unwrap_or(search::Language::Portuguese),
+            migrate: layers.get("migrate").unwrap_or(migration::Mode::Apply),
+            feed: layers.get("feed").unwrap_or(true),
+            shutdown_delay: layers.get("shutdown_delay").unwrap_or_default(),
+            shutdown_timeout: layers.get("shutdown_timeout").unwrap_or_default(),
+            route_logs: layers.get("route_logs").unwrap_or(true),
+            metrics: layers.get("metrics").unwrap_or(true),
+            rate_limit: {
+                let rate_limit = RateLimit {
+                    auth: layers.get("rate_limit_auth").unwrap_or(DEFAULT_RATE),
+                    user: layers.get("rate_limit_user").unwrap_or(DEFAULT_RATE),
+                    tech: layers.get("rate_limit_tech").unwrap_or(DEFAULT_RATE),
+                    trust_proxy: layers.get("rate_limit_trust_proxy").unwrap_or_default(),
+                    failures: layers.get("rate_limit_failures").unwrap_or_default(),
+                    backoff: layers.get("rate_limit_backoff").unwrap_or(1),
+                    backoff_max: layers.get("rate_limit_backoff_max").unwrap_or(1),
+                };
+                layers
+                    .get("rate_limit")
+                    .unwrap_or(true)
+                    .then_some(rate_limit)
+            },
+            log_format: layers.get("log_format").unwrap_or(telemetry::Format::Human),
+            log_level: layers.get("log_level").unwrap_or_default(),
+            loaded: Vec::new(),
+        };
+
+        // Validando as regras que dependem de mais de um valor.
+        layers.check(
+            !config.addresses.is_empty() || config.unix_socket.is_some(),
+            "'address': cannot be empty without an 'unix_socket'",
+        );
+        layers.check(
+            cfg!(unix) || config.unix_socket.is_none(),
+            "'unix_socket': not supported on this platform",
+        );
+        layers.check(
+            config.database.max_connections > 0,
+            "'database_max_connections': must be at least 1",
+        );
+        layers.check(
+            config.database.min_connections <= config.database.max_connections,
+            "'database_min_connections': cannot be bigger than database_max_connections",
+        );
+        layers.check(
+            config.database.acquire_timeout > 0,
+            "'database_acquire_timeout': must be at least 1 second",
+        );
+        layers.check(
+            config.access_token_ttl > 0 && config.refresh_token_ttl > 0,
+            "'access_token_ttl' and 'refresh_token_ttl': must be at least 1 second",
+        );
+        layers.check(
+            !config.token_secret.is_empty() || !layers.values.contains_key("token_secret"),
+            "'token_secret': cannot be empty",
+        );
+
+        if let Some(rate_limit) = &config.rate_limit {
+            layers.check(
+                rate_limit.backoff > 0 && rate_limit.backoff <= rate_limit.backoff_max,
+                "'rate_limit_backoff': must be between 1 and rate_limit_backoff_max",
+            );
+        }
+        layers.check(
+            telemetry::valid_level(&config.log_level),
+            "'log_level': invalid filter, expected something like \"info,sqlx=warn\"",
+        );
+
+        match layers.errors.is_empty() {
+            true => Ok(Config {
+                loaded: layers.loaded,
+                ..config
+            }),
+            false => Err(layers.errors),
+        }
+    }
+
+    // Método para registrar nos logs a origem de cada valor carregado.
+    pub fn log(&self) {
+        for loaded in &self.loaded {
+            match &loaded.value {
+                Some(value) => {
+                    info!(key = %loaded.key, value = %value, source = %loaded.source, "setting loaded")
+                }
+                None => info!(key = %loaded.key, source = %loaded.source, "secret setting loaded"),
+            }
+        }
+    }
+}
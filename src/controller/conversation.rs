@@ -0,0 +1,149 @@
+use actix_web::{
+    get, post,
+    web::{Data, Json, Path, Query},
+    Responder,
+};
+
+use crate::{auth::AuthUser, model, response, AppState};
+
+#[utoipa::path(
+    tag = "dm",
+    summary = "Start a conversation with other users.",
+    description = "With a single user it is the direct conversation of both, returned with 200 when it exists.",
+    request_body = model::Conversation,
+    security(("bearer" = [])),
+    responses(
+        (status = 201, body = response::Success<response::Conversation>),
+        (status = 200, body = response::Success<response::Conversation>),
+        (status = 400),
+        (status = 404),
+        (status = 422),
+    )
+)]
+#[post("/conversations")] // Rota POST para criar uma conversa via JSON Conversation no body.
+pub async fn post_conversation(
+    user: AuthUser,
+    body: Json<model::Conversation>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::conversation::add_conversation(user, body, data).await
+}
+
+#[utoipa::path(
+    tag = "dm",
+    summary = "Return the conversations of the authenticated user, by the newest message.",
+    params(model::Page),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<Vec<response::Conversation>>),
+        (status = 400),
+    )
+)]
+#[get("/conversations")] // Rota GET para obter as conversas do usuário autenticado.
+pub async fn get_conversations(
+    user: AuthUser,
+    query: Query<model::Page>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::conversation::get_conversations(user, query, data).await
+}
+
+#[utoipa::path(
+    tag = "dm",
+    summary = "Return one conversation of the authenticated user with the read receipts.",
+    params(("id" = uuid::Uuid, Path)),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<response::Conversation>),
+        (status = 400),
+        (status = 403),
+        (status = 404),
+    )
+)]
+#[get("/conversations/{id}")] // Rota GET para obter uma conversa com as confirmações de leitura.
+pub async fn get_conversation(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::conversation::get_conversation(user, path, data).await
+}
+
+#[utoipa::path(
+    tag = "dm",
+    summary = "Return the messages of a conversation, newest first.",
+    params(("id" = uuid::Uuid, Path), model::Page),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<Vec<response::DirectMessage>>),
+        (status = 400),
+        (status = 403),
+        (status = 404),
+    )
+)]
+#[get("/conversations/{id}/messages")] // Rota GET para obter as mensagens de uma conversa.
+pub async fn get_direct_messages(
+    user: AuthUser,
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::conversation::get_direct_messages(user, path, query, data).await
+}
+
+#[utoipa::path(
+    tag = "dm",
+    summary = "Send a message to a conversation of the authenticated user.",
+    params(("id" = uuid::Uuid, Path)),
+    request_body = model::DirectMessage,
+    security(("bearer" = [])),
+    responses(
+        (status = 201, body = response::Success<response::DirectMessage>),
+        (status = 400),
+        (status = 403),
+        (status = 404),
+    )
+)]
+#[post("/conversations/{id}/messages")] // Rota POST para enviar uma mensagem via JSON DirectMessage no body.
+pub async fn post_direct_message(
+    user: AuthUser,
+    path: Path<String>,
+    body: Json<model::DirectMessage>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::conversation::add_direct_message(user, path, body, data).await
+}
+
+#[utoipa::path(
+    tag = "dm",
+    summary = "Mark a conversation as read up to its newest message.",
+    params(("id" = uuid::Uuid, Path)),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<response::Conversation>),
+        (status = 400),
+        (status = 403),
+        (status = 404),
+    )
+)]
+#[post("/conversations/{id}/read")] // Rota POST para marcar uma conversa como lida.
+pub async fn read_conversation(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::conversation::read_conversation(user, path, data).await
+}
+
+#[utoipa::path(
+    tag = "dm",
+    summary = "Return the direct messages not read by the authenticated user.",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<response::Unread>),
+    )
+)]
+#[get("/unread")] // Rota GET para obter a quantidade de mensagens diretas não lidas.
+pub async fn get_unread(user: AuthUser, data: Data<AppState>) -> impl Responder {
+    crate::service::conversation::get_unread(user, data).await
+}
@@ -0,0 +1,39 @@
+use actix_web::{get, web::Path, Responder};
+
+#[utoipa::path(
+    tag = "docs",
+    summary = "Return this OpenAPI document.",
+    responses(
+        (status = 200, content_type = "application/json", body = Object),
+    )
+)]
+#[get("/openapi.json")] // Rota GET para obter o documento OpenAPI da api.
+pub async fn get_openapi() -> impl Responder {
+    crate::service::docs::get_openapi().await
+}
+
+#[utoipa::path(
+    tag = "docs",
+    summary = "Return the interactive UI of this document.",
+    responses(
+        (status = 200, content_type = "text/html", body = String),
+    )
+)]
+#[get("/docs")] // Rota GET para obter a interface interativa do documento OpenAPI.
+pub async fn get_docs() -> impl Responder {
+    crate::service::docs::get_docs().await
+}
+
+#[utoipa::path(
+    tag = "docs",
+    summary = "Return a file of the bundled Swagger UI.",
+    params(("file" = String, Path, example = "swagger-ui-bundle.js")),
+    responses(
+        (status = 200, content((String = "text/css"), (String = "text/javascript"))),
+        (status = 404),
+    )
+)]
+#[get("/swagger-ui/{file}")] // Rota GET para obter um arquivo do Swagger UI embutido no binário.
+pub async fn get_swagger_ui_asset(path: Path<String>) -> impl Responder {
+    crate::service::docs::get_swagger_ui_asset(path).await
+}
@@ -0,0 +1,87 @@
+use actix_web::{
+    delete, get, post,
+    web::{Data, Path, Query},
+    Responder,
+};
+
+use crate::{auth::AuthUser, model, response, AppState};
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Follow a user as the authenticated user.",
+    params(("id" = uuid::Uuid, Path)),
+    security(("bearer" = [])),
+    responses(
+        (status = 201, body = response::Success<response::User>),
+        (status = 400),
+        (status = 404),
+        (status = 409),
+        (status = 422),
+    )
+)]
+#[post("/follow/{id}")] // Rota POST para seguir um usuário com o usuário autenticado.
+pub async fn follow_user(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::follow::follow_user(user, path, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Unfollow a user as the authenticated user.",
+    params(("id" = uuid::Uuid, Path)),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<response::User>),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[delete("/follow/{id}")] // Rota DELETE para deixar de seguir um usuário.
+pub async fn unfollow_user(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::follow::unfollow_user(user, path, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Return the followers of a user, newest first.",
+    params(("id" = uuid::Uuid, Path), model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<response::Follow>>),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[get("/followers/{id}")] // Rota GET para obter os seguidores de um usuário.
+pub async fn get_followers(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::follow::get_followers(path, query, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Return the users followed by a user, newest first.",
+    params(("id" = uuid::Uuid, Path), model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<response::Follow>>),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[get("/following/{id}")] // Rota GET para obter os usuários seguidos por um usuário.
+pub async fn get_following(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::follow::get_following(path, query, data).await
+}
@@ -0,0 +1,28 @@
+use actix_web::{get, web::Data, Responder};
+
+use crate::{response, AppState};
+
+#[utoipa::path(
+    tag = "health",
+    summary = "Return whether the process is up.",
+    responses(
+        (status = 200, body = response::Success<String>),
+    )
+)]
+#[get("/live")] // Rota GET para verificar se o processo está de pé.
+pub async fn live() -> impl Responder {
+    crate::service::health::live().await
+}
+
+#[utoipa::path(
+    tag = "health",
+    summary = "Return whether the server can take requests.",
+    responses(
+        (status = 200, body = response::Success<response::Health>),
+        (status = 503, body = response::Success<response::Health>),
+    )
+)]
+#[get("/ready")] // Rota GET para verificar se o servidor pode receber requisições.
+pub async fn ready(data: Data<AppState>) -> impl Responder {
+    crate::service::health::ready(data).await
+}
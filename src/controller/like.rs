@@ -0,0 +1,68 @@
+use actix_web::{
+    delete, get, post,
+    web::{Data, Path, Query},
+    Responder,
+};
+
+use crate::{auth::AuthUser, model, response, schema, AppState};
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Like a message as the authenticated user.",
+    params(("id" = uuid::Uuid, Path)),
+    security(("bearer" = [])),
+    responses(
+        (status = 201, body = response::Success<schema::Message>),
+        (status = 400),
+        (status = 404),
+        (status = 409),
+        (status = 422),
+    )
+)]
+#[post("/like/{id}")] // Rota POST para curtir uma mensagem com o usuário autenticado.
+pub async fn like_message(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::like::like_message(user, path, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Remove the like of the authenticated user.",
+    params(("id" = uuid::Uuid, Path)),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<schema::Message>),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[delete("/like/{id}")] // Rota DELETE para remover a curtida do usuário autenticado.
+pub async fn unlike_message(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::like::unlike_message(user, path, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Return the users who liked a message, newest first.",
+    params(("id" = uuid::Uuid, Path), model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<response::Like>>),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[get("/likes/{id}")] // Rota GET para obter os usuários que curtiram uma mensagem.
+pub async fn get_message_likes(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::like::get_message_likes(path, query, data).await
+}
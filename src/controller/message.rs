@@ -1,38 +1,277 @@
 use actix_web::{
-    get, post,
-    web::{Data, Json, Path},
-    Responder,
+    delete, get, patch, post,
+    web::{Data, Json, Path, Payload, Query},
+    HttpRequest, Responder,
 };
 
-use crate::model;
+use crate::{auth::AuthUser, model, response, schema};
 
+#[utoipa::path(
+    tag = "tech",
+    summary = "Return all messages, newest first.",
+    params(model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<schema::Message>>),
+        (status = 400),
+    )
+)]
 #[get("/all")] // Rota GET para obter todas as mensagens.
-pub async fn get_all_messages(data: Data<crate::AppState>) -> impl Responder {
-    crate::service::message::get_all_messages(data).await
+pub async fn get_all_messages(
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::get_all_messages(query, data).await
 }
 
+#[utoipa::path(
+    tag = "tech",
+    summary = "Return one message by id.",
+    params(("id" = uuid::Uuid, Path)),
+    responses(
+        (status = 200, body = response::Success<schema::Message>),
+        (status = 400),
+        (status = 404),
+    )
+)]
 #[get("/one/{id}")] // Rota GET para obter uma mensagem pelo id.
 pub async fn get_one_message(path: Path<String>, data: Data<crate::AppState>) -> impl Responder {
     crate::service::message::get_message_by_id(path, data).await
 }
 
+#[utoipa::path(
+    tag = "tech",
+    summary = "Return the messages whose content contains the text.",
+    params(("content" = String, Path), model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<schema::Message>>),
+        (status = 400),
+        (status = 404),
+    )
+)]
 #[get("/some/{content}")] // Rota GET para obter algumas mensagens pelo conteudo.
-pub async fn get_some_messages(path: Path<String>, data: Data<crate::AppState>) -> impl Responder {
-    crate::service::message::get_messages_by_content(path, data).await
+pub async fn get_some_messages(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::get_messages_by_content(path, query, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Full-text search ordered by relevance.",
+    description = "The query accepts words, \"phrases\", prefix*, -word and or. Without a language, the configured one is used.",
+    params(model::Search, model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<response::SearchResult>>),
+        (status = 400),
+    )
+)]
+#[get("/search")] // Rota GET para buscar mensagens por texto completo.
+pub async fn search_messages(
+    search: Query<model::Search>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::search_messages(search, query, data).await
 }
 
+#[utoipa::path(
+    tag = "tech",
+    summary = "Return the messages of a user, newest first.",
+    params(("user_id" = uuid::Uuid, Path), model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<schema::Message>>),
+        (status = 400),
+        (status = 404),
+    )
+)]
 #[get("/from/{user_id}")] // Rota GET para obter todas as mensagens de um usuário.
 pub async fn get_messages_from_user(
     path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::get_messages_from_user_id(path, query, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Return the messages with a hashtag, with or without the #, newest first.",
+    params(("tag" = String, Path, max_length = 101), model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<schema::Message>>),
+        (status = 400),
+    )
+)]
+#[get("/tag/{tag}")] // Rota GET para obter as mensagens com uma hashtag.
+pub async fn get_tag_messages(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::get_messages_by_tag(path, query, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Return the messages that mention a user, newest first.",
+    params(("user_id" = uuid::Uuid, Path), model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<schema::Message>>),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[get("/mentions/{user_id}")] // Rota GET para obter as mensagens que mencionam um usuário.
+pub async fn get_mentions(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::get_mentions(path, query, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Return the messages of the followed users, newest first.",
+    params(model::Page),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<Vec<schema::Message>>),
+        (status = 400),
+    )
+)]
+#[get("/feed")] // Rota GET para obter a linha do tempo do usuário autenticado.
+pub async fn get_home_feed(
+    user: AuthUser,
+    query: Query<model::Page>,
     data: Data<crate::AppState>,
 ) -> impl Responder {
-    crate::service::message::get_messages_from_user_id(path, data).await
+    crate::service::message::get_home_timeline(user, query, data).await
 }
 
-#[post("/manage")] // Rota POST para asdicionar uma mensagem com autênticação.
+#[utoipa::path(
+    tag = "tech",
+    summary = "Add a message of the authenticated user, optionally as a reply.",
+    request_body = model::Message,
+    security(("bearer" = [])),
+    responses(
+        (status = 201, body = response::Success<schema::Message>),
+        (status = 400),
+        (status = 404),
+        (status = 422),
+    )
+)]
+#[post("/manage")] // Rota POST para adicionar uma mensagem do usuário autenticado via Bearer token.
 pub async fn post_message(
+    user: AuthUser,
     body: Json<model::Message>,
     data: Data<crate::AppState>,
 ) -> impl Responder {
-    crate::service::message::add_message_by_model(body, data).await
+    crate::service::message::add_message_by_model(user, body, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Open the WebSocket feed of new messages.",
+    description = "With global, every new message is sent. With user_id, only the new messages of that user.",
+    params(model::Feed),
+    responses(
+        (status = 101, description = "Switching Protocols"),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[get("/ws")] // Rota GET para abrir a conexão WebSocket do feed de mensagens novas.
+pub async fn open_feed(
+    req: HttpRequest,
+    stream: Payload,
+    query: Query<model::Feed>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::open_feed(req, stream, query, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Open the Server-Sent Events stream of new messages, likes and deletions.",
+    description = "Each event has an increasing id, and reconnecting with the Last-Event-ID header, or the last_event_id parameter, replays the missed events still retained, or sends a reset event. The user_id and tag parameters only keep the events of the messages of a user or with a hashtag.",
+    params(model::Stream),
+    responses(
+        (status = 200, content_type = "text/event-stream", body = String),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[get("/stream")] // Rota GET para abrir a conexão Server-Sent Events dos eventos das mensagens.
+pub async fn open_stream(
+    req: HttpRequest,
+    query: Query<model::Stream>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::open_stream(req, query, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Edit a message of the authenticated user, keeping its history.",
+    params(("id" = uuid::Uuid, Path)),
+    request_body = model::MessageEdit,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<schema::Message>),
+        (status = 400),
+        (status = 403),
+        (status = 404),
+    )
+)]
+#[patch("/manage/{id}")] // Rota PATCH para editar uma mensagem do usuário autenticado.
+pub async fn patch_message(
+    user: AuthUser,
+    path: Path<String>,
+    body: Json<model::MessageEdit>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::edit_message(user, path, body, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Delete a message of the authenticated user. With replies it stays as a tombstone.",
+    params(("id" = uuid::Uuid, Path)),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<String>),
+        (status = 400),
+        (status = 403),
+        (status = 404),
+    )
+)]
+#[delete("/manage/{id}")] // Rota DELETE para remover uma mensagem do usuário autenticado.
+pub async fn delete_message(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::delete_message(user, path, data).await
+}
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Return the edit history of a message, newest first.",
+    params(("id" = uuid::Uuid, Path), model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<schema::MessageRevision>>),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[get("/history/{id}")] // Rota GET para obter o histórico de edições de uma mensagem.
+pub async fn get_message_history(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> impl Responder {
+    crate::service::message::get_message_history(path, query, data).await
 }
@@ -0,0 +1,17 @@
+use actix_web::{get, web::Data, Responder};
+
+use crate::AppState;
+
+#[utoipa::path(
+    tag = "metrics",
+    summary = "Return the metrics in the Prometheus text format.",
+    responses(
+        (status = 200, content_type = "text/plain", body = String),
+        (status = 404),
+        (status = 500),
+    )
+)]
+#[get("")] // Rota GET para obter as métricas no formato do Prometheus.
+pub async fn get_metrics(data: Data<AppState>) -> impl Responder {
+    crate::service::metrics::get_metrics(data).await
+}
@@ -1,2 +1,10 @@
+pub mod conversation; // Exportando o controlador de ("api/dm").
+pub mod docs; // Exportando o controlador da documentação em ("api").
+pub mod follow; // Exportando o controlador de follows em ("api/user").
+pub mod health; // Exportando o controlador de ("health").
+pub mod like; // Exportando o controlador de curtidas em ("api/tech").
 pub mod message; // Exportando o controlador de ("api/tech").
+pub mod metrics; // Exportando o controlador de ("metrics").
+pub mod notification; // Exportando o controlador de notificações em ("api/user").
+pub mod thread; // Exportando o controlador de conversas em ("api/tech").
 pub mod user; // Exportando o controlador de ("api/user").
@@ -0,0 +1,92 @@
+use actix_web::{
+    get, patch, post,
+    web::{Data, Json, Path, Query},
+    Responder,
+};
+
+use crate::{auth::AuthUser, model, response, AppState};
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Return the notifications of the authenticated user grouped by kind and message, newest first.",
+    params(model::Notifications, model::Page),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<Vec<response::Notification>>),
+        (status = 400),
+    )
+)]
+#[get("/notifications")] // Rota GET para obter as notificações do usuário autenticado.
+pub async fn get_notifications(
+    user: AuthUser,
+    filter: Query<model::Notifications>,
+    query: Query<model::Page>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::notification::get_notifications(user, filter, query, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Mark a notification and the older ones of its group as read, returning how many were marked.",
+    params(("id" = uuid::Uuid, Path)),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<i64>),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[post("/notifications/read/{id}")] // Rota POST para marcar um grupo de notificações como lido.
+pub async fn read_notification(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::notification::read_notification(user, path, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Mark every notification as read, returning how many were marked.",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<i64>),
+    )
+)]
+#[post("/notifications/read")] // Rota POST para marcar todas as notificações como lidas.
+pub async fn read_all_notifications(user: AuthUser, data: Data<AppState>) -> impl Responder {
+    crate::service::notification::read_all_notifications(user, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Return the notification kinds recorded for the authenticated user.",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<response::NotificationPreferences>),
+    )
+)]
+#[get("/notifications/preferences")] // Rota GET para obter as preferências de notificação.
+pub async fn get_notification_preferences(user: AuthUser, data: Data<AppState>) -> impl Responder {
+    crate::service::notification::get_preferences(user, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Choose the notification kinds recorded for the authenticated user.",
+    request_body = model::NotificationPreferences,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<response::NotificationPreferences>),
+        (status = 422),
+    )
+)]
+#[patch("/notifications/preferences")] // Rota PATCH para alterar as preferências via JSON NotificationPreferences no body.
+pub async fn patch_notification_preferences(
+    user: AuthUser,
+    body: Json<model::NotificationPreferences>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::notification::update_preferences(user, body, data).await
+}
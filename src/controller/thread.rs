@@ -0,0 +1,28 @@
+use actix_web::{
+    get,
+    web::{Data, Path, Query},
+    Responder,
+};
+
+use crate::{model, response, AppState};
+
+#[utoipa::path(
+    tag = "tech",
+    summary = "Return a message with its replies as a tree. The page is of the direct replies.",
+    description = "The depth is the levels of replies, with up to 5 replies of each nested reply.",
+    params(("id" = uuid::Uuid, Path), model::Thread, model::Page),
+    responses(
+        (status = 200, body = response::Success<response::Thread>),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[get("/thread/{id}")] // Rota GET para obter a conversa de uma mensagem em árvore.
+pub async fn get_thread(
+    path: Path<String>,
+    thread: Query<model::Thread>,
+    query: Query<model::Page>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::thread::get_thread(path, thread, query, data).await
+}
@@ -1,31 +1,167 @@
-use crate::{model, schema, AppState};
+use crate::{auth::AuthUser, model, response, AppState};
 use actix_web::{
-    delete, get, post,
-    web::{Data, Json, Path},
+    delete, get, patch, post,
+    web::{Data, Json, Path, Query},
     Responder,
 };
 
+#[utoipa::path(
+    tag = "user",
+    summary = "Return all users ordered by name.",
+    params(model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<response::User>>),
+        (status = 400),
+    )
+)]
 #[get("/all")] // Rota GET para obter todos os usuários.
-pub async fn get_all_users(data: Data<AppState>) -> impl Responder {
-    crate::service::user::get_all_users(data).await
+pub async fn get_all_users(query: Query<model::Page>, data: Data<AppState>) -> impl Responder {
+    crate::service::user::get_all_users(query, data).await
 }
 
+#[utoipa::path(
+    tag = "user",
+    summary = "Return one user by id.",
+    params(("id" = uuid::Uuid, Path)),
+    responses(
+        (status = 200, body = response::Success<response::User>),
+        (status = 400),
+        (status = 404),
+    )
+)]
 #[get("/one/{id}")] // Rota GET para obter uma pesquisa de usuário via Uuid.
 pub async fn get_one_user(path: Path<String>, data: Data<AppState>) -> impl Responder {
     crate::service::user::find_user_by_id(path, data).await
 }
 
+#[utoipa::path(
+    tag = "user",
+    summary = "Return the users whose name contains the text.",
+    params(("name" = String, Path), model::Page),
+    responses(
+        (status = 200, body = response::Success<Vec<response::User>>),
+        (status = 400),
+        (status = 404),
+    )
+)]
 #[get("/some/{name}")] // Rota GET para obter uma pesquisa de assemelhação de usuários via name.
-pub async fn get_some_users(path: Path<String>, data: Data<AppState>) -> impl Responder {
-    crate::service::user::find_users_by_name(path, data).await
+pub async fn get_some_users(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::user::find_users_by_name(path, query, data).await
 }
 
+#[utoipa::path(
+    tag = "user",
+    summary = "Add one user.",
+    request_body = model::User,
+    responses(
+        (status = 201, body = response::Success<response::User>),
+        (status = 406),
+        (status = 409),
+    )
+)]
 #[post("/manage")] // Rota POST para adicionar usuário via JSON UserModel no body.
 pub async fn post_one_user(body: Json<model::User>, data: Data<AppState>) -> impl Responder {
     crate::service::user::add_user_by_model(body, data).await
 }
 
-#[delete("/manage")] // Rota POST para remover usuário via autenticação em JSON UserSchema em body.
-pub async fn delete_one_user(body: Json<schema::User>, data: Data<AppState>) -> impl Responder {
-    crate::service::user::delete_user_by_schema(body, data).await
+#[utoipa::path(
+    tag = "user",
+    summary = "Update the profile of the authenticated user.",
+    request_body = model::UserUpdate,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<response::Profile>),
+        (status = 400),
+        (status = 404),
+        (status = 406),
+        (status = 409),
+    )
+)]
+#[patch("/manage")] // Rota PATCH para alterar o perfil do usuário autenticado via JSON UserUpdate no body.
+pub async fn patch_one_user(
+    user: AuthUser,
+    body: Json<model::UserUpdate>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::user::update_user(user, body, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Return the profile of one user with their counts.",
+    params(("id" = uuid::Uuid, Path)),
+    responses(
+        (status = 200, body = response::Success<response::Profile>),
+        (status = 400),
+        (status = 404),
+    )
+)]
+#[get("/profile/{id}")] // Rota GET para obter o perfil de um usuário com as quantidades de mensagens e curtidas.
+pub async fn get_profile(path: Path<String>, data: Data<AppState>) -> impl Responder {
+    crate::service::user::get_profile(path, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Delete the authenticated user and everything they own.",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<String>),
+        (status = 404),
+    )
+)]
+#[delete("/manage")] // Rota DELETE para remover o usuário autenticado via Bearer token.
+pub async fn delete_one_user(user: AuthUser, data: Data<AppState>) -> impl Responder {
+    crate::service::user::delete_user(user, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Return new tokens for the name and password.",
+    request_body = model::Login,
+    responses(
+        (status = 200, body = response::Success<response::Session>),
+        (status = 401),
+    )
+)]
+#[post("/login")] // Rota POST para iniciar uma sessão via JSON LoginModel no body.
+pub async fn login(body: Json<model::Login>, data: Data<AppState>) -> impl Responder {
+    crate::service::user::login(body, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Revoke one refresh token of the authenticated user.",
+    request_body = model::Refresh,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, body = response::Success<String>),
+        (status = 404),
+    )
+)]
+#[post("/logout")] // Rota POST para revogar o token de atualização do usuário autenticado.
+pub async fn logout(
+    user: AuthUser,
+    body: Json<model::Refresh>,
+    data: Data<AppState>,
+) -> impl Responder {
+    crate::service::user::logout(user, body, data).await
+}
+
+#[utoipa::path(
+    tag = "user",
+    summary = "Exchange a refresh token for new tokens, revoking it.",
+    request_body = model::Refresh,
+    responses(
+        (status = 200, body = response::Success<response::Session>),
+        (status = 401),
+    )
+)]
+#[post("/refresh")] // Rota POST para renovar a sessão via JSON RefreshModel no body.
+pub async fn refresh(body: Json<model::Refresh>, data: Data<AppState>) -> impl Responder {
+    crate::service::user::refresh(body, data).await
 }
@@ -1,70 +1,279 @@
-use actix_web::{web::Data, App, HttpServer};
+use actix::Addr;
+use actix_web::{
+    body::MessageBody,
+    dev::{ServerHandle, ServiceFactory, ServiceRequest, ServiceResponse},
+    http::KeepAlive,
+    middleware::from_fn,
+    rt,
+    web::Data,
+    App, HttpServer,
+};
 use sqlx::{postgres::PgPoolOptions, Pool, Postgres};
 use std::io::Result as IoResult;
-use std::{env::var, process::exit, str::FromStr};
+use std::{
+    process::exit,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tracing::{error, info, warn};
 
-use crate::{service, AppState};
+use crate::{
+    config, feed, metrics, middleware, migration, password, rate_limit, repository, service, tls,
+    token, AppState,
+};
 
-// Método para obter variável de ambiente em forma de String.
-pub fn venvs(key: &str) -> String {
-    match var(key) {
-        Ok(value) => {
-            println!("@ENV('{}') loaded successfully.", key); // Propagando um log de suceso.
-            value // Retornando a variável.
+// Método para criar o hasher de senhas a partir dos custos do Argon2id.
+pub fn hasher(memory_cost: u32, time_cost: u32, parallelism: u32) -> password::Hasher {
+    match password::Hasher::new(memory_cost, time_cost, parallelism) {
+        Ok(hasher) => {
+            info!("password hasher configured"); // Propagando um log de sucesso.
+            hasher // Retornando o hasher.
         }
-        Err(_) => {
-            println!("@ENV('{}') must be set!", key); // Propagando um log de erro.
-            exit(1) // Finalizando a execução.
+        Err(err) => {
+            error!("invalid Argon2 parameters: {}", err); // Propagando um log de erro.
+            exit(1) // Finalizando execução.
         }
     }
 }
 
-// Método para obter variável de ambiente em forma de um tipo genérico.
-pub fn venv<T: FromStr>(key: &str) -> T {
-    match var(key) {
-        Ok(var_value) => {
-            println!("@ENV('{}') loaded successfully.", key); // Propagando um log de sucesso.
-            match var_value.trim().parse::<T>() {
-                Ok(value) => value, // Retornando a variável.
-                Err(_) => {
-                    println!("@ENV('{}') cannot be parsed to the expected type!", key); // Propagando um log de erro.
-                    exit(1) // Finalizando a execução.
-                }
+// Método para aguardar uma conexão com o PostgresSQL
+// e aplicar as migrações de acordo com o modo escolhido.
+pub async fn connection(database: &config::Database, mode: migration::Mode) -> Pool<Postgres> {
+    let pool = match PgPoolOptions::new()
+        .max_connections(database.max_connections) // Definindo o maximo de conexões simultâneas.
+        .min_connections(database.min_connections) // Definindo as conexões mantidas abertas.
+        .acquire_timeout(Duration::from_secs(database.acquire_timeout)) // Definindo a espera por uma conexão livre.
+        .idle_timeout(database.idle_timeout.map(Duration::from_secs)) // Definindo o tempo de uma conexão ociosa.
+        .max_lifetime(database.max_lifetime.map(Duration::from_secs)) // Definindo o tempo de vida de uma conexão.
+        .connect(&database.url) // Conectando via DATABASE-URL.
+        .await // Aguardando a operação.
+    {
+        Ok(pool) => {
+            info!("database connection resolved"); // Propagando um log de sucesso.
+            pool
+        }
+        Err(err) => {
+            error!("failed to connect to the database: {}", err); // Propagando um log de erro.
+            exit(1) // Finalizando execução.
+        }
+    };
+
+    let status = match migration::status(&pool).await {
+        Ok(status) => status,
+        Err(err) => {
+            error!("failed to read the applied migrations: {}", err); // Propagando um log de erro.
+            exit(1) // Finalizando execução.
+        }
+    };
+
+    // Recusando iniciar com um banco de dados mais novo que o binário.
+    if !status.unknown.is_empty() {
+        error!(unknown = ?status.unknown, "the database schema is newer than this binary"); // Propagando um log de erro.
+        exit(1) // Finalizando execução.
+    }
+
+    if mode == migration::Mode::Skip {
+        if !status.pending.is_empty() {
+            warn!(pending = ?status.pending, "skipping pending migrations"); // Propagando um log de aviso.
+        }
+    } else {
+        match migration::MIGRATOR.run(&pool).await {
+            Ok(()) => info!(applied = ?status.pending, "migrations applied"), // Propagando um log de sucesso.
+            Err(err) => {
+                error!("failed to apply the migrations: {}", err); // Propagando um log de erro.
+                exit(1) // Finalizando execução.
             }
         }
-        Err(_) => {
-            println!("@ENV('{}') must be set!", key); // Propagando um log de erro.
-            exit(1) // Finalizando a execução.
+    }
+
+    // Extraindo as hashtags e as menções das mensagens anteriores à extração,
+    // o que exige a migração que adiciona as entidades.
+    if mode != migration::Mode::Skip || status.pending.is_empty() {
+        match repository::PostgresRepository::new(pool.clone())
+            .extract_entities()
+            .await
+        {
+            Ok(0) => {}
+            Ok(extracted) => info!(messages = extracted, "message entities extracted"), // Propagando um log de sucesso.
+            Err(err) => {
+                error!("failed to extract the message entities: {}", err); // Propagando um log de erro.
+                exit(1) // Finalizando execução.
+            }
         }
     }
+
+    pool // Retornando a conexão.
 }
 
-// Método para aguardar uma conexão com o PostgresSQL
-pub async fn connection(database_url: &str, max_connections: u32) -> Pool<Postgres> {
-    match PgPoolOptions::new()
-        .max_connections(max_connections) // Definindo o maximo de conexões simultâneas.
-        .connect(database_url) // Conectando via DATABASE-URL.
-        .await // Aguardando a operação.
-    {
-        Ok(pool) => {
-            println!("$ Connection DB resolved"); // Propagando um log de sucesso.
-            pool // Retornando a conexão.
+// Método para carregar o certificado do HTTPS, verificando
+// periodicamente os arquivos quando o recarregamento está habilitado.
+pub fn tls(config: &config::Tls) -> rustls::ServerConfig {
+    let certificate = match tls::Certificate::load(&config.cert, &config.key) {
+        Ok(certificate) => certificate,
+        Err(err) => {
+            error!("failed to load the TLS certificate: {}", err); // Propagando um log de erro.
+            exit(1) // Finalizando execução.
         }
+    };
+
+    let server_config = match certificate.server_config() {
+        Ok(server_config) => server_config,
         Err(err) => {
-            println!("$ Failed to connect to the database: {:?}", err); // Propagando um log de erro.
+            error!("invalid TLS configuration: {}", err); // Propagando um log de erro.
             exit(1) // Finalizando execução.
         }
+    };
+
+    if let Some(reload) = config.reload {
+        certificate.watch(Duration::from_secs(reload));
     }
+
+    info!(cert = %config.cert, "TLS certificate loaded"); // Propagando um log de sucesso.
+    server_config // Retornando a configuração.
+}
+
+// Método para criar a aplicação com o estado, os middlewares e as rotas,
+// compartilhado entre os workers do server e os testes.
+pub fn app(
+    state: AppState,
+) -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Config = (),
+        Response = ServiceResponse<impl MessageBody>,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        .app_data(Data::new(state))
+        .wrap(from_fn(middleware::rate_limit)) // Definindo o limite de requisições.
+        .wrap(from_fn(middleware::request_metrics)) // Definindo as métricas das requisições.
+        .wrap(from_fn(middleware::request_log)) // Definindo o log das requisições.
+        .configure(service::config) // Definindo a configuração do serviços.
 }
 
 // Método para aguardar a inicialização de um server.
-pub async fn server(addrs: &str, pool: Pool<Postgres>) -> IoResult<()> {
-    HttpServer::new(move || {
-        App::new()
-            .app_data(Data::new(AppState { db: pool.clone() })) // Definindo o banco de dados.
-            .configure(service::config) // Definindo a configuração do serviços.
-    })
-    .bind(addrs)? // Adicionando a host.
-    .run() // Inicializando o server.
-    .await // Aguardando o client.
+pub async fn server(
+    config: &config::Config,
+    pool: Pool<Postgres>,
+    hasher: password::Hasher,
+    tokens: token::Signer,
+    feed: Option<Addr<feed::Hub>>,
+    metrics: metrics::Metrics,
+) -> IoResult<()> {
+    let search_language = config.search_language;
+    let metrics_enabled = config.metrics;
+    let draining = Arc::new(AtomicBool::new(false));
+    let draining_state = draining.clone();
+    let db = pool.clone();
+    let limiter = config.rate_limit.clone().map(rate_limit::Limiter::new);
+    let repository = Arc::new(repository::PostgresRepository::new(pool.clone()));
+
+    let server = HttpServer::new(move || {
+        app(AppState {
+            db: pool.clone(),                  // Definindo o banco de dados.
+            users: repository.clone(),         // Definindo o repositório de usuários.
+            messages: repository.clone(),      // Definindo o repositório de mensagens.
+            conversations: repository.clone(), // Definindo o repositório das conversas privadas.
+            hasher: hasher.clone(),            // Definindo o hasher de senhas.
+            tokens: tokens.clone(),            // Definindo o assinador de tokens.
+            search_language,                   // Definindo o idioma padrão da busca.
+            feed: feed.clone(),                // Definindo o hub do feed em tempo real.
+            metrics: metrics.clone(),          // Definindo as métricas do Prometheus.
+            metrics_enabled,                   // Definindo se as métricas são expostas.
+            draining: draining_state.clone(),  // Definindo o aviso de encerramento.
+            limiter: limiter.clone(),          // Definindo o limite de requisições.
+        })
+    });
+
+    // Utilizando a quantidade de núcleos quando os workers não são definidos.
+    let server = match config.workers {
+        Some(workers) => server.workers(workers),
+        None => server,
+    };
+
+    let mut server = server
+        .keep_alive(config.keep_alive.map_or(KeepAlive::Disabled, |secs| {
+            KeepAlive::Timeout(Duration::from_secs(secs))
+        })) // Definindo o tempo de uma conexão ociosa.
+        .shutdown_timeout(config.shutdown_timeout) // Definindo a espera pelas requisições em andamento.
+        .disable_signals(); // Os sinais são tratados por shutdown.
+
+    let tls = config.tls.as_ref().map(tls);
+
+    for address in &config.addresses {
+        server = match &tls {
+            Some(tls) => server.bind_rustls_0_23(address, tls.clone())?, // Adicionando a host com HTTPS.
+            None => server.bind(address)?,                               // Adicionando a host.
+        };
+        info!(address = %address, tls = tls.is_some(), "listening"); // Propagando um log de sucesso.
+    }
+
+    if let Some(path) = &config.unix_socket {
+        // A configuração recusa o socket Unix nas demais plataformas.
+        #[cfg(unix)]
+        {
+            server = server.bind_uds(path)?; // Adicionando o socket Unix.
+        }
+        info!(path = %path, "listening"); // Propagando um log de sucesso.
+    }
+
+    let server = server.run(); // Inicializando o server.
+
+    rt::spawn(shutdown(
+        server.handle(),
+        draining,
+        Duration::from_secs(config.shutdown_delay),
+    ));
+
+    let result = server.await; // Aguardando o client.
+
+    // Fechando as conexões com o banco após as requisições em andamento terminarem.
+    info!("closing the database connections");
+    db.close().await;
+    info!("server stopped"); // Propagando um log de sucesso.
+
+    result // Retornando o resultado do server.
+}
+
+// Método para aguardar o sinal de encerramento (Ctrl+C ou SIGTERM),
+// marcando o servidor como indisponível em "health/ready" durante o
+// atraso configurado, para que o orquestrador pare de enviar requisições
+// antes do servidor parar de aceitar conexões.
+async fn shutdown(server: ServerHandle, draining: Arc<AtomicBool>, delay: Duration) {
+    signal().await;
+    draining.store(true, Ordering::SeqCst);
+    warn!(delay_secs = delay.as_secs(), "shutdown requested, draining"); // Propagando um log de aviso.
+
+    rt::time::sleep(delay).await;
+    info!("stopping the server"); // Propagando um log de sucesso.
+    server.stop(true).await; // Aguardando as requisições em andamento.
+}
+
+// Método para aguardar Ctrl+C ou SIGTERM.
+#[cfg(unix)]
+async fn signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+        }
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+// Método para aguardar Ctrl+C.
+#[cfg(not(unix))]
+async fn signal() {
+    let _ = tokio::signal::ctrl_c().await;
 }
@@ -0,0 +1,82 @@
+use crate::schema::{Entity, EntityKind};
+
+// Tamanho máximo de uma hashtag, sem o "#", como em message_tags.tag.
+pub const TAG_MAX: usize = 100;
+
+// Caracteres de uma hashtag.
+fn is_tag(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Caracteres de um nome mencionado. O "." e o "-" finais são pontuação.
+fn is_name(c: char) -> bool {
+    is_tag(c) || c == '.' || c == '-'
+}
+
+// Método para normalizar uma hashtag digitada, com ou sem o "#",
+// retornando None quando ela não seria reconhecida no conteúdo.
+// As verificações valem para o texto já em minúsculas, pois a
+// conversão pode mudar a quantidade de caracteres ("İ" vira "i̇").
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.strip_prefix('#').unwrap_or(tag).to_lowercase();
+    let valid = !tag.is_empty()
+        && tag.chars().count() <= TAG_MAX
+        && tag.chars().all(is_tag)
+        && !tag.chars().all(|c| c.is_ascii_digit());
+
+    valid.then_some(tag)
+}
+
+// Método para extrair as hashtags ("#rust") e as menções ("@ana") do
+// conteúdo. O símbolo precisa estar no início do texto ou depois de um
+// caractere que não faça parte de uma palavra, ignorando "C#" e e-mails.
+// As menções ainda não são resolvidas, ficando sem o usuário.
+pub fn extract(content: &str) -> Vec<Entity> {
+    let mut entities = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = content.char_indices().peekable();
+
+    while let Some((start, symbol)) = chars.next() {
+        let boundary = previous.is_none_or(|c| !is_name(c) && c != '#' && c != '@');
+        previous = Some(symbol);
+
+        let kind = match symbol {
+            '#' if boundary => EntityKind::Hashtag,
+            '@' if boundary => EntityKind::Mention,
+            _ => continue,
+        };
+        let accepts = match kind {
+            EntityKind::Hashtag => is_tag,
+            EntityKind::Mention => is_name,
+        };
+
+        let body = start + symbol.len_utf8();
+        let mut end = body;
+        while let Some(&(i, c)) = chars.peek().filter(|(_, c)| accepts(*c)) {
+            end = i + c.len_utf8();
+            previous = Some(c);
+            chars.next();
+        }
+
+        let text = &content[body..end];
+        let entity = match kind {
+            EntityKind::Hashtag => normalize_tag(text).map(|tag| (tag, end)),
+            EntityKind::Mention => {
+                let name = text.trim_end_matches(['.', '-']);
+                (!name.is_empty()).then(|| (name.to_string(), body + name.len()))
+            }
+        };
+
+        if let Some((text, end)) = entity {
+            entities.push(Entity {
+                kind,
+                text,
+                start,
+                end,
+                user_id: None,
+            });
+        }
+    }
+
+    entities
+}
@@ -0,0 +1,133 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use tracing::error;
+
+use crate::{rate_limit::CredentialFailure, response};
+
+// Código do PostgreSQL para violação de unicidade.
+const UNIQUE_VIOLATION: &str = "23505";
+// Código do PostgreSQL para violação de chave estrangeira.
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+
+// Erro retornado pelos serviços, convertido na resposta HTTP
+// adequada no formato de ServerError. Os detalhes de erros
+// internos são apenas registrados no log, nunca enviados ao cliente.
+#[derive(Debug)]
+pub enum AppError {
+    BadRequest(String),         // 400: requisição inválida.
+    Unauthorized(String),       // 401: autenticação ausente ou inválida.
+    InvalidCredentials(String), // 401: senha ou token de atualização incorretos.
+    Forbidden(String),          // 403: recurso de outro usuário.
+    NotFound(String),           // 404: recurso inexistente.
+    NotAcceptable(String),      // 406: dados recusados pela validação.
+    Conflict(String),           // 409: recurso já existente.
+    Unprocessable(String),      // 422: referência a um recurso inexistente.
+    TooManyRequests(String),    // 429: limite de requisições excedido.
+    Internal,                   // 500: erro inesperado.
+}
+
+impl AppError {
+    // Registra o erro inesperado e retorna o erro genérico.
+    pub fn internal(err: impl Display) -> Self {
+        error!("unexpected error: {}", err); // Propagando um log de erro.
+        AppError::Internal
+    }
+
+    // Cria o erro de um UUID inválido informado no caminho da rota.
+    pub fn invalid_uuid(action: &str) -> Self {
+        AppError::BadRequest(format!("Invalid UUID format, unable to {}!", action))
+    }
+
+    // Cria o erro de um nome de usuário repetido.
+    pub fn name_in_use() -> Self {
+        AppError::Conflict("This name is already in use!".to_string())
+    }
+
+    // Cria o erro de uma referência a um recurso inexistente.
+    pub fn missing_reference() -> Self {
+        AppError::Unprocessable(
+            "This request references a resource that doesn't exist!".to_string(),
+        )
+    }
+
+    // Mensagem enviada ao cliente.
+    fn message(&self) -> &str {
+        match self {
+            AppError::BadRequest(message)
+            | AppError::Unauthorized(message)
+            | AppError::InvalidCredentials(message)
+            | AppError::Forbidden(message)
+            | AppError::NotFound(message)
+            | AppError::NotAcceptable(message)
+            | AppError::Conflict(message)
+            | AppError::Unprocessable(message)
+            | AppError::TooManyRequests(message) => message,
+            AppError::Internal => "An unexpected error occurred, try again later!",
+        }
+    }
+}
+
+impl Display for AppError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    // Classifica os erros do sqlx que dependem da requisição,
+    // tratando qualquer outro como erro interno.
+    fn from(err: sqlx::Error) -> Self {
+        match &err {
+            sqlx::Error::RowNotFound => {
+                AppError::NotFound("This resource doesn't exist!".to_string())
+            }
+            sqlx::Error::Database(db) => match db.code().as_deref() {
+                Some(UNIQUE_VIOLATION) => match db.constraint() {
+                    Some("rust_user_name_key") => AppError::name_in_use(),
+                    _ => AppError::Conflict("This resource already exists!".to_string()),
+                },
+                Some(FOREIGN_KEY_VIOLATION) => AppError::missing_reference(),
+                _ => AppError::internal(err),
+            },
+            _ => AppError::internal(err),
+        }
+    }
+}
+
+impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized(_) | AppError::InvalidCredentials(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::NotAcceptable(_) => StatusCode::NOT_ACCEPTABLE,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::Unprocessable(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let status = self.status_code();
+        let mut builder = HttpResponse::build(status);
+
+        // Indicando o esquema de autenticação esperado.
+        if let AppError::Unauthorized(_) | AppError::InvalidCredentials(_) = self {
+            builder.insert_header(("WWW-Authenticate", "Bearer"));
+        }
+
+        let mut res = builder.json(response::ServerError::new(
+            &status.canonical_reason().unwrap_or("error").to_lowercase(),
+            self.message(),
+        ));
+
+        // Marcando a falha de credenciais, contada pelo bloqueio de endereços.
+        if let AppError::InvalidCredentials(_) = self {
+            res.extensions_mut().insert(CredentialFailure);
+        }
+
+        res // Retornando a resposta do erro.
+    }
+}
@@ -0,0 +1,407 @@
+use actix::{Actor, Context, Handler, Message, Recipient};
+use actix_web::web::Bytes;
+use chrono::Utc;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::schema;
+
+// Quantidade máxima de tópicos de usuário por conexão.
+pub const MAX_TOPICS: usize = 100;
+// Quantidade de eventos mantidos para as conexões Server-Sent Events
+// que retomam pelo Last-Event-ID.
+pub const RETENTION: usize = 1024;
+
+// Tópico assinado por uma conexão.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    Global,     // Todas as mensagens novas.
+    User(Uuid), // Mensagens novas de um único usuário.
+}
+
+// Evento já serializado entregue para cada conexão inscrita.
+#[derive(Message, Clone)]
+#[rtype(result = "()")]
+pub struct Deliver(pub Arc<str>);
+
+// Registra uma conexão no hub, retornando o seu id.
+#[derive(Message)]
+#[rtype(result = "usize")]
+pub struct Connect {
+    pub recipient: Recipient<Deliver>,
+}
+
+// Remove a conexão e todas as suas inscrições.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Disconnect {
+    pub id: usize,
+}
+
+// Inscreve a conexão em um tópico, retornando false
+// quando o limite de tópicos foi atingido.
+#[derive(Message)]
+#[rtype(result = "bool")]
+pub struct Subscribe {
+    pub id: usize,
+    pub topic: Topic,
+}
+
+// Cancela a inscrição da conexão em um tópico.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Unsubscribe {
+    pub id: usize,
+    pub topic: Topic,
+}
+
+// Filtros de uma conexão Server-Sent Events; sem filtros ela recebe todos os eventos.
+#[derive(Default)]
+pub struct Filter {
+    pub user_id: Option<Uuid>, // Autor da mensagem.
+    pub tag: Option<String>,   // Hashtag normalizada da mensagem.
+}
+
+impl Filter {
+    fn matches(&self, record: &Record) -> bool {
+        self.user_id.is_none_or(|user_id| user_id == record.user_id)
+            && self
+                .tag
+                .as_ref()
+                .is_none_or(|tag| record.tags.contains(tag))
+    }
+}
+
+// Registra uma conexão Server-Sent Events, retornando os eventos perdidos
+// desde o Last-Event-ID. Os eventos seguintes são enviados pelo sender.
+#[derive(Message)]
+#[rtype(result = "Vec<Bytes>")]
+pub struct Listen {
+    pub sender: mpsc::Sender<Bytes>,
+    pub filter: Filter,
+    pub last_event_id: Option<u64>,
+}
+
+// Publica um evento de uma mensagem. As mensagens novas são entregues aos
+// inscritos no tópico global e no tópico do autor, e todos os eventos às
+// conexões Server-Sent Events com os filtros correspondentes.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Publish {
+    user_id: Uuid,
+    tags: Vec<String>,
+    kind: &'static str,
+    payload: Arc<str>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Event<'a> {
+    Message {
+        message: &'a schema::Message,
+    },
+    Like {
+        message_id: Uuid,
+        user_id: Uuid, // Usuário que curtiu.
+        likes: i32,
+    },
+    Unlike {
+        message_id: Uuid,
+        user_id: Uuid, // Usuário que descurtiu.
+        likes: i32,
+    },
+    Delete {
+        message_id: Uuid,
+    },
+}
+
+impl Publish {
+    // Serializa o evento uma única vez para todas as conexões.
+    fn new(message: &schema::Message, event: Event) -> Self {
+        let kind = match event {
+            Event::Message { .. } => "message",
+            Event::Like { .. } => "like",
+            Event::Unlike { .. } => "unlike",
+            Event::Delete { .. } => "delete",
+        };
+
+        Self {
+            user_id: message.user_id,
+            tags: message
+                .entities
+                .iter()
+                .filter(|e| e.kind == schema::EntityKind::Hashtag)
+                .map(|e| e.text.clone())
+                .collect(),
+            kind,
+            payload: serde_json::to_string(&event).unwrap_or_default().into(),
+        }
+    }
+
+    // Evento de uma mensagem nova.
+    pub fn message(message: &schema::Message) -> Self {
+        Self::new(message, Event::Message { message })
+    }
+
+    // Evento da curtida de uma mensagem, com o contador atualizado.
+    pub fn like(message: &schema::Message, user_id: Uuid) -> Self {
+        let event = Event::Like {
+            message_id: message.id,
+            user_id,
+            likes: message.likes,
+        };
+        Self::new(message, event)
+    }
+
+    // Evento da remoção de uma curtida, com o contador atualizado.
+    pub fn unlike(message: &schema::Message, user_id: Uuid) -> Self {
+        let event = Event::Unlike {
+            message_id: message.id,
+            user_id,
+            likes: message.likes,
+        };
+        Self::new(message, event)
+    }
+
+    // Evento da remoção de uma mensagem, lida antes de ser removida.
+    pub fn delete(message: &schema::Message) -> Self {
+        Self::new(
+            message,
+            Event::Delete {
+                message_id: message.id,
+            },
+        )
+    }
+}
+
+// Evento mantido para a retomada, já no formato do text/event-stream.
+struct Record {
+    id: u64,
+    user_id: Uuid,
+    tags: Vec<String>,
+    frame: Bytes,
+}
+
+// Conexão Server-Sent Events registrada no hub.
+struct Listener {
+    sender: mpsc::Sender<Bytes>,
+    filter: Filter,
+}
+
+// Conexão registrada no hub, com os tópicos de usuário assinados.
+struct Subscriber {
+    recipient: Recipient<Deliver>,
+    users: HashSet<Uuid>,
+}
+
+// Ator que mantém as conexões do feed e distribui os eventos das mensagens.
+pub struct Hub {
+    next_id: usize,
+    sessions: HashMap<usize, Subscriber>,
+    global: HashSet<usize>,
+    users: HashMap<Uuid, HashSet<usize>>,
+    last_event_id: u64,
+    events: VecDeque<Record>,
+    listeners: Vec<Listener>,
+}
+
+impl Default for Hub {
+    // Os ids dos eventos partem do horário de início em microssegundos, sendo
+    // maiores que os de uma execução anterior: um Last-Event-ID anterior ao
+    // reinício fica fora da janela de retomada em vez de coincidir com outro evento.
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            sessions: HashMap::new(),
+            global: HashSet::new(),
+            users: HashMap::new(),
+            last_event_id: Utc::now().timestamp_micros().max(0) as u64,
+            events: VecDeque::new(),
+            listeners: Vec::new(),
+        }
+    }
+}
+
+impl Hub {
+    // Remove a conexão de um tópico de usuário, liberando o tópico vazio.
+    fn leave_user(&mut self, id: usize, user_id: Uuid) {
+        if let Some(ids) = self.users.get_mut(&user_id) {
+            ids.remove(&id);
+            if ids.is_empty() {
+                self.users.remove(&user_id);
+            }
+        }
+    }
+}
+
+impl Actor for Hub {
+    type Context = Context<Self>;
+}
+
+impl Handler<Connect> for Hub {
+    type Result = usize;
+
+    fn handle(&mut self, msg: Connect, _: &mut Self::Context) -> Self::Result {
+        self.next_id += 1;
+        self.sessions.insert(
+            self.next_id,
+            Subscriber {
+                recipient: msg.recipient,
+                users: HashSet::new(),
+            },
+        );
+        self.next_id
+    }
+}
+
+impl Handler<Disconnect> for Hub {
+    type Result = ();
+
+    fn handle(&mut self, msg: Disconnect, _: &mut Self::Context) {
+        self.global.remove(&msg.id);
+
+        if let Some(subscriber) = self.sessions.remove(&msg.id) {
+            for user_id in subscriber.users {
+                self.leave_user(msg.id, user_id);
+            }
+        }
+    }
+}
+
+impl Handler<Subscribe> for Hub {
+    type Result = bool;
+
+    fn handle(&mut self, msg: Subscribe, _: &mut Self::Context) -> Self::Result {
+        match msg.topic {
+            Topic::Global => {
+                self.global.insert(msg.id);
+                true
+            }
+            Topic::User(user_id) => {
+                let subscriber = match self.sessions.get_mut(&msg.id) {
+                    Some(subscriber) => subscriber,
+                    None => return false, // Conexão já encerrada.
+                };
+
+                if !subscriber.users.contains(&user_id) && subscriber.users.len() >= MAX_TOPICS {
+                    return false;
+                }
+                subscriber.users.insert(user_id);
+                self.users.entry(user_id).or_default().insert(msg.id);
+                true
+            }
+        }
+    }
+}
+
+impl Handler<Unsubscribe> for Hub {
+    type Result = ();
+
+    fn handle(&mut self, msg: Unsubscribe, _: &mut Self::Context) {
+        match msg.topic {
+            Topic::Global => {
+                self.global.remove(&msg.id);
+            }
+            Topic::User(user_id) => {
+                if let Some(subscriber) = self.sessions.get_mut(&msg.id) {
+                    subscriber.users.remove(&user_id);
+                }
+                self.leave_user(msg.id, user_id);
+            }
+        }
+    }
+}
+
+impl Handler<Listen> for Hub {
+    type Result = Vec<Bytes>;
+
+    fn handle(&mut self, msg: Listen, _: &mut Self::Context) -> Self::Result {
+        // O registro e a leitura dos eventos perdidos acontecem juntos no
+        // ator, sem repetir nem perder os eventos publicados entre eles.
+        let replay = match msg.last_event_id {
+            None => Vec::new(),
+            Some(last) => {
+                // Id do último evento que já saiu da janela.
+                let evicted = self.last_event_id - self.events.len() as u64;
+
+                // Os eventos seguintes ao Last-Event-ID já saíram da janela,
+                // ou o id não foi emitido por esta execução.
+                if last < evicted || last > self.last_event_id {
+                    vec![Bytes::from(format!(
+                        "id: {}\nevent: reset\ndata: {{\"type\":\"reset\"}}\n\n",
+                        self.last_event_id
+                    ))]
+                } else {
+                    self.events
+                        .iter()
+                        .filter(|record| record.id > last && msg.filter.matches(record))
+                        .map(|record| record.frame.clone())
+                        .collect()
+                }
+            }
+        };
+
+        // Removendo as conexões fechadas pelo cliente, para que não se
+        // acumulem enquanto nenhum evento é publicado.
+        self.listeners
+            .retain(|listener| !listener.sender.is_closed());
+        self.listeners.push(Listener {
+            sender: msg.sender,
+            filter: msg.filter,
+        });
+        replay
+    }
+}
+
+impl Handler<Publish> for Hub {
+    type Result = ();
+
+    fn handle(&mut self, msg: Publish, _: &mut Self::Context) {
+        if msg.kind == "message" {
+            // Uma conexão inscrita nos dois tópicos recebe a mensagem uma única vez.
+            let mut targets: HashSet<usize> = self.global.clone();
+            if let Some(ids) = self.users.get(&msg.user_id) {
+                targets.extend(ids);
+            }
+
+            let deliver = Deliver(msg.payload.clone());
+            for id in targets {
+                if let Some(subscriber) = self.sessions.get(&id) {
+                    // try_send respeita a capacidade da caixa de entrada da conexão:
+                    // conexões lentas perdem a mensagem em vez de acumular memória.
+                    let _ = subscriber.recipient.try_send(deliver.clone());
+                }
+            }
+        }
+
+        self.last_event_id += 1;
+        let record = Record {
+            id: self.last_event_id,
+            user_id: msg.user_id,
+            tags: msg.tags,
+            frame: Bytes::from(format!(
+                "id: {}\nevent: {}\ndata: {}\n\n",
+                self.last_event_id, msg.kind, msg.payload
+            )),
+        };
+
+        // A conexão Server-Sent Events com a fila cheia é encerrada em vez de
+        // perder o evento, e o cliente retoma pelo Last-Event-ID. As conexões
+        // fechadas pelo cliente também são removidas aqui e a cada registro.
+        self.listeners
+            .retain(|listener| match listener.filter.matches(&record) {
+                true => listener.sender.try_send(record.frame.clone()).is_ok(),
+                false => !listener.sender.is_closed(),
+            });
+
+        self.events.push_back(record);
+        if self.events.len() > RETENTION {
+            self.events.pop_front();
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod hub;
+mod session;
+mod stream;
+
+pub use hub::*; // Exportando o ator que distribui as mensagens.
+pub use session::*; // Exportando o ator de cada conexão WebSocket.
+pub use stream::*; // Exportando o corpo de cada conexão Server-Sent Events.
@@ -0,0 +1,228 @@
+use actix::{
+    fut, Actor, ActorContext, ActorFutureExt, Addr, AsyncContext, ContextFutureSpawner, Handler,
+    Running, StreamHandler, WrapFuture,
+};
+use actix_web_actors::ws;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use super::hub::{self, Topic};
+
+// Intervalo entre os pings enviados ao cliente.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+// Tempo sem resposta do cliente até a conexão ser encerrada.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(15);
+// Capacidade da caixa de entrada da conexão; mensagens excedentes são descartadas pelo hub.
+const MAILBOX_CAPACITY: usize = 64;
+// Quantidade de frames enviados e ainda não lidos pelo cliente até novas mensagens
+// serem descartadas. Um pong confirma a leitura de tudo que foi enviado antes do ping.
+const MAX_BACKLOG: u64 = 256;
+
+// Comando enviado pelo cliente; sem user_id o tópico é o feed global.
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+enum Command {
+    Subscribe { user_id: Option<Uuid> },
+    Unsubscribe { user_id: Option<Uuid> },
+}
+
+// Evento de controle enviado ao cliente.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Event<'a> {
+    Subscribed { user_id: Option<Uuid> },
+    Unsubscribed { user_id: Option<Uuid> },
+    Lagged { dropped: u64 },
+    Error { message: &'a str },
+}
+
+fn topic(user_id: Option<Uuid>) -> Topic {
+    user_id.map_or(Topic::Global, Topic::User)
+}
+
+// Ator de uma conexão WebSocket do feed de mensagens.
+pub struct Session {
+    id: usize,
+    hub: Addr<hub::Hub>,
+    heartbeat: Instant,
+    initial: Vec<Topic>,
+    sent: u64,    // Frames enviados ao cliente.
+    acked: u64,   // Frames confirmados pelo último pong.
+    dropped: u64, // Mensagens descartadas desde o último aviso de atraso.
+}
+
+impl Session {
+    // Cria a sessão já inscrita nos tópicos iniciais.
+    pub fn new(hub: Addr<hub::Hub>, initial: Vec<Topic>) -> Self {
+        Self {
+            id: 0,
+            hub,
+            heartbeat: Instant::now(),
+            initial,
+            sent: 0,
+            acked: 0,
+            dropped: 0,
+        }
+    }
+
+    fn send(&mut self, ctx: &mut ws::WebsocketContext<Self>, text: String) {
+        self.sent += 1;
+        ctx.text(text);
+    }
+
+    fn event(&mut self, ctx: &mut ws::WebsocketContext<Self>, event: Event) {
+        if let Ok(text) = serde_json::to_string(&event) {
+            self.send(ctx, text);
+        }
+    }
+
+    // Envia pings periódicos com a quantidade de frames enviados,
+    // encerrando a conexão quando o cliente para de responder.
+    fn heartbeat(&self, ctx: &mut ws::WebsocketContext<Self>) {
+        ctx.run_interval(HEARTBEAT_INTERVAL, |act, ctx| {
+            if Instant::now().duration_since(act.heartbeat) > CLIENT_TIMEOUT {
+                ctx.close(Some(ws::CloseCode::Away.into()));
+                ctx.stop();
+                return;
+            }
+            ctx.ping(&act.sent.to_be_bytes());
+        });
+    }
+
+    fn subscribe(&mut self, user_id: Option<Uuid>, ctx: &mut ws::WebsocketContext<Self>) {
+        self.hub
+            .send(hub::Subscribe {
+                id: self.id,
+                topic: topic(user_id),
+            })
+            .into_actor(self)
+            .then(move |res, act, ctx| {
+                match res {
+                    Ok(true) => {
+                        act.event(ctx, Event::Subscribed { user_id });
+                    }
+                    Ok(false) => act.event(
+                        ctx,
+                        Event::Error {
+                            message: "Too many subscriptions on this connection!",
+                        },
+                    ),
+                    Err(_) => ctx.stop(), // O hub não está mais disponível.
+                }
+                fut::ready(())
+            })
+            .wait(ctx);
+    }
+
+    fn unsubscribe(&mut self, user_id: Option<Uuid>, ctx: &mut ws::WebsocketContext<Self>) {
+        self.hub.do_send(hub::Unsubscribe {
+            id: self.id,
+            topic: topic(user_id),
+        });
+        self.event(ctx, Event::Unsubscribed { user_id });
+    }
+}
+
+impl Actor for Session {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.set_mailbox_capacity(MAILBOX_CAPACITY);
+        self.heartbeat(ctx);
+
+        // Registrando a conexão antes de processar qualquer comando.
+        self.hub
+            .send(hub::Connect {
+                recipient: ctx.address().recipient(),
+            })
+            .into_actor(self)
+            .then(|res, act, ctx| {
+                match res {
+                    Ok(id) => {
+                        act.id = id;
+                        for topic in std::mem::take(&mut act.initial) {
+                            let user_id = match topic {
+                                Topic::Global => None,
+                                Topic::User(user_id) => Some(user_id),
+                            };
+                            act.subscribe(user_id, ctx);
+                        }
+                    }
+                    Err(_) => ctx.stop(), // O hub não está mais disponível.
+                }
+                fut::ready(())
+            })
+            .wait(ctx);
+    }
+
+    fn stopping(&mut self, _: &mut Self::Context) -> Running {
+        self.hub.do_send(hub::Disconnect { id: self.id });
+        Running::Stop
+    }
+}
+
+impl Handler<hub::Deliver> for Session {
+    type Result = ();
+
+    fn handle(&mut self, msg: hub::Deliver, ctx: &mut Self::Context) {
+        // Descartando mensagens enquanto o cliente não lê o que já foi enviado.
+        if self.sent - self.acked >= MAX_BACKLOG {
+            self.dropped += 1;
+            return;
+        }
+
+        if self.dropped > 0 {
+            let dropped = std::mem::take(&mut self.dropped);
+            self.event(ctx, Event::Lagged { dropped });
+        }
+        self.send(ctx, msg.0.to_string());
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for Session {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        let msg = match msg {
+            Ok(msg) => msg,
+            Err(_) => {
+                ctx.stop();
+                return;
+            }
+        };
+
+        match msg {
+            ws::Message::Ping(payload) => {
+                self.heartbeat = Instant::now();
+                ctx.pong(&payload);
+            }
+            ws::Message::Pong(payload) => {
+                self.heartbeat = Instant::now();
+                // O payload do pong é a quantidade de frames enviados antes do ping.
+                if let Ok(bytes) = <[u8; 8]>::try_from(&payload[..]) {
+                    self.acked = self.acked.max(u64::from_be_bytes(bytes));
+                }
+            }
+            ws::Message::Text(text) => match serde_json::from_str::<Command>(&text) {
+                Ok(Command::Subscribe { user_id }) => self.subscribe(user_id, ctx),
+                Ok(Command::Unsubscribe { user_id }) => self.unsubscribe(user_id, ctx),
+                Err(_) => self.event(
+                    ctx,
+                    Event::Error {
+                        message: "Invalid command, expected {\"action\": \"subscribe\" | \"unsubscribe\", \"user_id\"?: UUID}!",
+                    },
+                ),
+            },
+            ws::Message::Binary(_) => self.event(
+                ctx,
+                Event::Error {
+                    message: "Binary frames are not supported!",
+                },
+            ),
+            ws::Message::Close(reason) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            ws::Message::Continuation(_) | ws::Message::Nop => {}
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use actix::{
+    clock::{interval_at, Instant, Interval},
+    Addr, MailboxError,
+};
+use actix_web::{
+    body::{BodySize, MessageBody},
+    web::Bytes,
+};
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::mpsc;
+
+use super::hub::{self, Filter};
+
+// Intervalo entre os comentários que mantêm a conexão aberta nos proxies.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+// Capacidade da fila da conexão; o hub encerra a conexão que não a esvazia.
+const QUEUE_CAPACITY: usize = 64;
+// Espera sugerida ao cliente antes de reconectar, em milissegundos.
+const RETRY: u64 = 3000;
+
+// Corpo de uma conexão Server-Sent Events: os eventos perdidos desde o
+// Last-Event-ID e, em seguida, os eventos publicados pelo hub.
+pub struct EventStream {
+    pending: VecDeque<Bytes>,
+    receiver: mpsc::Receiver<Bytes>,
+    keep_alive: Interval,
+}
+
+impl EventStream {
+    // Registra a conexão no hub com os filtros, retomando após o Last-Event-ID.
+    pub async fn open(
+        hub: &Addr<hub::Hub>,
+        filter: Filter,
+        last_event_id: Option<u64>,
+    ) -> Result<Self, MailboxError> {
+        let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+        let replay = hub
+            .send(hub::Listen {
+                sender,
+                filter,
+                last_event_id,
+            })
+            .await?;
+
+        let mut pending = VecDeque::from(replay);
+        pending.push_front(Bytes::from(format!("retry: {}\n\n", RETRY)));
+
+        Ok(Self {
+            pending,
+            receiver,
+            keep_alive: interval_at(Instant::now() + KEEP_ALIVE_INTERVAL, KEEP_ALIVE_INTERVAL),
+        })
+    }
+}
+
+impl MessageBody for EventStream {
+    type Error = Infallible;
+
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let this = self.get_mut();
+
+        if let Some(frame) = this.pending.pop_front() {
+            return Poll::Ready(Some(Ok(frame)));
+        }
+
+        // Sem o sender o hub encerrou a conexão, e o corpo termina.
+        match this.receiver.poll_recv(cx) {
+            Poll::Ready(frame) => return Poll::Ready(frame.map(Ok)),
+            Poll::Pending => {}
+        }
+
+        match this.keep_alive.poll_tick(cx) {
+            Poll::Ready(_) => Poll::Ready(Some(Ok(Bytes::from_static(b": keep-alive\n\n")))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -1,41 +1,123 @@
+mod auth;
+mod config;
 mod controller;
 mod create;
+mod entity;
+mod error;
+mod feed;
+mod metrics;
+mod middleware;
+mod migration;
 mod model;
+mod openapi;
+mod pagination;
+mod password;
+mod rate_limit;
+mod repository;
 mod response;
 mod schema;
+mod search;
 mod service;
+mod telemetry;
+mod tls;
+mod token;
 
+#[cfg(test)]
+mod tests;
+
+use actix::{Actor, Addr};
 use dotenv::dotenv;
 use sqlx::{Pool, Postgres};
-use std::io::Result as IoResult;
+use std::{
+    io::Result as IoResult,
+    process::exit,
+    sync::{atomic::AtomicBool, Arc},
+};
+use tracing::{error, info};
 
-// Estrutura para o banco de dados do Actix.
+// Estrutura para o estado compartilhado do Actix.
+// O pool é usado diretamente apenas pela prontidão e pelas métricas;
+// as rotas acessam os dados pelos repositórios.
 pub struct AppState {
     db: Pool<Postgres>,
+    users: Arc<dyn repository::UserRepository>,
+    messages: Arc<dyn repository::MessageRepository>,
+    conversations: Arc<dyn repository::ConversationRepository>,
+    hasher: password::Hasher,
+    tokens: token::Signer,
+    search_language: search::Language,
+    feed: Option<Addr<feed::Hub>>,
+    metrics: metrics::Metrics,
+    metrics_enabled: bool,
+    draining: Arc<AtomicBool>,
+    limiter: Option<rate_limit::Limiter>,
 }
 
 #[actix::main]
 async fn main() -> IoResult<()> {
-    println!("$ Starting the server...");
-
     dotenv().ok();
 
-    println!("\n# Loading the environment variables!");
-    let addrs = &create::venvs("ADDRESS");
-    let database_url = &create::venvs("DATABASE_URL");
-    let max_connections = create::venv::<u32>("MAX_CONNECTIONS");
+    // O tracing depende da configuração, então os erros dela vão para o stderr.
+    let config = match config::Config::load() {
+        Ok(config) => config,
+        Err(errors) => {
+            eprintln!("Invalid configuration:");
+            for error in errors {
+                eprintln!("  - {}", error); // Propagando todos os erros de uma só vez.
+            }
+            exit(1) // Finalizando execução.
+        }
+    };
+
+    telemetry::init(config.log_format, &config.log_level);
+    info!("starting the server");
+    config.log();
+
+    let hasher = create::hasher(
+        config.argon2.memory_cost,
+        config.argon2.time_cost,
+        config.argon2.parallelism,
+    );
+
+    let tokens = token::Signer::new(
+        &config.token_secret,
+        config.access_token_ttl,
+        config.refresh_token_ttl,
+    );
+
+    let pool = create::connection(&config.database, config.migrate).await;
+
+    if config.migrate == migration::Mode::Only {
+        info!("migrations finished, exiting without starting the server");
+        return Ok(());
+    }
 
-    println!("\n# Connecting to the database!");
-    let pool = create::connection(database_url, max_connections).await;
+    let feed = match config.feed {
+        true => {
+            info!("starting the message feed");
+            Some(feed::Hub::default().start())
+        }
+        false => None,
+    };
 
-    println!("\n# Creating the http server!");
-    let server = create::server(addrs, pool);
+    let metrics = match metrics::Metrics::new() {
+        Ok(metrics) => metrics,
+        Err(err) => {
+            error!("failed to register the metrics: {}", err); // Propagando um log de erro.
+            exit(1) // Finalizando execução.
+        }
+    };
 
-    println!("$ Server started successfully.");
-    println!("$ Access http://{} to verify the server.", addrs);
+    let server = create::server(&config, pool, hasher, tokens, feed, metrics);
 
-    service::user::logs();
-    service::message::logs();
+    if config.route_logs {
+        service::user::logs();
+        service::message::logs();
+        service::conversation::logs();
+        service::metrics::logs();
+        service::health::logs();
+        service::docs::logs();
+    }
 
     return server.await;
 }
@@ -0,0 +1,102 @@
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+
+// Métricas do servidor no formato do Prometheus. Os clones
+// compartilham os mesmos valores entre os workers.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    requests: IntCounterVec, // Requisições por método, rota e status.
+    latency: HistogramVec,   // Latência por método e rota.
+    errors: IntCounterVec,   // Respostas de erro pelo status do ServerError.
+    pool_size: IntGauge,     // Conexões abertas no pool.
+    pool_idle: IntGauge,     // Conexões ociosas no pool.
+    messages_posted: IntCounter,
+    users_created: IntCounter,
+}
+
+impl Metrics {
+    // Cria e registra todas as métricas.
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let registry = Registry::new();
+
+        let requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests by route and status."),
+            &["method", "route", "status"],
+        )?;
+        let latency = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency by route.",
+            ),
+            &["method", "route"],
+        )?;
+        let errors = IntCounterVec::new(
+            Opts::new("http_errors_total", "HTTP error responses by status."),
+            &["status"],
+        )?;
+        let pool_size = IntGauge::new("db_pool_connections", "Open database connections.")?;
+        let pool_idle = IntGauge::new("db_pool_idle_connections", "Idle database connections.")?;
+        let messages_posted = IntCounter::new("messages_posted_total", "Messages posted.")?;
+        let users_created = IntCounter::new("users_created_total", "Users created.")?;
+
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(latency.clone()))?;
+        registry.register(Box::new(errors.clone()))?;
+        registry.register(Box::new(pool_size.clone()))?;
+        registry.register(Box::new(pool_idle.clone()))?;
+        registry.register(Box::new(messages_posted.clone()))?;
+        registry.register(Box::new(users_created.clone()))?;
+
+        Ok(Self {
+            registry,
+            requests,
+            latency,
+            errors,
+            pool_size,
+            pool_idle,
+            messages_posted,
+            users_created,
+        })
+    }
+
+    // Registra uma requisição concluída. A rota é o padrão registrado
+    // em service::config, evitando uma série para cada id.
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, latency: Duration) {
+        self.requests
+            .with_label_values(&[method, route, &status.to_string()])
+            .inc();
+        self.latency
+            .with_label_values(&[method, route])
+            .observe(latency.as_secs_f64());
+    }
+
+    // Registra uma resposta de erro pelo status do ServerError ("not found", ...).
+    pub fn observe_error(&self, status: &str) {
+        self.errors.with_label_values(&[status]).inc();
+    }
+
+    pub fn message_posted(&self) {
+        self.messages_posted.inc();
+    }
+
+    pub fn user_created(&self) {
+        self.users_created.inc();
+    }
+
+    // Atualiza as métricas do pool e gera o texto no formato do Prometheus.
+    // Os valores do pool são lidos sem obter uma conexão, então a coleta
+    // nunca espera pelo banco nem ocupa uma conexão das requisições.
+    pub fn render(&self, pool: &Pool<Postgres>) -> Result<String, prometheus::Error> {
+        self.pool_size.set(pool.size() as i64);
+        self.pool_idle.set(pool.num_idle() as i64);
+
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        String::from_utf8(buffer).map_err(|err| prometheus::Error::Msg(err.to_string()))
+    }
+}
@@ -0,0 +1,7 @@
+mod rate_limit;
+mod request_log;
+mod request_metrics;
+
+pub use rate_limit::*; // Exportando o middleware de limite de requisições.
+pub use request_log::*; // Exportando o middleware de log das requisições.
+pub use request_metrics::*; // Exportando o middleware de métricas das requisições.
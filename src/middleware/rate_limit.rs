@@ -0,0 +1,117 @@
+use actix_web::{
+    body::{EitherBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue, RETRY_AFTER},
+    middleware::Next,
+    web::Data,
+    Error, ResponseError,
+};
+use tracing::warn;
+
+use crate::{
+    auth,
+    error::AppError,
+    rate_limit::{Client, CredentialFailure, Decision, Scope},
+    AppState,
+};
+
+// Método para inserir os cabeçalhos RateLimit-* com o estado do balde.
+fn insert_headers<B>(res: &mut ServiceResponse<B>, decision: &Decision) {
+    let headers = res.headers_mut();
+    let values = [
+        ("ratelimit-limit", decision.limit.to_string()),
+        ("ratelimit-remaining", decision.remaining.to_string()),
+        ("ratelimit-reset", decision.reset.to_string()),
+        (
+            "ratelimit-policy",
+            format!("{};w={}", decision.rate.requests, decision.rate.period),
+        ),
+    ];
+
+    for (name, value) in values {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(HeaderName::from_static(name), value);
+        }
+    }
+}
+
+// Método para criar a resposta 429 no formato de ServerError.
+fn too_many_requests<B>(
+    req: ServiceRequest,
+    message: &str,
+    retry_after: u64,
+) -> ServiceResponse<EitherBody<B>> {
+    let mut res = req
+        .into_response(AppError::TooManyRequests(message.to_string()).error_response())
+        .map_into_right_body();
+
+    res.headers_mut().insert(RETRY_AFTER, retry_after.into());
+    res
+}
+
+// Middleware que limita as requisições da api por escopo de rotas, com
+// um balde de fichas para o endereço IP e outro para o usuário autenticado,
+// e bloqueia progressivamente as rotas de autenticação para os endereços
+// que erram as credenciais.
+pub async fn rate_limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let limiter = req
+        .app_data::<Data<AppState>>()
+        .and_then(|data| data.limiter.clone());
+
+    let (limiter, scope) = match (limiter, Scope::of(req.method().as_str(), req.path())) {
+        (Some(limiter), Some(scope)) => (limiter, scope),
+        _ => return Ok(next.call(req).await?.map_into_left_body()), // Rota sem limite.
+    };
+
+    // O endereço do proxy só é utilizado quando configurado, evitando que
+    // o cliente escolha o próprio endereço pelo cabeçalho X-Forwarded-For.
+    let ip = match limiter.trust_proxy() {
+        true => req
+            .connection_info()
+            .realip_remote_addr()
+            .map(str::to_string),
+        false => req.peer_addr().map(|addr| addr.ip().to_string()),
+    }
+    .unwrap_or_else(|| "unix".to_string()); // Conexões pelo socket Unix.
+
+    // O bloqueio vale apenas para as rotas de autenticação, e um endereço
+    // bloqueado continua utilizando as demais rotas com o token já emitido.
+    if let Some(retry_after) = limiter.locked(&ip).filter(|_| scope == Scope::Auth) {
+        warn!(ip = %ip, retry_after, "request blocked after failed authentications"); // Propagando um log de aviso.
+        return Ok(too_many_requests(
+            req,
+            "Too many failed authentication attempts, try again later!",
+            retry_after,
+        ));
+    }
+
+    let mut decision = limiter.check(scope, Client::Ip(ip.clone()));
+    if let Ok(user) = auth::authorize(req.request()) {
+        decision = decision.strictest(limiter.check(scope, Client::User(user.id)));
+    }
+
+    if !decision.allowed {
+        warn!(ip = %ip, scope = scope.name(), "rate limit exceeded"); // Propagando um log de aviso.
+        let mut res = too_many_requests(req, "Too many requests, slow down!", decision.retry_after);
+        insert_headers(&mut res, &decision);
+        return Ok(res);
+    }
+
+    let mut res = next.call(req).await?;
+
+    // Apenas as credenciais incorretas marcadas pelos serviços contam como
+    // falhas, nunca um token de acesso ausente ou expirado. As falhas não são
+    // esquecidas após um sucesso, senão bastaria autenticar uma conta própria
+    // entre as tentativas para evitar o bloqueio.
+    if res.response().extensions().contains::<CredentialFailure>() {
+        if let Some(lock) = limiter.failure(&ip) {
+            warn!(ip = %ip, lock_secs = lock.as_secs(), "address blocked"); // Propagando um log de aviso.
+        }
+    }
+
+    insert_headers(&mut res, &decision);
+    Ok(res.map_into_left_body())
+}
@@ -0,0 +1,77 @@
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue},
+    middleware::Next,
+    Error,
+};
+use std::time::Instant;
+use tracing::{error, field, info, info_span, warn, Instrument};
+use uuid::Uuid;
+
+// Cabeçalho que carrega o identificador da requisição.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// Reaproveita o identificador enviado pelo cliente ou por um proxy quando
+// ele é seguro para logs e cabeçalhos, gerando um novo caso contrário.
+fn request_id(req: &ServiceRequest) -> String {
+    req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= 128)
+        .filter(|id| id.chars().all(|c| c.is_ascii_graphic()))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+// Middleware que abre o span "request" com o identificador da requisição,
+// propagado para todos os logs emitidos durante ela, e registra ao final
+// o método, a rota, o status, a latência e o usuário autenticado.
+pub async fn request_log(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let id = request_id(&req);
+    let span = info_span!(
+        "request",
+        request_id = %id,
+        method = %req.method(),
+        route = field::Empty,
+        user_id = field::Empty, // Registrado pelo extrator AuthUser.
+    );
+
+    let start = Instant::now();
+    let result = next.call(req).instrument(span.clone()).await;
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let _entered = span.enter();
+
+    let mut res = match result {
+        Ok(res) => res,
+        Err(err) => {
+            let status = err.as_response_error().status_code().as_u16();
+            error!(status, latency_ms, "request failed: {}", err);
+            return Err(err);
+        }
+    };
+
+    // Utilizando o padrão da rota para não espalhar ids pelos logs.
+    let route = res
+        .request()
+        .match_pattern()
+        .unwrap_or_else(|| "<unmatched>".to_string());
+    span.record("route", route.as_str());
+
+    let status = res.status().as_u16();
+    match status {
+        500.. => error!(status, latency_ms, "request completed"),
+        400..=499 => warn!(status, latency_ms, "request completed"),
+        _ => info!(status, latency_ms, "request completed"),
+    }
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        res.headers_mut()
+            .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+
+    Ok(res)
+}
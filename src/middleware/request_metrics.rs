@@ -0,0 +1,43 @@
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    middleware::Next,
+    web::Data,
+    Error,
+};
+use std::time::Instant;
+
+use crate::AppState;
+
+// Middleware que registra a contagem, a latência e os erros de
+// cada requisição nas métricas do Prometheus.
+pub async fn request_metrics(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let data = req.app_data::<Data<AppState>>().cloned();
+    let method = req.method().to_string();
+
+    let start = Instant::now();
+    let res = next.call(req).await?;
+
+    if let Some(data) = data {
+        // Requisições sem rota compartilham uma única série.
+        let route = res
+            .request()
+            .match_pattern()
+            .unwrap_or_else(|| "<unmatched>".to_string());
+        let status = res.status();
+
+        data.metrics
+            .observe_request(&method, &route, status.as_u16(), start.elapsed());
+
+        // O status do ServerError é o motivo do código HTTP em minúsculas.
+        if status.is_client_error() || status.is_server_error() {
+            let reason = status.canonical_reason().unwrap_or("error");
+            data.metrics.observe_error(&reason.to_lowercase());
+        }
+    }
+
+    Ok(res)
+}
@@ -0,0 +1,69 @@
+use sqlx::{migrate::Migrator, query_scalar, Pool, Postgres};
+use std::str::FromStr;
+
+// Migrações versionadas da pasta "migrations" embutidas no binário.
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+// Modo de execução das migrações escolhido na configuração.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Apply, // Aplica as migrações pendentes e inicia o servidor.
+    Only,  // Aplica as migrações pendentes e finaliza ("--migrate-only").
+    Skip,  // Não aplica as migrações ("--no-migrate").
+}
+
+// Situação do banco de dados em relação às migrações do binário.
+pub struct Status {
+    pub pending: Vec<i64>, // Versões do binário ainda não aplicadas.
+    pub unknown: Vec<i64>, // Versões aplicadas que o binário não conhece.
+}
+
+// Modo lido da configuração "migrate": "apply", "only" ou "skip".
+impl FromStr for Mode {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "apply" => Ok(Mode::Apply),
+            "only" => Ok(Mode::Only),
+            "skip" => Ok(Mode::Skip),
+            _ => Err(()),
+        }
+    }
+}
+
+// Método para comparar as migrações aplicadas no banco
+// de dados com as migrações embutidas no binário.
+pub async fn status(pool: &Pool<Postgres>) -> Result<Status, sqlx::Error> {
+    // A tabela de controle não existe antes da primeira migração.
+    let exists = query_scalar::<_, bool>("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+
+    let applied = match exists {
+        true => {
+            query_scalar::<_, i64>(
+                "SELECT version FROM _sqlx_migrations WHERE success ORDER BY version",
+            )
+            .fetch_all(pool)
+            .await?
+        }
+        false => Vec::new(),
+    };
+
+    Ok(Status {
+        pending: MIGRATOR
+            .iter()
+            .map(|migration| migration.version)
+            .filter(|version| !applied.contains(version))
+            .collect(),
+        unknown: applied
+            .into_iter()
+            .filter(|version| {
+                !MIGRATOR
+                    .iter()
+                    .any(|migration| migration.version == *version)
+            })
+            .collect(),
+    })
+}
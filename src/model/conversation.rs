@@ -0,0 +1,22 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Participantes de uma nova conversa, além do usuário autenticado.
+// Com um único participante a conversa é direta.
+#[derive(Deserialize, ToSchema)]
+#[schema(
+    as = ConversationModel,
+    example = json!({ "user_ids": ["4f6c2a3e-8d1b-4c5a-9e7f-2b3d4c5e6f70"] })
+)]
+pub struct Conversation {
+    #[schema(min_items = 1, max_items = 9)]
+    pub user_ids: Vec<Uuid>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[schema(as = DirectMessageModel, example = json!({ "content": "Olá!" }))]
+pub struct DirectMessage {
+    #[schema(min_length = 1, max_length = 1000)]
+    pub content: String,
+}
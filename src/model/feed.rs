@@ -0,0 +1,20 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct Feed {
+    pub global: Option<bool>,
+    pub user_id: Option<Uuid>,
+}
+
+// Filtros e retomada da conexão Server-Sent Events.
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct Stream {
+    pub user_id: Option<Uuid>,
+    #[param(max_length = 101)]
+    pub tag: Option<String>,
+    pub last_event_id: Option<u64>, // Alternativa ao cabeçalho Last-Event-ID.
+}
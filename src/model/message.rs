@@ -1,9 +1,23 @@
 use serde::Deserialize;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Deserialize)]
+// Conteúdo de uma mensagem adicionada, opcionalmente em
+// resposta a outra mensagem.
+#[derive(Deserialize, ToSchema)]
+#[schema(as = MessageModel, example = json!({ "content": "Olá, mundo!" }))]
 pub struct Message {
-    pub user_id: Uuid,
-    pub password: String,
+    #[schema(min_length = 1, max_length = 1000)]
+    pub content: String,
+    pub parent_id: Option<Uuid>, // Mensagem respondida.
+}
+
+// Novo conteúdo de uma mensagem editada. A mensagem respondida
+// não pode ser alterada, então campos extras são recusados.
+#[derive(Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = MessageEditModel, example = json!({ "content": "Olá, mundo editado!" }))]
+pub struct MessageEdit {
+    #[schema(min_length = 1, max_length = 1000)]
     pub content: String,
 }
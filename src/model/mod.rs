@@ -1,5 +1,19 @@
+mod conversation;
+mod feed;
 mod message;
+mod notification;
+mod page;
+mod search;
+mod session;
+mod thread;
 mod user;
 
+pub use conversation::*;
+pub use feed::*;
 pub use message::*;
+pub use notification::*;
+pub use page::*;
+pub use search::*;
+pub use session::*;
+pub use thread::*;
 pub use user::*;
@@ -0,0 +1,24 @@
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct Notifications {
+    #[param(default = false)]
+    pub unread: Option<bool>, // Apenas as notificações não lidas.
+}
+
+// Alterações das preferências de notificação do usuário autenticado.
+// Os tipos ausentes são mantidos.
+#[derive(Deserialize, ToSchema)]
+#[schema(
+    as = NotificationPreferencesModel,
+    description = "Absent kinds are kept.",
+    example = json!({ "likes": false })
+)]
+pub struct NotificationPreferences {
+    pub likes: Option<bool>,
+    pub replies: Option<bool>,
+    pub mentions: Option<bool>,
+    pub follows: Option<bool>,
+}
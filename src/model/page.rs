@@ -0,0 +1,12 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::pagination::DEFAULT_LIMIT;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct Page {
+    #[param(minimum = 1, maximum = 100, default = json!(DEFAULT_LIMIT))]
+    pub limit: Option<i64>,
+    pub cursor: Option<String>, // O next_cursor da página anterior.
+}
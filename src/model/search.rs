@@ -0,0 +1,12 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::search::Language;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct Search {
+    pub q: String,
+    #[param(inline)]
+    pub language: Option<Language>, // Sem o idioma, usa o configurado.
+}
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema)]
+#[schema(as = LoginModel, example = json!({ "name": "ana", "password": "12345678" }))]
+pub struct Login {
+    pub name: String,
+    #[schema(format = Password)]
+    pub password: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[schema(
+    as = RefreshModel,
+    example = json!({ "refresh_token": "tVvJ0n2lqvB1M3Qd8aJ6bq8c1fH9s0xYkQ2wPz7uE4o" })
+)]
+pub struct Refresh {
+    pub refresh_token: String,
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::service::thread::DEFAULT_DEPTH;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct Thread {
+    #[param(minimum = 1, maximum = 10, default = json!(DEFAULT_DEPTH))]
+    pub depth: Option<i32>,
+}
@@ -1,7 +1,35 @@
 use serde::Deserialize;
+use utoipa::ToSchema;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
+#[schema(as = UserModel, example = json!({ "name": "ana", "password": "12345678" }))]
 pub struct User {
+    #[schema(min_length = 1)]
     pub name: String,
+    #[schema(min_length = 8, format = Password)]
     pub password: String,
 }
+
+// Alterações do perfil do usuário autenticado. Os campos ausentes
+// são mantidos e um texto vazio remove o campo opcional. A nova
+// senha exige a senha atual.
+#[derive(Deserialize, ToSchema)]
+#[schema(
+    as = UserUpdateModel,
+    description = "Absent fields are kept and an empty text removes an optional field. Changing the password requires current_password and revokes every session.",
+    example = json!({ "display_name": "Ana", "bio": "Rustacean", "avatar_url": "" })
+)]
+pub struct UserUpdate {
+    #[schema(min_length = 1, max_length = 255)]
+    pub name: Option<String>,
+    #[schema(max_length = 64)]
+    pub display_name: Option<String>,
+    #[schema(max_length = 280)]
+    pub bio: Option<String>,
+    #[schema(max_length = 2048, format = "uri")]
+    pub avatar_url: Option<String>,
+    #[schema(min_length = 8, format = Password)]
+    pub password: Option<String>,
+    #[schema(format = Password)]
+    pub current_password: Option<String>,
+}
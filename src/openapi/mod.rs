@@ -0,0 +1,148 @@
+mod scope;
+
+pub use scope::*; // Exportando o escopo que registra as rotas documentadas.
+
+use actix_web::http::StatusCode;
+use std::sync::OnceLock;
+use utoipa::{
+    openapi::{
+        path::Operation,
+        schema::{ObjectBuilder, Type},
+        security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+        Content, Header, Ref, RefOr,
+    },
+    Modify, OpenApi,
+};
+
+use crate::{rate_limit, response, service};
+
+// Documento gerado a partir das anotações #[utoipa::path] dos
+// controladores e dos tipos de model, schema e response.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Global Rust Server",
+        description = "Users, messages, likes, direct messages and the real-time feed."
+    ),
+    nest(
+        (path = "/api/user", api = service::user::Api),
+        (path = "/api/tech", api = service::message::Api),
+        (path = "/api/dm", api = service::conversation::Api),
+        (path = "/api", api = service::docs::Api),
+        (path = "/health", api = service::health::Api),
+        (path = "/metrics", api = service::metrics::Api),
+    ),
+    tags(
+        (name = "user", description = "Users, sessions, follows and notifications."),
+        (name = "tech", description = "Messages, edits, likes, the timeline, the real-time feed and the event stream."),
+        (name = "dm", description = "Private conversations, direct messages and read receipts."),
+        (name = "health", description = "Liveness and readiness checks."),
+        (name = "metrics", description = "Prometheus metrics."),
+        (name = "docs", description = "This document and its interactive UI."),
+    ),
+    components(schemas(response::ServerError)),
+    modifiers(&Responses)
+)]
+struct Document;
+
+// Método para obter o documento OpenAPI 3 da api, montado na primeira chamada.
+pub fn document() -> &'static utoipa::openapi::OpenApi {
+    static DOCUMENT: OnceLock<utoipa::openapi::OpenApi> = OnceLock::new();
+    DOCUMENT.get_or_init(Document::openapi)
+}
+
+// Completa as respostas comuns a várias rotas: o token de acesso,
+// o limite de requisições e o formato de ServerError dos erros.
+struct Responses;
+
+impl Modify for Responses {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi.info.license = None; // Preenchida pelo Cargo.toml, que não tem licença.
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "bearer",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            );
+
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            let operations = [
+                ("GET", &mut item.get),
+                ("POST", &mut item.post),
+                ("PATCH", &mut item.patch),
+                ("DELETE", &mut item.delete),
+            ];
+
+            for (method, operation) in operations {
+                let Some(operation) = operation else {
+                    continue;
+                };
+
+                // As rotas autenticadas respondem 401 sem um token válido.
+                if operation.security.is_some() {
+                    error(operation, 401);
+                }
+
+                // As rotas limitadas respondem 429 ao exceder o limite, e as que
+                // acessam o banco de dados podem falhar com 500.
+                if rate_limit::Scope::of(method, path).is_some() {
+                    error(operation, 429);
+                    error(operation, 500);
+                }
+
+                describe(operation);
+            }
+        }
+    }
+}
+
+// Método para adicionar uma resposta de erro à operação.
+fn error(operation: &mut Operation, status: u16) {
+    operation
+        .responses
+        .responses
+        .entry(status.to_string())
+        .or_insert_with(|| RefOr::T(Default::default()));
+}
+
+// Método para preencher as respostas sem descrição com o motivo do status
+// e as respostas de erro sem conteúdo com o formato de ServerError.
+fn describe(operation: &mut Operation) {
+    for (status, response) in operation.responses.responses.iter_mut() {
+        let RefOr::T(response) = response else {
+            continue;
+        };
+        let status = status
+            .parse::<u16>()
+            .ok()
+            .and_then(|status| StatusCode::from_u16(status).ok());
+
+        if response.description.is_empty() {
+            response.description = status
+                .and_then(|status| status.canonical_reason())
+                .unwrap_or("Response")
+                .to_string();
+        }
+
+        let Some(status) = status.filter(|status| status.as_u16() >= 400) else {
+            continue;
+        };
+        if response.content.is_empty() {
+            response.content.insert(
+                "application/json".to_string(),
+                Content::new(Some(Ref::from_schema_name("ServerError"))),
+            );
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let mut header = Header::new(ObjectBuilder::new().schema_type(Type::Integer));
+            header.description = Some("Seconds until a new request is accepted.".to_string());
+            response.headers.insert("Retry-After".to_string(), header);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use actix_web::{
+    dev::{AppService, HttpServiceFactory},
+    web,
+};
+use utoipa::openapi::HttpMethod;
+
+// Rota registrada, com o método em maiúsculas e o caminho completo.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Route {
+    pub method: &'static str,
+    pub path: String,
+}
+
+// Escopo do actix que aceita apenas controladores documentados com
+// #[utoipa::path], guardando as rotas registradas para que os testes
+// as comparem com o documento.
+pub struct Scope {
+    path: String,
+    scope: actix_web::Scope,
+    routes: Vec<Route>,
+}
+
+// Método para criar um escopo no caminho informado.
+pub fn scope(path: &str) -> Scope {
+    Scope {
+        path: path.to_string(),
+        scope: web::scope(path),
+        routes: Vec::new(),
+    }
+}
+
+// Método para obter o nome do método HTTP como nas requisições.
+fn method_name(method: &HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::Get => "GET",
+        HttpMethod::Put => "PUT",
+        HttpMethod::Post => "POST",
+        HttpMethod::Delete => "DELETE",
+        HttpMethod::Options => "OPTIONS",
+        HttpMethod::Head => "HEAD",
+        HttpMethod::Patch => "PATCH",
+        HttpMethod::Trace => "TRACE",
+    }
+}
+
+impl Scope {
+    // Registra um controlador e as rotas descritas pelo seu #[utoipa::path].
+    pub fn service<H>(mut self, handler: H) -> Self
+    where
+        H: HttpServiceFactory + utoipa::Path + 'static,
+    {
+        for method in H::methods() {
+            self.routes.push(Route {
+                method: method_name(&method),
+                path: format!("{}{}", self.path, H::path()),
+            });
+        }
+        self.scope = self.scope.service(handler);
+        self
+    }
+
+    // Registra um escopo interno, prefixando as suas rotas.
+    pub fn nest(mut self, inner: Scope) -> Self {
+        for route in &inner.routes {
+            self.routes.push(Route {
+                method: route.method,
+                path: format!("{}{}", self.path, route.path),
+            });
+        }
+        self.scope = self.scope.service(inner.scope);
+        self
+    }
+
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+}
+
+impl HttpServiceFactory for Scope {
+    fn register(self, config: &mut AppService) {
+        self.scope.register(config)
+    }
+}
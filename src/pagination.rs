@@ -0,0 +1,72 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{error::AppError, model};
+
+// Quantidade de itens por página quando "limit" não é informado.
+pub const DEFAULT_LIMIT: i64 = 20;
+// Quantidade máxima de itens por página.
+pub const MAX_LIMIT: i64 = 100;
+
+// Página requisitada, com a chave do último item da página anterior.
+pub struct Page<K> {
+    pub limit: i64,
+    pub after: Option<K>,
+}
+
+impl<K> Page<K> {
+    // Quantidade de linhas a buscar, uma a mais para saber se há outra página.
+    pub fn fetch(&self) -> i64 {
+        self.limit + 1
+    }
+}
+
+// Cria o erro de paginação inválida.
+fn bad_request(message: &str) -> AppError {
+    AppError::BadRequest(message.to_string())
+}
+
+// Método para validar os parâmetros de paginação e decodificar o cursor.
+pub fn parse<K: DeserializeOwned>(query: &model::Page) -> Result<Page<K>, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(bad_request(&format!(
+            "The limit must be between 1 and {}!",
+            MAX_LIMIT
+        )));
+    }
+
+    let after = match &query.cursor {
+        Some(cursor) => Some(
+            URL_SAFE_NO_PAD
+                .decode(cursor)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<K>(&bytes).ok())
+                .ok_or_else(|| bad_request("Invalid cursor!"))?,
+        ),
+        None => None,
+    };
+
+    Ok(Page { limit, after })
+}
+
+// Método para separar a linha excedente buscada por Page::fetch,
+// retornando os itens da página e o cursor da próxima página.
+pub fn finish<T, K: Serialize>(
+    mut rows: Vec<T>,
+    page: &Page<K>,
+    key: impl Fn(&T) -> K,
+) -> (Vec<T>, Option<String>) {
+    if rows.len() as i64 <= page.limit {
+        return (rows, None); // Última página.
+    }
+
+    rows.truncate(page.limit as usize);
+    let cursor = rows
+        .last()
+        .and_then(|last| serde_json::to_vec(&key(last)).ok())
+        .map(|bytes| URL_SAFE_NO_PAD.encode(bytes));
+
+    (rows, cursor)
+}
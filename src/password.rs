@@ -0,0 +1,138 @@
+use actix_web::web::block;
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Algorithm, Argon2, Params, Version,
+};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use subtle::ConstantTimeEq;
+
+// Prefixo de todo hash gerado com Argon2id no formato PHC.
+const ARGON2ID_PREFIX: &str = "$argon2id$";
+// Salt e saída fixos do hash fictício, em base64 sem preenchimento.
+const DUMMY_SALT: &str = "ZHVtbXlkdW1teWR1bW15";
+const DUMMY_OUTPUT: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+// Estrutura responsável por gerar e verificar os hashes
+// de senha com Argon2id a partir dos custos configurados.
+#[derive(Clone)]
+pub struct Hasher {
+    params: Params,
+    dummy: String, // Hash fictício com os custos atuais, sem senha correspondente.
+}
+
+// Resultado da verificação de uma senha.
+#[derive(PartialEq, Eq)]
+pub enum Verification {
+    Valid,    // A senha confere e o hash está atualizado.
+    Outdated, // A senha confere, mas está em texto puro ou com custos antigos.
+    Invalid,  // A senha não confere.
+}
+
+// Erro retornado quando não é possivel gerar um hash.
+#[derive(Debug)]
+pub struct HashError(String);
+
+impl Display for HashError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "failed to hash the password: {}", self.0)
+    }
+}
+
+impl Hasher {
+    // Cria um Hasher a partir do custo de memória (KiB),
+    // do número de iterações e do paralelismo.
+    pub fn new(memory_cost: u32, time_cost: u32, parallelism: u32) -> Result<Self, argon2::Error> {
+        let params = Params::new(memory_cost, time_cost, parallelism, None)?;
+        let dummy = format!(
+            "{}v=19$m={},t={},p={}${}${}",
+            ARGON2ID_PREFIX,
+            params.m_cost(),
+            params.t_cost(),
+            params.p_cost(),
+            DUMMY_SALT,
+            DUMMY_OUTPUT
+        );
+
+        Ok(Self { params, dummy })
+    }
+
+    // Hash verificado quando o usuário não existe, para que a resposta
+    // leve o mesmo tempo de uma senha incorreta.
+    pub fn dummy(&self) -> &str {
+        &self.dummy
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+
+    // Gera o hash no formato PHC para ser armazenado em rust_user.password.
+    pub fn hash_sync(&self, password: &str) -> Result<String, HashError> {
+        let salt = SaltString::generate(&mut OsRng); // Gerando um salt aleatório.
+
+        self.argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|err| HashError(err.to_string()))
+    }
+
+    // Verifica a senha contra o valor armazenado em tempo constante.
+    // Valores que não estão no formato Argon2id são tratados como
+    // senhas legadas em texto puro.
+    pub fn verify_sync(&self, password: &str, stored: &str) -> Verification {
+        if !stored.starts_with(ARGON2ID_PREFIX) {
+            // Comparando a senha legada sem interromper no primeiro byte diferente.
+            return match bool::from(password.as_bytes().ct_eq(stored.as_bytes())) {
+                true => Verification::Outdated,
+                false => Verification::Invalid,
+            };
+        }
+
+        let hash = match PasswordHash::new(stored) {
+            Ok(hash) => hash,
+            Err(_) => return Verification::Invalid, // Hash corrompido nunca autentica.
+        };
+
+        if self
+            .argon2()
+            .verify_password(password.as_bytes(), &hash)
+            .is_err()
+        {
+            return Verification::Invalid;
+        }
+
+        // Verificando se o hash foi gerado com os custos atuais.
+        match Params::try_from(&hash) {
+            Ok(params)
+                if params.m_cost() == self.params.m_cost()
+                    && params.t_cost() == self.params.t_cost()
+                    && params.p_cost() == self.params.p_cost() =>
+            {
+                Verification::Valid
+            }
+            _ => Verification::Outdated,
+        }
+    }
+
+    // Gera o hash fora das threads do servidor, pois o Argon2 é custoso.
+    pub async fn hash(&self, password: &str) -> Result<String, HashError> {
+        let hasher = self.clone();
+        let password = password.to_string();
+
+        match block(move || hasher.hash_sync(&password)).await {
+            Ok(result) => result,
+            Err(err) => Err(HashError(err.to_string())),
+        }
+    }
+
+    // Verifica a senha fora das threads do servidor, pois o Argon2 é custoso.
+    pub async fn verify(&self, password: &str, stored: &str) -> Verification {
+        let hasher = self.clone();
+        let password = password.to_string();
+        let stored = stored.to_string();
+
+        block(move || hasher.verify_sync(&password, &stored))
+            .await
+            .unwrap_or(Verification::Invalid)
+    }
+}
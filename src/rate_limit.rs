@@ -0,0 +1,269 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::config;
+
+// Quantidade de chaves a partir da qual os baldes cheios são descartados.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+// Limite de um escopo: quantidade de requisições reabastecidas
+// ao longo do período ("60/60" permite 60 requisições por minuto).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Rate {
+    pub requests: u32,
+    pub period: u64, // Segundos.
+}
+
+impl Rate {
+    // Fichas reabastecidas por segundo.
+    fn per_second(&self) -> f64 {
+        self.requests as f64 / self.period as f64
+    }
+}
+
+impl FromStr for Rate {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (requests, period) = value.split_once('/').ok_or(())?;
+        let rate = Rate {
+            requests: requests.trim().parse().map_err(|_| ())?,
+            period: period.trim().parse().map_err(|_| ())?,
+        };
+
+        match rate.requests > 0 && rate.period > 0 {
+            true => Ok(rate),
+            false => Err(()),
+        }
+    }
+}
+
+impl Display for Rate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}/{}", self.requests, self.period)
+    }
+}
+
+// Extensão da resposta que indica credenciais incorretas (senha ou token de
+// atualização), as únicas falhas contadas pelo bloqueio de endereços.
+#[derive(Clone, Copy, Debug)]
+pub struct CredentialFailure;
+
+// Escopos de rotas com limites próprios.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Scope {
+    Auth, // Login, renovação de sessão, criação e alteração de usuários.
+    User, // Demais rotas de "api/user".
+    Tech, // Rotas de "api/tech" e das mensagens diretas em "api/dm".
+}
+
+impl Scope {
+    // Método para classificar uma requisição pelo caminho e método.
+    // As rotas fora da api (saúde e métricas) não são limitadas.
+    pub fn of(method: &str, path: &str) -> Option<Scope> {
+        match path {
+            "/api/user/login" | "/api/user/refresh" => Some(Scope::Auth),
+            "/api/user/manage" if method == "POST" || method == "PATCH" => Some(Scope::Auth),
+            _ if path.starts_with("/api/user") => Some(Scope::User),
+            _ if path.starts_with("/api/tech") || path.starts_with("/api/dm") => Some(Scope::Tech),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scope::Auth => "auth",
+            Scope::User => "user",
+            Scope::Tech => "tech",
+        }
+    }
+}
+
+// Cliente identificado pelo endereço IP ou pelo usuário autenticado.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Client {
+    Ip(String),
+    User(uuid::Uuid),
+}
+
+// Resultado da verificação de um balde.
+#[derive(Clone, Copy, Debug)]
+pub struct Decision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset: u64,       // Segundos até o balde encher novamente.
+    pub retry_after: u64, // Segundos até a próxima ficha, zero quando permitido.
+    pub rate: Rate,
+}
+
+impl Decision {
+    // Método para escolher a decisão mais restritiva entre duas.
+    pub fn strictest(self, other: Decision) -> Decision {
+        match (self.allowed, other.allowed) {
+            (true, false) => other,
+            (false, true) => self,
+            (false, false) if other.retry_after > self.retry_after => other,
+            (true, true) if other.remaining < self.remaining => other,
+            _ => self,
+        }
+    }
+}
+
+// Balde de fichas de um cliente em um escopo.
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    // Reabastece o balde de acordo com o tempo passado.
+    fn refill(&mut self, rate: Rate, now: Instant) {
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate.per_second()).min(rate.requests as f64);
+        self.updated = now;
+    }
+}
+
+// Falhas de autenticação seguidas de um endereço IP.
+struct Failures {
+    count: u32,
+    last: Instant,
+    locked_until: Option<Instant>,
+}
+
+#[derive(Default)]
+struct State {
+    buckets: HashMap<(Scope, Client), Bucket>,
+    failures: HashMap<String, Failures>,
+}
+
+// Limitador de requisições por balde de fichas, compartilhado entre os
+// workers, com bloqueio progressivo após falhas de autenticação.
+#[derive(Clone)]
+pub struct Limiter {
+    config: Arc<config::RateLimit>,
+    state: Arc<Mutex<State>>,
+}
+
+impl Limiter {
+    pub fn new(config: config::RateLimit) -> Self {
+        Self {
+            config: Arc::new(config),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    // Indica se o endereço do cliente vem do cabeçalho do proxy.
+    pub fn trust_proxy(&self) -> bool {
+        self.config.trust_proxy
+    }
+
+    // Limite configurado para o escopo.
+    fn rate(&self, scope: Scope) -> Rate {
+        match scope {
+            Scope::Auth => self.config.auth,
+            Scope::User => self.config.user,
+            Scope::Tech => self.config.tech,
+        }
+    }
+
+    // Método para consumir uma ficha do balde do cliente no escopo.
+    pub fn check(&self, scope: Scope, client: Client) -> Decision {
+        let rate = self.rate(scope);
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+
+        if state.buckets.len() > PRUNE_THRESHOLD {
+            // Descartando os baldes que já estariam cheios.
+            state.buckets.retain(|(scope, _), bucket| {
+                let rate = self.rate(*scope);
+                bucket.refill(rate, now);
+                bucket.tokens < rate.requests as f64
+            });
+        }
+
+        let bucket = state
+            .buckets
+            .entry((scope, client))
+            .or_insert_with(|| Bucket {
+                tokens: rate.requests as f64,
+                updated: now,
+            });
+        bucket.refill(rate, now);
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        Decision {
+            allowed,
+            limit: rate.requests,
+            remaining: bucket.tokens.floor() as u32,
+            reset: ((rate.requests as f64 - bucket.tokens) / rate.per_second()).ceil() as u64,
+            retry_after: match allowed {
+                true => 0,
+                false => ((1.0 - bucket.tokens) / rate.per_second()).ceil().max(1.0) as u64,
+            },
+            rate,
+        }
+    }
+
+    // Método para obter os segundos restantes do bloqueio do endereço.
+    pub fn locked(&self, ip: &str) -> Option<u64> {
+        let now = Instant::now();
+        let state = self.state.lock().unwrap();
+
+        state
+            .failures
+            .get(ip)
+            .and_then(|failures| failures.locked_until)
+            .filter(|until| *until > now)
+            .map(|until| until.duration_since(now).as_secs_f64().ceil() as u64)
+    }
+
+    // Método para registrar uma falha de autenticação, bloqueando o
+    // endereço por um tempo que dobra a cada nova falha após as toleradas.
+    // Retorna a duração do bloqueio aplicado.
+    pub fn failure(&self, ip: &str) -> Option<Duration> {
+        let now = Instant::now();
+        let max = Duration::from_secs(self.config.backoff_max);
+        let mut state = self.state.lock().unwrap();
+
+        if state.failures.len() > PRUNE_THRESHOLD {
+            // Descartando as falhas antigas o suficiente para serem esquecidas.
+            state
+                .failures
+                .retain(|_, failures| now.duration_since(failures.last) < max);
+        }
+
+        let failures = state.failures.entry(ip.to_string()).or_insert(Failures {
+            count: 0,
+            last: now,
+            locked_until: None,
+        });
+
+        // Esquecendo as falhas após um período sem novas tentativas.
+        if now.duration_since(failures.last) >= max {
+            failures.count = 0;
+        }
+        failures.count += 1;
+        failures.last = now;
+
+        let exceeding = failures
+            .count
+            .checked_sub(self.config.failures)
+            .filter(|&n| n > 0)?;
+        let lock = Duration::from_secs(self.config.backoff)
+            .saturating_mul(2u32.saturating_pow((exceeding - 1).min(31)))
+            .min(max);
+        failures.locked_until = Some(now + lock);
+        Some(lock)
+    }
+}
@@ -0,0 +1,1525 @@
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use std::{
+    cmp::Ordering,
+    future::ready,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use uuid::Uuid;
+
+use super::{
+    check_ownership, direct_key, BoxFuture, ConversationRepository, MessageRepository,
+    NotificationChanges, ProfileChanges, RankKey, TimeKey, UserKey, UserRepository,
+};
+use crate::{error::AppError, pagination::Page, response, schema, search};
+
+// Usuário armazenado, com as colunas de rust_user.
+struct User {
+    id: Uuid,
+    name: String,
+    password: String,
+    created_at: DateTime<Utc>,
+    display_name: Option<String>,
+    bio: Option<String>,
+    avatar_url: Option<String>,
+}
+
+impl User {
+    // Credenciais do usuário, como lidas do PostgreSQL.
+    fn credentials(&self) -> schema::User {
+        schema::User {
+            id: self.id,
+            password: self.password.clone(),
+        }
+    }
+}
+
+// Token de atualização armazenado.
+struct RefreshToken {
+    user_id: Uuid,
+    hash: String,
+    expires_at: DateTime<Utc>,
+    revoked: bool,
+}
+
+// Curtida de uma mensagem.
+struct Like {
+    message_id: Uuid,
+    user_id: Uuid,
+    liked_at: DateTime<Utc>,
+}
+
+// Usuário seguido por outro.
+struct Follow {
+    follower_id: Uuid,
+    followed_id: Uuid,
+    followed_at: DateTime<Utc>,
+}
+
+// Notificação recebida por um usuário.
+struct Notification {
+    id: Uuid,
+    user_id: Uuid,
+    kind: schema::NotificationKind,
+    actor_id: Uuid,
+    message_id: Option<Uuid>,
+    source_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    read_at: Option<DateTime<Utc>>,
+}
+
+// Conversa entre usuários. Somente as conversas diretas têm chave.
+struct Conversation {
+    id: Uuid,
+    direct_key: Option<String>,
+    created_at: DateTime<Utc>,
+    last_message_at: DateTime<Utc>,
+}
+
+// Participante de uma conversa.
+struct Member {
+    conversation_id: Uuid,
+    user_id: Uuid,
+    last_read_at: Option<DateTime<Utc>>,
+}
+
+// Mensagem de uma conversa.
+struct DirectMessage {
+    id: Uuid,
+    conversation_id: Uuid,
+    user_id: Uuid,
+    content: String,
+    sended_at: DateTime<Utc>,
+}
+
+// Tabelas mantidas em memória.
+#[derive(Default)]
+struct Store {
+    users: Vec<User>,
+    messages: Vec<schema::Message>,
+    revisions: Vec<schema::MessageRevision>,
+    likes: Vec<Like>,
+    follows: Vec<Follow>,
+    tokens: Vec<RefreshToken>,
+    notifications: Vec<Notification>,
+    preferences: Vec<(Uuid, response::NotificationPreferences)>,
+    conversations: Vec<Conversation>,
+    members: Vec<Member>,
+    direct_messages: Vec<DirectMessage>,
+}
+
+impl Store {
+    // Usuário público com a quantidade de mensagens.
+    fn public_user(&self, user: &User) -> response::User {
+        response::User {
+            id: user.id,
+            name: user.name.clone(),
+            created_at: user.created_at,
+            message_count: self.visible().filter(|m| m.user_id == user.id).count() as i64,
+        }
+    }
+
+    // Perfil público com as quantidades de mensagens e curtidas.
+    fn profile(&self, user: &User) -> response::Profile {
+        let messages = self.visible().filter(|m| m.user_id == user.id);
+
+        response::Profile {
+            id: user.id,
+            name: user.name.clone(),
+            display_name: user.display_name.clone(),
+            bio: user.bio.clone(),
+            avatar_url: user.avatar_url.clone(),
+            created_at: user.created_at,
+            message_count: messages.clone().count() as i64,
+            likes_given: self.likes.iter().filter(|l| l.user_id == user.id).count() as i64,
+            likes_received: messages.map(|m| m.likes as i64).sum(),
+        }
+    }
+
+    // Mensagens que não foram removidas, sem os tombstones.
+    fn visible(&self) -> impl Iterator<Item = &schema::Message> + Clone {
+        self.messages.iter().filter(|m| m.deleted_at.is_none())
+    }
+
+    fn user_exists(&self, id: Uuid) -> bool {
+        self.users.iter().any(|u| u.id == id)
+    }
+
+    // Entidades com as menções resolvidas pelo nome, descartando as desconhecidas.
+    fn resolve_mentions(&self, entities: &[schema::Entity]) -> Vec<schema::Entity> {
+        entities
+            .iter()
+            .filter_map(|e| match e.kind {
+                schema::EntityKind::Hashtag => Some(e.clone()),
+                schema::EntityKind::Mention => {
+                    self.users
+                        .iter()
+                        .find(|u| u.name == e.text)
+                        .map(|u| schema::Entity {
+                            user_id: Some(u.id),
+                            ..e.clone()
+                        })
+                }
+            })
+            .collect()
+    }
+
+    // Mensagens visíveis com alguma entidade que atende ao filtro.
+    fn with_entity(&self, filter: impl Fn(&schema::Entity) -> bool) -> Vec<schema::Message> {
+        self.visible()
+            .filter(|m| m.entities.iter().any(&filter))
+            .cloned()
+            .collect()
+    }
+
+    fn preferences(&self, user_id: Uuid) -> response::NotificationPreferences {
+        self.preferences
+            .iter()
+            .find(|(id, _)| *id == user_id)
+            .map(|(_, preferences)| preferences.clone())
+            .unwrap_or_default()
+    }
+
+    // Registra a notificação como a função add_notification do PostgreSQL: apenas
+    // quando o usuário não é o autor do evento e não desativou o tipo.
+    fn notify(
+        &mut self,
+        user_id: Uuid,
+        actor_id: Uuid,
+        kind: schema::NotificationKind,
+        message_id: Option<Uuid>,
+        source_id: Option<Uuid>,
+    ) {
+        let preferences = self.preferences(user_id);
+        let enabled = match kind {
+            schema::NotificationKind::Like => preferences.likes,
+            schema::NotificationKind::Reply => preferences.replies,
+            schema::NotificationKind::Mention => preferences.mentions,
+            schema::NotificationKind::Follow => preferences.follows,
+        };
+
+        if user_id != actor_id && enabled {
+            self.notifications.push(Notification {
+                id: Uuid::new_v4(),
+                user_id,
+                kind,
+                actor_id,
+                message_id,
+                source_id,
+                created_at: Utc::now(),
+                read_at: None,
+            });
+        }
+    }
+
+    // Notifica os usuários mencionados na mensagem que ainda não foram notificados
+    // e remove as notificações das menções retiradas, como os gatilhos de message_mentions.
+    fn notify_mentions(&mut self, message: &schema::Message, previous: &[schema::Entity]) {
+        let mentioned = |entities: &[schema::Entity]| -> Vec<Uuid> {
+            entities.iter().filter_map(|e| e.user_id).collect()
+        };
+        let (current, previous) = (mentioned(&message.entities), mentioned(previous));
+
+        self.notifications.retain(|n| {
+            n.kind != schema::NotificationKind::Mention
+                || n.message_id != Some(message.id)
+                || current.contains(&n.user_id)
+        });
+        for (i, user_id) in current.iter().enumerate() {
+            if !previous.contains(user_id) && !current[..i].contains(user_id) {
+                self.notify(
+                    *user_id,
+                    message.user_id,
+                    schema::NotificationKind::Mention,
+                    Some(message.id),
+                    None,
+                );
+            }
+        }
+    }
+
+    // Conversa pelo ponto de vista do participante, com os participantes
+    // ordenados pelo nome e as mensagens dos outros ainda não lidas.
+    fn conversation(&self, id: Uuid, user_id: Uuid) -> Option<response::Conversation> {
+        let conversation = self.conversations.iter().find(|c| c.id == id)?;
+        let me = self
+            .members
+            .iter()
+            .find(|m| m.conversation_id == id && m.user_id == user_id)?;
+
+        let mut members: Vec<response::Member> = self
+            .members
+            .iter()
+            .filter(|m| m.conversation_id == id)
+            .filter_map(|m| {
+                let user = self.users.iter().find(|u| u.id == m.user_id)?;
+                Some(response::Member {
+                    user_id: m.user_id,
+                    name: user.name.clone(),
+                    last_read_at: m.last_read_at,
+                })
+            })
+            .collect();
+        members.sort_by(|a, b| (&a.name, a.user_id).cmp(&(&b.name, b.user_id)));
+
+        Some(response::Conversation {
+            id,
+            direct: conversation.direct_key.is_some(),
+            members,
+            created_at: conversation.created_at,
+            last_message_at: conversation.last_message_at,
+            unread: self.unread_messages(me).count() as i64,
+        })
+    }
+
+    // Mensagens dos outros participantes ainda não lidas pelo participante.
+    fn unread_messages<'a>(&'a self, me: &'a Member) -> impl Iterator<Item = &'a DirectMessage> {
+        self.direct_messages.iter().filter(move |d| {
+            d.conversation_id == me.conversation_id
+                && d.user_id != me.user_id
+                && me.last_read_at.is_none_or(|at| d.sended_at > at)
+        })
+    }
+
+    // Mensagem com os participantes que a leram, além do autor.
+    fn direct_message(&self, message: &DirectMessage) -> response::DirectMessage {
+        let mut read_by: Vec<Uuid> = self
+            .members
+            .iter()
+            .filter(|m| m.conversation_id == message.conversation_id)
+            .filter(|m| m.user_id != message.user_id)
+            .filter(|m| m.last_read_at.is_some_and(|at| at >= message.sended_at))
+            .map(|m| m.user_id)
+            .collect();
+        read_by.sort();
+
+        response::DirectMessage {
+            id: message.id,
+            conversation_id: message.conversation_id,
+            user_id: message.user_id,
+            content: message.content.clone(),
+            sended_at: message.sended_at,
+            read_by,
+        }
+    }
+
+    // Usuários ligados pelos follows, com a data em que passaram a seguir.
+    fn follow_list(&self, filter: impl Fn(&Follow) -> Option<Uuid>) -> Vec<response::Follow> {
+        self.follows
+            .iter()
+            .filter_map(|f| {
+                let user_id = filter(f)?;
+                let user = self.users.iter().find(|u| u.id == user_id)?;
+                Some(response::Follow {
+                    user_id,
+                    name: user.name.clone(),
+                    followed_at: f.followed_at,
+                })
+            })
+            .collect()
+    }
+
+    // Remove as curtidas que atendem ao filtro, mantendo os contadores.
+    fn remove_likes(&mut self, filter: impl Fn(&Like) -> bool) -> bool {
+        let (removed, kept) = std::mem::take(&mut self.likes)
+            .into_iter()
+            .partition::<Vec<_>, _>(&filter);
+        self.likes = kept;
+
+        self.notifications.retain(|n| {
+            n.kind != schema::NotificationKind::Like
+                || !removed
+                    .iter()
+                    .any(|l| n.message_id == Some(l.message_id) && n.actor_id == l.user_id)
+        });
+        for like in &removed {
+            if let Some(message) = self.messages.iter_mut().find(|m| m.id == like.message_id) {
+                message.likes -= 1;
+            }
+        }
+        !removed.is_empty()
+    }
+
+    // Remove as mensagens que atendem ao filtro como os gatilhos e as chaves
+    // estrangeiras do PostgreSQL: as respostas passam a ser raízes, os contadores
+    // das mensagens respondidas diminuem e os tombstones sem respostas são removidos.
+    fn remove_messages(&mut self, filter: &dyn Fn(&schema::Message) -> bool) {
+        let (removed, kept) = std::mem::take(&mut self.messages)
+            .into_iter()
+            .partition::<Vec<_>, _>(|m| filter(m));
+        self.messages = kept;
+
+        let ids: Vec<Uuid> = removed.iter().map(|m| m.id).collect();
+        self.remove_likes(|l| ids.contains(&l.message_id));
+        self.revisions.retain(|r| !ids.contains(&r.message_id));
+        self.notifications.retain(|n| {
+            !n.message_id.is_some_and(|id| ids.contains(&id))
+                && !n.source_id.is_some_and(|id| ids.contains(&id))
+        });
+
+        let mut emptied = Vec::new();
+        for message in self.messages.iter_mut() {
+            if message.parent_id.is_some_and(|id| ids.contains(&id)) {
+                message.parent_id = None;
+            }
+        }
+        for parent_id in removed.iter().filter_map(|m| m.parent_id) {
+            if let Some(parent) = self.messages.iter_mut().find(|m| m.id == parent_id) {
+                parent.reply_count -= 1;
+                if parent.deleted_at.is_some() && parent.reply_count == 0 {
+                    emptied.push(parent.id);
+                }
+            }
+        }
+
+        if !emptied.is_empty() {
+            self.remove_messages(&|m| emptied.contains(&m.id));
+        }
+    }
+}
+
+// Repositório que mantém os dados em memória, com as mesmas regras do
+// PostgreSQL (unicidade, chaves estrangeiras e remoções em cascata).
+// Utilizado pelos testes das rotas, que rodam sem um banco de dados.
+#[derive(Clone, Default)]
+pub struct MemoryRepository {
+    store: Arc<Mutex<Store>>,
+}
+
+impl MemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn store(&self) -> MutexGuard<'_, Store> {
+        self.store.lock().unwrap()
+    }
+}
+
+// Método para retornar um resultado já calculado como BoxFuture.
+fn done<'a, T: Send + 'a>(result: Result<T, AppError>) -> BoxFuture<'a, T> {
+    Box::pin(ready(result))
+}
+
+// Método para ordenar os itens pela chave e aplicar o cursor e o limite da página.
+fn paginate<T, K: PartialOrd>(
+    mut items: Vec<T>,
+    page: &Page<K>,
+    key: impl Fn(&T) -> K,
+    descending: bool,
+) -> Vec<T> {
+    items.sort_by(|a, b| {
+        let order = key(a).partial_cmp(&key(b)).unwrap_or(Ordering::Equal);
+        match descending {
+            true => order.reverse(),
+            false => order,
+        }
+    });
+
+    items
+        .into_iter()
+        .filter(|item| match &page.after {
+            Some(after) if descending => key(item) < *after,
+            Some(after) => key(item) > *after,
+            None => true,
+        })
+        .take(page.fetch() as usize)
+        .collect()
+}
+
+impl UserRepository for MemoryRepository {
+    fn all_users<'a>(&'a self, page: &'a Page<UserKey>) -> BoxFuture<'a, Vec<response::User>> {
+        let store = self.store();
+        let users = store.users.iter().map(|u| store.public_user(u)).collect();
+        done(Ok(paginate(
+            users,
+            page,
+            |u: &response::User| (u.name.clone(), u.id),
+            false,
+        )))
+    }
+
+    fn find_user(&self, id: Uuid) -> BoxFuture<'_, Option<response::User>> {
+        let store = self.store();
+        let user = store.users.iter().find(|u| u.id == id);
+        done(Ok(user.map(|u| store.public_user(u))))
+    }
+
+    fn missing_users<'a>(&'a self, ids: &'a [Uuid]) -> BoxFuture<'a, Vec<Uuid>> {
+        let store = self.store();
+        let missing = ids
+            .iter()
+            .filter(|id| !store.users.iter().any(|u| u.id == **id))
+            .copied()
+            .collect();
+        done(Ok(missing))
+    }
+
+    fn find_users_by_name<'a>(
+        &'a self,
+        name: &'a str,
+        page: &'a Page<UserKey>,
+    ) -> BoxFuture<'a, Vec<response::User>> {
+        let store = self.store();
+        let users = store
+            .users
+            .iter()
+            .filter(|u| u.name.contains(name))
+            .map(|u| store.public_user(u))
+            .collect();
+        done(Ok(paginate(
+            users,
+            page,
+            |u: &response::User| (u.name.clone(), u.id),
+            false,
+        )))
+    }
+
+    fn find_profile(&self, id: Uuid) -> BoxFuture<'_, Option<response::Profile>> {
+        let store = self.store();
+        let user = store.users.iter().find(|u| u.id == id);
+        done(Ok(user.map(|u| store.profile(u))))
+    }
+
+    fn find_credentials<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Option<schema::User>> {
+        let store = self.store();
+        done(Ok(store
+            .users
+            .iter()
+            .find(|u| u.name == name)
+            .map(User::credentials)))
+    }
+
+    fn find_credentials_by_id(&self, id: Uuid) -> BoxFuture<'_, Option<schema::User>> {
+        let store = self.store();
+        done(Ok(store
+            .users
+            .iter()
+            .find(|u| u.id == id)
+            .map(User::credentials)))
+    }
+
+    fn add_user<'a>(&'a self, name: &'a str, hash: &'a str) -> BoxFuture<'a, response::User> {
+        let mut store = self.store();
+        if store.users.iter().any(|u| u.name == name) {
+            return done(Err(AppError::name_in_use()));
+        }
+
+        let user = User {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            password: hash.to_string(),
+            created_at: Utc::now(),
+            display_name: None,
+            bio: None,
+            avatar_url: None,
+        };
+        let public = store.public_user(&user);
+        store.users.push(user);
+        done(Ok(public))
+    }
+
+    fn update_profile<'a>(
+        &'a self,
+        id: Uuid,
+        changes: &'a ProfileChanges<'a>,
+    ) -> BoxFuture<'a, bool> {
+        let mut store = self.store();
+        if let Some(name) = changes.name {
+            if store.users.iter().any(|u| u.name == name && u.id != id) {
+                return done(Err(AppError::name_in_use()));
+            }
+        }
+
+        let user = match store.users.iter_mut().find(|u| u.id == id) {
+            Some(user) => user,
+            None => return done(Ok(false)),
+        };
+
+        // Um texto vazio remove o campo, como o NULLIF do PostgreSQL.
+        let optional = |value: &str| Some(value.to_string()).filter(|v| !v.is_empty());
+        if let Some(name) = changes.name {
+            user.name = name.to_string();
+        }
+        if let Some(display_name) = changes.display_name {
+            user.display_name = optional(display_name);
+        }
+        if let Some(bio) = changes.bio {
+            user.bio = optional(bio);
+        }
+        if let Some(avatar_url) = changes.avatar_url {
+            user.avatar_url = optional(avatar_url);
+        }
+        if let Some(password) = changes.password {
+            user.password = password.to_string();
+        }
+        done(Ok(true))
+    }
+
+    fn update_password<'a>(&'a self, id: Uuid, hash: &'a str) -> BoxFuture<'a, ()> {
+        let mut store = self.store();
+        if let Some(user) = store.users.iter_mut().find(|u| u.id == id) {
+            user.password = hash.to_string();
+        }
+        done(Ok(()))
+    }
+
+    fn delete_user(&self, id: Uuid) -> BoxFuture<'_, bool> {
+        let mut store = self.store();
+        if !store.user_exists(id) {
+            return done(Ok(false));
+        }
+
+        // Removendo em cascata como as chaves estrangeiras do PostgreSQL.
+        store.remove_messages(&|m| m.user_id == id);
+        store.remove_likes(|l| l.user_id == id);
+        store.tokens.retain(|t| t.user_id != id);
+        store
+            .follows
+            .retain(|f| f.follower_id != id && f.followed_id != id);
+        store
+            .notifications
+            .retain(|n| n.user_id != id && n.actor_id != id);
+        store.preferences.retain(|(user_id, _)| *user_id != id);
+        store.members.retain(|m| m.user_id != id);
+        store.direct_messages.retain(|d| d.user_id != id);
+        store.users.retain(|u| u.id != id);
+        done(Ok(true))
+    }
+
+    fn add_refresh_token<'a>(
+        &'a self,
+        user_id: Uuid,
+        hash: &'a str,
+        expires_at: DateTime<Utc>,
+    ) -> BoxFuture<'a, ()> {
+        let mut store = self.store();
+        if !store.user_exists(user_id) {
+            return done(Err(AppError::missing_reference()));
+        }
+
+        store.tokens.push(RefreshToken {
+            user_id,
+            hash: hash.to_string(),
+            expires_at,
+            revoked: false,
+        });
+        done(Ok(()))
+    }
+
+    fn consume_refresh_token<'a>(&'a self, hash: &'a str) -> BoxFuture<'a, Option<Uuid>> {
+        let mut store = self.store();
+        let now = Utc::now();
+        let token = store
+            .tokens
+            .iter_mut()
+            .find(|t| t.hash == hash && !t.revoked && t.expires_at > now);
+
+        done(Ok(token.map(|token| {
+            token.revoked = true;
+            token.user_id
+        })))
+    }
+
+    fn revoke_reused_refresh_token<'a>(&'a self, hash: &'a str) -> BoxFuture<'a, ()> {
+        let mut store = self.store();
+        let owner = store
+            .tokens
+            .iter()
+            .find(|t| t.hash == hash && t.revoked)
+            .map(|t| t.user_id);
+
+        if let Some(owner) = owner {
+            for token in store.tokens.iter_mut().filter(|t| t.user_id == owner) {
+                token.revoked = true;
+            }
+        }
+        done(Ok(()))
+    }
+
+    fn revoke_refresh_token<'a>(&'a self, hash: &'a str, user_id: Uuid) -> BoxFuture<'a, bool> {
+        let mut store = self.store();
+        let token = store
+            .tokens
+            .iter_mut()
+            .find(|t| t.hash == hash && t.user_id == user_id && !t.revoked);
+
+        done(Ok(token.map(|token| token.revoked = true).is_some()))
+    }
+
+    fn revoke_all_refresh_tokens(&self, user_id: Uuid) -> BoxFuture<'_, ()> {
+        let mut store = self.store();
+        for token in store.tokens.iter_mut().filter(|t| t.user_id == user_id) {
+            token.revoked = true;
+        }
+        done(Ok(()))
+    }
+
+    fn add_follow(&self, follower_id: Uuid, followed_id: Uuid) -> BoxFuture<'_, bool> {
+        let mut store = self.store();
+        if !store.user_exists(followed_id) {
+            return done(Ok(false));
+        }
+        if !store.user_exists(follower_id) {
+            return done(Err(AppError::missing_reference()));
+        }
+        if store
+            .follows
+            .iter()
+            .any(|f| f.follower_id == follower_id && f.followed_id == followed_id)
+        {
+            return done(Ok(false));
+        }
+
+        store.follows.push(Follow {
+            follower_id,
+            followed_id,
+            followed_at: Utc::now(),
+        });
+        store.notify(
+            followed_id,
+            follower_id,
+            schema::NotificationKind::Follow,
+            None,
+            None,
+        );
+        done(Ok(true))
+    }
+
+    fn remove_follow(&self, follower_id: Uuid, followed_id: Uuid) -> BoxFuture<'_, bool> {
+        let mut store = self.store();
+        let before = store.follows.len();
+        store
+            .follows
+            .retain(|f| f.follower_id != follower_id || f.followed_id != followed_id);
+        store.notifications.retain(|n| {
+            n.kind != schema::NotificationKind::Follow
+                || n.user_id != followed_id
+                || n.actor_id != follower_id
+        });
+        done(Ok(store.follows.len() < before))
+    }
+
+    fn followers<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Follow>> {
+        let store = self.store();
+        let followers = store.follow_list(|f| (f.followed_id == id).then_some(f.follower_id));
+        done(Ok(paginate(
+            followers,
+            page,
+            |f| (f.followed_at, f.user_id),
+            true,
+        )))
+    }
+
+    fn following<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Follow>> {
+        let store = self.store();
+        let following = store.follow_list(|f| (f.follower_id == id).then_some(f.followed_id));
+        done(Ok(paginate(
+            following,
+            page,
+            |f| (f.followed_at, f.user_id),
+            true,
+        )))
+    }
+
+    fn notifications<'a>(
+        &'a self,
+        user_id: Uuid,
+        unread: bool,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Notification>> {
+        let store = self.store();
+        let mut notifications: Vec<&Notification> = store
+            .notifications
+            .iter()
+            .filter(|n| n.user_id == user_id && (!unread || n.read_at.is_none()))
+            .collect();
+        notifications.sort_by_key(|n| std::cmp::Reverse((n.created_at, n.id)));
+
+        // Agrupando pelo tipo e pela mensagem, da notificação mais recente para a mais antiga.
+        let mut groups: Vec<Vec<&Notification>> = Vec::new();
+        for notification in notifications {
+            match groups.iter_mut().find(|g| {
+                g[0].kind == notification.kind && g[0].message_id == notification.message_id
+            }) {
+                Some(group) => group.push(notification),
+                None => groups.push(vec![notification]),
+            }
+        }
+
+        let groups = groups
+            .into_iter()
+            .map(|group| {
+                let mut actors: Vec<Uuid> = Vec::new();
+                for notification in &group {
+                    if !actors.contains(&notification.actor_id) {
+                        actors.push(notification.actor_id);
+                    }
+                }
+
+                response::Notification::new(
+                    group[0].id,
+                    group[0].kind,
+                    group[0].message_id,
+                    actors
+                        .iter()
+                        .take(response::NOTIFICATION_ACTORS)
+                        .filter_map(|id| store.users.iter().find(|u| u.id == *id))
+                        .map(|u| response::Actor {
+                            user_id: u.id,
+                            name: u.name.clone(),
+                        })
+                        .collect(),
+                    actors.len() as i64,
+                    group.iter().all(|n| n.read_at.is_some()),
+                    group[0].created_at,
+                )
+            })
+            .collect();
+        done(Ok(paginate(groups, page, |n| (n.created_at, n.id), true)))
+    }
+
+    fn read_notification(&self, user_id: Uuid, id: Uuid) -> BoxFuture<'_, Option<u64>> {
+        let mut store = self.store();
+        let Some(target) = store
+            .notifications
+            .iter()
+            .find(|n| n.id == id && n.user_id == user_id)
+            .map(|n| (n.kind, n.message_id, n.created_at, n.id))
+        else {
+            return done(Ok(None));
+        };
+
+        let now = Utc::now();
+        let mut marked = 0;
+        for notification in store.notifications.iter_mut().filter(|n| {
+            n.user_id == user_id
+                && n.kind == target.0
+                && n.message_id == target.1
+                && (n.created_at, n.id) <= (target.2, target.3)
+                && n.read_at.is_none()
+        }) {
+            notification.read_at = Some(now);
+            marked += 1;
+        }
+        done(Ok(Some(marked)))
+    }
+
+    fn read_all_notifications(&self, user_id: Uuid) -> BoxFuture<'_, u64> {
+        let mut store = self.store();
+        let now = Utc::now();
+        let mut marked = 0;
+        for notification in store
+            .notifications
+            .iter_mut()
+            .filter(|n| n.user_id == user_id && n.read_at.is_none())
+        {
+            notification.read_at = Some(now);
+            marked += 1;
+        }
+        done(Ok(marked))
+    }
+
+    fn notification_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> BoxFuture<'_, response::NotificationPreferences> {
+        done(Ok(self.store().preferences(user_id)))
+    }
+
+    fn update_notification_preferences<'a>(
+        &'a self,
+        user_id: Uuid,
+        changes: &'a NotificationChanges,
+    ) -> BoxFuture<'a, response::NotificationPreferences> {
+        let mut store = self.store();
+        if !store.user_exists(user_id) {
+            return done(Err(AppError::missing_reference()));
+        }
+
+        let current = store.preferences(user_id);
+        let preferences = response::NotificationPreferences {
+            likes: changes.likes.unwrap_or(current.likes),
+            replies: changes.replies.unwrap_or(current.replies),
+            mentions: changes.mentions.unwrap_or(current.mentions),
+            follows: changes.follows.unwrap_or(current.follows),
+        };
+        store.preferences.retain(|(id, _)| *id != user_id);
+        store.preferences.push((user_id, preferences.clone()));
+        done(Ok(preferences))
+    }
+}
+
+impl MessageRepository for MemoryRepository {
+    fn all_messages<'a>(&'a self, page: &'a Page<TimeKey>) -> BoxFuture<'a, Vec<schema::Message>> {
+        let store = self.store();
+        done(Ok(paginate(
+            store.visible().cloned().collect(),
+            page,
+            |m| (m.sended_at, m.id),
+            true,
+        )))
+    }
+
+    fn find_message(&self, id: Uuid) -> BoxFuture<'_, Option<schema::Message>> {
+        let store = self.store();
+        let message = store.visible().find(|m| m.id == id).cloned();
+        done(Ok(message))
+    }
+
+    fn find_messages_by_content<'a>(
+        &'a self,
+        content: &'a str,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>> {
+        let store = self.store();
+        let messages = store
+            .visible()
+            .filter(|m| m.content.contains(content))
+            .cloned()
+            .collect();
+        done(Ok(paginate(messages, page, |m| (m.sended_at, m.id), true)))
+    }
+
+    fn search_messages<'a>(
+        &'a self,
+        tsquery: &'a str,
+        _language: search::Language,
+        page: &'a Page<RankKey>,
+    ) -> BoxFuture<'a, Vec<response::SearchResult>> {
+        let store = self.store();
+        let query = Query::parse(tsquery);
+        let results = store
+            .visible()
+            .filter_map(|message| {
+                query
+                    .rank(&message.content)
+                    .map(|rank| response::SearchResult {
+                        message: message.clone(),
+                        rank,
+                        snippet: query.highlight(&message.content),
+                    })
+            })
+            .collect();
+        done(Ok(paginate(
+            results,
+            page,
+            |r| (r.rank, r.message.sended_at, r.message.id),
+            true,
+        )))
+    }
+
+    fn messages_from_user<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>> {
+        let store = self.store();
+        let messages = store
+            .visible()
+            .filter(|m| m.user_id == user_id)
+            .cloned()
+            .collect();
+        done(Ok(paginate(messages, page, |m| (m.sended_at, m.id), true)))
+    }
+
+    fn home_timeline<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>> {
+        let store = self.store();
+        let followed: Vec<Uuid> = store
+            .follows
+            .iter()
+            .filter(|f| f.follower_id == user_id)
+            .map(|f| f.followed_id)
+            .collect();
+        let messages = store
+            .visible()
+            .filter(|m| followed.contains(&m.user_id))
+            .cloned()
+            .collect();
+        done(Ok(paginate(messages, page, |m| (m.sended_at, m.id), true)))
+    }
+
+    fn tag_timeline<'a>(
+        &'a self,
+        tag: &'a str,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>> {
+        let store = self.store();
+        let messages =
+            store.with_entity(|e| e.kind == schema::EntityKind::Hashtag && e.text == tag);
+        done(Ok(paginate(messages, page, |m| (m.sended_at, m.id), true)))
+    }
+
+    fn mention_timeline<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>> {
+        let store = self.store();
+        let messages = store.with_entity(|e| e.user_id == Some(user_id));
+        done(Ok(paginate(messages, page, |m| (m.sended_at, m.id), true)))
+    }
+
+    fn thread<'a>(
+        &'a self,
+        id: Uuid,
+        depth: i32,
+        replies: i64,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Option<(schema::Message, Vec<schema::Message>)>> {
+        let store = self.store();
+        let Some(root) = store.messages.iter().find(|m| m.id == id).cloned() else {
+            return done(Ok(None));
+        };
+
+        let children = |parent_id: Uuid| -> Vec<schema::Message> {
+            store
+                .messages
+                .iter()
+                .filter(|m| m.parent_id == Some(parent_id))
+                .cloned()
+                .collect()
+        };
+
+        let mut level = paginate(children(id), page, |m| (m.sended_at, m.id), false);
+        let mut descendants = Vec::new();
+        for _ in 1..depth {
+            let mut next = Vec::new();
+            for message in &level {
+                let mut replies_of = children(message.id);
+                replies_of.sort_by_key(|m| (m.sended_at, m.id));
+                replies_of.truncate(replies as usize);
+                next.extend(replies_of);
+            }
+            descendants.append(&mut level);
+            level = next;
+        }
+        descendants.append(&mut level);
+
+        done(Ok(Some((root, descendants))))
+    }
+
+    fn add_message<'a>(
+        &'a self,
+        user_id: Uuid,
+        content: &'a str,
+        parent_id: Option<Uuid>,
+        entities: &'a [schema::Entity],
+    ) -> BoxFuture<'a, schema::Message> {
+        let mut store = self.store();
+        if !store.user_exists(user_id) {
+            return done(Err(AppError::missing_reference()));
+        }
+        let parent = parent_id.map(|id| store.messages.iter().position(|m| m.id == id));
+        if let Some(None) = parent {
+            return done(Err(AppError::missing_reference()));
+        }
+
+        let message = schema::Message {
+            id: Uuid::new_v4(),
+            likes: 0,
+            user_id,
+            content: content.to_string(),
+            sended_at: Utc::now(),
+            edited_at: None,
+            parent_id,
+            reply_count: 0,
+            deleted_at: None,
+            entities: Json(store.resolve_mentions(entities)),
+        };
+        if let Some(Some(position)) = parent {
+            store.messages[position].reply_count += 1;
+
+            let owner = store.messages[position].user_id;
+            store.notify(
+                owner,
+                user_id,
+                schema::NotificationKind::Reply,
+                parent_id,
+                Some(message.id),
+            );
+        }
+        store.messages.push(message.clone());
+        store.notify_mentions(&message, &[]);
+        done(Ok(message))
+    }
+
+    fn edit_message<'a>(
+        &'a self,
+        id: Uuid,
+        user_id: Uuid,
+        content: &'a str,
+        entities: &'a [schema::Entity],
+    ) -> BoxFuture<'a, schema::Message> {
+        let mut store = self.store();
+        let position = store
+            .messages
+            .iter()
+            .position(|m| m.id == id && m.deleted_at.is_none());
+        if let Err(err) = check_ownership(position.map(|i| store.messages[i].user_id), user_id) {
+            return done(Err(err));
+        }
+
+        let now = Utc::now();
+        let entities = store.resolve_mentions(entities);
+        let message =
+            &mut store.messages[position.expect("check_ownership checks the message exists")];
+        let revision = schema::MessageRevision {
+            id: Uuid::new_v4(),
+            message_id: id,
+            content: std::mem::replace(&mut message.content, content.to_string()),
+            written_at: message.edited_at.unwrap_or(message.sended_at),
+            replaced_at: now,
+        };
+        message.edited_at = Some(now);
+        let previous = std::mem::replace(&mut message.entities, Json(entities));
+        let message = message.clone();
+
+        store.revisions.push(revision);
+        store.notify_mentions(&message, &previous);
+        done(Ok(message))
+    }
+
+    fn delete_message(&self, id: Uuid, user_id: Uuid) -> BoxFuture<'_, ()> {
+        let mut store = self.store();
+        let position = store
+            .messages
+            .iter()
+            .position(|m| m.id == id && m.deleted_at.is_none());
+        if let Err(err) = check_ownership(position.map(|i| store.messages[i].user_id), user_id) {
+            return done(Err(err));
+        }
+
+        // A mensagem com respostas vira um tombstone, mantendo a conversa.
+        let position = position.expect("check_ownership checks the message exists");
+        match store.messages[position].reply_count > 0 {
+            true => {
+                store.remove_likes(|l| l.message_id == id);
+                store.revisions.retain(|r| r.message_id != id);
+                store
+                    .notifications
+                    .retain(|n| n.message_id != Some(id) && n.source_id != Some(id));
+
+                let message = &mut store.messages[position];
+                message.content.clear();
+                message.entities.clear();
+                message.edited_at = None;
+                message.deleted_at = Some(Utc::now());
+            }
+            false => store.remove_messages(&|m| m.id == id),
+        }
+        done(Ok(()))
+    }
+
+    fn message_history<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::MessageRevision>> {
+        let store = self.store();
+        let revisions = store
+            .revisions
+            .iter()
+            .filter(|r| r.message_id == id)
+            .cloned()
+            .collect();
+        done(Ok(paginate(
+            revisions,
+            page,
+            |r| (r.replaced_at, r.id),
+            true,
+        )))
+    }
+
+    fn add_like(&self, message_id: Uuid, user_id: Uuid) -> BoxFuture<'_, bool> {
+        let mut store = self.store();
+        if !store.visible().any(|m| m.id == message_id) {
+            return done(Ok(false));
+        }
+        if !store.user_exists(user_id) {
+            return done(Err(AppError::missing_reference()));
+        }
+        if store
+            .likes
+            .iter()
+            .any(|l| l.message_id == message_id && l.user_id == user_id)
+        {
+            return done(Ok(false));
+        }
+
+        store.likes.push(Like {
+            message_id,
+            user_id,
+            liked_at: Utc::now(),
+        });
+        let message = store
+            .messages
+            .iter_mut()
+            .find(|m| m.id == message_id)
+            .expect("the liked message is visible");
+        message.likes += 1;
+
+        let owner = message.user_id;
+        store.notify(
+            owner,
+            user_id,
+            schema::NotificationKind::Like,
+            Some(message_id),
+            None,
+        );
+        done(Ok(true))
+    }
+
+    fn remove_like(&self, message_id: Uuid, user_id: Uuid) -> BoxFuture<'_, bool> {
+        let mut store = self.store();
+        done(Ok(store.remove_likes(|l| {
+            l.message_id == message_id && l.user_id == user_id
+        })))
+    }
+
+    fn message_likes<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Like>> {
+        let store = self.store();
+        let likes = store
+            .likes
+            .iter()
+            .filter(|l| l.message_id == id)
+            .filter_map(|l| {
+                let user = store.users.iter().find(|u| u.id == l.user_id)?;
+                Some(response::Like {
+                    user_id: l.user_id,
+                    name: user.name.clone(),
+                    liked_at: l.liked_at,
+                })
+            })
+            .collect();
+        done(Ok(paginate(likes, page, |l| (l.liked_at, l.user_id), true)))
+    }
+}
+
+impl ConversationRepository for MemoryRepository {
+    fn add_conversation<'a>(
+        &'a self,
+        user_id: Uuid,
+        members: &'a [Uuid],
+    ) -> BoxFuture<'a, (response::Conversation, bool)> {
+        let mut store = self.store();
+        let key = match members {
+            [other_id] => Some(direct_key(user_id, *other_id)),
+            _ => None,
+        };
+
+        if let Some(existing) = store
+            .conversations
+            .iter()
+            .find(|c| key.is_some() && c.direct_key == key)
+        {
+            let id = existing.id;
+            return done(
+                store
+                    .conversation(id, user_id)
+                    .map(|c| (c, false))
+                    .ok_or_else(AppError::missing_reference),
+            );
+        }
+        if !members
+            .iter()
+            .chain([&user_id])
+            .all(|id| store.user_exists(*id))
+        {
+            return done(Err(AppError::missing_reference()));
+        }
+
+        let (id, now) = (Uuid::new_v4(), Utc::now());
+        store.conversations.push(Conversation {
+            id,
+            direct_key: key,
+            created_at: now,
+            last_message_at: now,
+        });
+        for member in members.iter().chain([&user_id]) {
+            store.members.push(Member {
+                conversation_id: id,
+                user_id: *member,
+                last_read_at: None,
+            });
+        }
+
+        done(
+            store
+                .conversation(id, user_id)
+                .map(|c| (c, true))
+                .ok_or_else(AppError::missing_reference),
+        )
+    }
+
+    fn is_member(&self, id: Uuid, user_id: Uuid) -> BoxFuture<'_, Option<bool>> {
+        let store = self.store();
+        if !store.conversations.iter().any(|c| c.id == id) {
+            return done(Ok(None));
+        }
+        done(Ok(Some(
+            store
+                .members
+                .iter()
+                .any(|m| m.conversation_id == id && m.user_id == user_id),
+        )))
+    }
+
+    fn find_conversation(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> BoxFuture<'_, Option<response::Conversation>> {
+        done(Ok(self.store().conversation(id, user_id)))
+    }
+
+    fn conversations<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Conversation>> {
+        let store = self.store();
+        let conversations = store
+            .members
+            .iter()
+            .filter(|m| m.user_id == user_id)
+            .filter_map(|m| store.conversation(m.conversation_id, user_id))
+            .collect();
+        done(Ok(paginate(
+            conversations,
+            page,
+            |c| (c.last_message_at, c.id),
+            true,
+        )))
+    }
+
+    fn direct_messages<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::DirectMessage>> {
+        let store = self.store();
+        let messages = store
+            .direct_messages
+            .iter()
+            .filter(|d| d.conversation_id == id)
+            .map(|d| store.direct_message(d))
+            .collect();
+        done(Ok(paginate(messages, page, |d| (d.sended_at, d.id), true)))
+    }
+
+    fn add_direct_message<'a>(
+        &'a self,
+        id: Uuid,
+        user_id: Uuid,
+        content: &'a str,
+    ) -> BoxFuture<'a, Option<response::DirectMessage>> {
+        let mut store = self.store();
+        let Some(member) = store
+            .members
+            .iter_mut()
+            .find(|m| m.conversation_id == id && m.user_id == user_id)
+        else {
+            return done(Ok(None));
+        };
+
+        // O autor leu a conversa até a própria mensagem.
+        let now = Utc::now();
+        member.last_read_at = member.last_read_at.max(Some(now));
+        if let Some(conversation) = store.conversations.iter_mut().find(|c| c.id == id) {
+            conversation.last_message_at = conversation.last_message_at.max(now);
+        }
+
+        let message = DirectMessage {
+            id: Uuid::new_v4(),
+            conversation_id: id,
+            user_id,
+            content: content.to_string(),
+            sended_at: now,
+        };
+        let response = response::DirectMessage {
+            read_by: Vec::new(),
+            ..store.direct_message(&message)
+        };
+        store.direct_messages.push(message);
+        done(Ok(Some(response)))
+    }
+
+    fn read_conversation(&self, id: Uuid, user_id: Uuid) -> BoxFuture<'_, ()> {
+        let mut store = self.store();
+        let newest = store
+            .direct_messages
+            .iter()
+            .filter(|d| d.conversation_id == id)
+            .map(|d| d.sended_at)
+            .max();
+
+        if let Some(member) = store
+            .members
+            .iter_mut()
+            .find(|m| m.conversation_id == id && m.user_id == user_id)
+        {
+            member.last_read_at = member.last_read_at.max(newest);
+        }
+        done(Ok(()))
+    }
+
+    fn unread(&self, user_id: Uuid) -> BoxFuture<'_, response::Unread> {
+        let store = self.store();
+        let (mut messages, mut conversations) = (0, 0);
+
+        for me in store.members.iter().filter(|m| m.user_id == user_id) {
+            let count = store.unread_messages(me).count() as i64;
+            messages += count;
+            conversations += (count > 0) as i64;
+        }
+        done(Ok(response::Unread {
+            messages,
+            conversations,
+        }))
+    }
+}
+
+// Termo da consulta gerada por search::to_tsquery.
+struct Term {
+    words: Vec<String>, // Palavras em sequência.
+    prefix: bool,       // A última palavra é um prefixo.
+    negated: bool,
+}
+
+impl Term {
+    // Quantidade de ocorrências da sequência de palavras no texto.
+    fn occurrences(&self, words: &[String]) -> usize {
+        (0..words.len())
+            .filter(|&start| {
+                self.words.iter().enumerate().all(|(i, word)| {
+                    words.get(start + i).is_some_and(|found| {
+                        match self.prefix && i == self.words.len() - 1 {
+                            true => found.starts_with(word.as_str()),
+                            false => found == word,
+                        }
+                    })
+                })
+            })
+            .count()
+    }
+
+    // Indica se a palavra faz parte do termo, para ser destacada.
+    fn highlights(&self, word: &str) -> bool {
+        !self.negated
+            && self.words.iter().enumerate().any(|(i, term)| {
+                match self.prefix && i == self.words.len() - 1 {
+                    true => word.starts_with(term.as_str()),
+                    false => word == term,
+                }
+            })
+    }
+}
+
+// Consulta de busca textual avaliada em memória. Sem os dicionários do
+// PostgreSQL as palavras são comparadas sem stemming, em qualquer idioma.
+struct Query {
+    groups: Vec<Vec<Term>>, // Alternativas separadas por "|".
+}
+
+impl Query {
+    // Método para ler a consulta no formato de search::to_tsquery.
+    fn parse(tsquery: &str) -> Self {
+        let unwrap = |text: &str| {
+            text.trim_start_matches('(')
+                .trim_end_matches(')')
+                .to_string()
+        };
+
+        let groups = tsquery
+            .split(" | ")
+            .map(|group| {
+                unwrap(group)
+                    .split(" & ")
+                    .map(|term| {
+                        let negated = term.starts_with('!');
+                        let term = unwrap(term.trim_start_matches('!'));
+                        let prefix = term.ends_with(":*");
+                        Term {
+                            words: term
+                                .trim_end_matches(":*")
+                                .split(" <-> ")
+                                .map(str::to_string)
+                                .collect(),
+                            prefix,
+                            negated,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self { groups }
+    }
+
+    // Método para calcular a relevância do texto, None quando não corresponde.
+    fn rank(&self, content: &str) -> Option<f32> {
+        let words = search::lexemes(content);
+
+        self.groups
+            .iter()
+            .filter_map(|group| {
+                let mut found = 0;
+                for term in group {
+                    let occurrences = term.occurrences(&words);
+                    match term.negated {
+                        true if occurrences > 0 => return None,
+                        true => {}
+                        false if occurrences == 0 => return None,
+                        false => found += occurrences,
+                    }
+                }
+                Some(found as f32 / 10.0)
+            })
+            .reduce(f32::max)
+    }
+
+    // Método para destacar com <mark> as palavras encontradas no texto.
+    fn highlight(&self, content: &str) -> String {
+        let mut snippet = String::with_capacity(content.len());
+        let mut word = String::new();
+
+        let flush = |word: &mut String, snippet: &mut String| {
+            let lower = word.to_lowercase();
+            let marked = self.groups.iter().flatten().any(|t| t.highlights(&lower));
+            match marked && !word.is_empty() {
+                true => snippet.push_str(&format!("<mark>{}</mark>", word)),
+                false => snippet.push_str(word),
+            }
+            word.clear();
+        };
+
+        for c in content.chars() {
+            match c.is_alphanumeric() {
+                true => word.push(c),
+                false => {
+                    flush(&mut word, &mut snippet);
+                    snippet.push(c);
+                }
+            }
+        }
+        flush(&mut word, &mut snippet);
+        snippet
+    }
+}
@@ -0,0 +1,352 @@
+#[cfg(test)]
+mod memory;
+mod postgres;
+
+#[cfg(test)]
+pub use memory::*; // Exportando o repositório em memória, usado pelos testes.
+pub use postgres::*; // Exportando o repositório do PostgreSQL.
+
+use chrono::{DateTime, Utc};
+use std::{future::Future, pin::Pin};
+use uuid::Uuid;
+
+use crate::{error::AppError, pagination::Page, response, schema, search};
+
+// Resultado assíncrono das operações dos repositórios. As futures são
+// alocadas para que os repositórios possam ser usados como trait objects.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, AppError>> + Send + 'a>>;
+
+// Chave de paginação dos usuários, ordenados pelo nome.
+pub type UserKey = (String, Uuid);
+// Chave de paginação das mensagens, revisões, curtidas, notificações e conversas
+// privadas, das mais novas para as mais antigas, e das respostas de uma conversa,
+// das mais antigas para as mais novas.
+pub type TimeKey = (DateTime<Utc>, Uuid);
+// Chave de paginação da busca textual, ordenada pela relevância.
+pub type RankKey = (f32, DateTime<Utc>, Uuid);
+
+// Alterações do perfil de um usuário. Os campos None são mantidos e
+// um texto vazio remove o campo opcional.
+#[derive(Default)]
+pub struct ProfileChanges<'a> {
+    pub name: Option<&'a str>,
+    pub display_name: Option<&'a str>,
+    pub bio: Option<&'a str>,
+    pub avatar_url: Option<&'a str>,
+    pub password: Option<&'a str>, // Hash da nova senha.
+}
+
+// Alterações das preferências de notificação. Os tipos None são mantidos.
+#[derive(Default)]
+pub struct NotificationChanges {
+    pub likes: Option<bool>,
+    pub replies: Option<bool>,
+    pub mentions: Option<bool>,
+    pub follows: Option<bool>,
+}
+
+// Acesso aos usuários e às sessões (tokens de atualização).
+pub trait UserRepository: Send + Sync {
+    // Página de usuários ordenados pelo nome.
+    fn all_users<'a>(&'a self, page: &'a Page<UserKey>) -> BoxFuture<'a, Vec<response::User>>;
+
+    // Usuário pelo id.
+    fn find_user(&self, id: Uuid) -> BoxFuture<'_, Option<response::User>>;
+
+    // Ids informados que não pertencem a nenhum usuário.
+    fn missing_users<'a>(&'a self, ids: &'a [Uuid]) -> BoxFuture<'a, Vec<Uuid>>;
+
+    // Página de usuários cujo nome contém o texto.
+    fn find_users_by_name<'a>(
+        &'a self,
+        name: &'a str,
+        page: &'a Page<UserKey>,
+    ) -> BoxFuture<'a, Vec<response::User>>;
+
+    // Perfil do usuário com as quantidades de mensagens e curtidas.
+    fn find_profile(&self, id: Uuid) -> BoxFuture<'_, Option<response::Profile>>;
+
+    // Usuário com o hash da senha, usado na autenticação.
+    fn find_credentials<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Option<schema::User>>;
+
+    // Usuário com o hash da senha pelo id, usado na troca de senha.
+    fn find_credentials_by_id(&self, id: Uuid) -> BoxFuture<'_, Option<schema::User>>;
+
+    // Adiciona um usuário. O erro é 409 quando o nome já existe.
+    fn add_user<'a>(&'a self, name: &'a str, hash: &'a str) -> BoxFuture<'a, response::User>;
+
+    // Altera o perfil do usuário, retornando false quando ele não existe.
+    // O erro é 409 quando o novo nome já existe.
+    fn update_profile<'a>(
+        &'a self,
+        id: Uuid,
+        changes: &'a ProfileChanges<'a>,
+    ) -> BoxFuture<'a, bool>;
+
+    // Substitui o hash da senha do usuário.
+    fn update_password<'a>(&'a self, id: Uuid, hash: &'a str) -> BoxFuture<'a, ()>;
+
+    // Remove o usuário junto às suas mensagens, curtidas e sessões.
+    // Retorna false quando o usuário não existe.
+    fn delete_user(&self, id: Uuid) -> BoxFuture<'_, bool>;
+
+    // Armazena o hash de um novo token de atualização.
+    fn add_refresh_token<'a>(
+        &'a self,
+        user_id: Uuid,
+        hash: &'a str,
+        expires_at: DateTime<Utc>,
+    ) -> BoxFuture<'a, ()>;
+
+    // Revoga o token de atualização ativo, retornando o usuário dele.
+    fn consume_refresh_token<'a>(&'a self, hash: &'a str) -> BoxFuture<'a, Option<Uuid>>;
+
+    // Revoga todas as sessões do usuário dono do token já revogado.
+    fn revoke_reused_refresh_token<'a>(&'a self, hash: &'a str) -> BoxFuture<'a, ()>;
+
+    // Revoga o token ativo do usuário, retornando false quando não existe.
+    fn revoke_refresh_token<'a>(&'a self, hash: &'a str, user_id: Uuid) -> BoxFuture<'a, bool>;
+
+    // Revoga todas as sessões do usuário.
+    fn revoke_all_refresh_tokens(&self, user_id: Uuid) -> BoxFuture<'_, ()>;
+
+    // Segue o usuário, retornando false quando ele não existe ou já
+    // é seguido. O erro é 422 quando o seguidor não existe.
+    fn add_follow(&self, follower_id: Uuid, followed_id: Uuid) -> BoxFuture<'_, bool>;
+
+    // Deixa de seguir o usuário, retornando false quando não o seguia.
+    fn remove_follow(&self, follower_id: Uuid, followed_id: Uuid) -> BoxFuture<'_, bool>;
+
+    // Página dos seguidores do usuário, dos mais novos para os mais antigos.
+    fn followers<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Follow>>;
+
+    // Página dos usuários seguidos, dos mais novos para os mais antigos.
+    fn following<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Follow>>;
+
+    // Página dos grupos de notificações do usuário, dos mais recentes para os
+    // mais antigos, pela notificação mais recente de cada grupo. Com `unread`
+    // os grupos consideram apenas as notificações não lidas.
+    fn notifications<'a>(
+        &'a self,
+        user_id: Uuid,
+        unread: bool,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Notification>>;
+
+    // Marca como lidas a notificação e as anteriores do mesmo grupo, retornando
+    // a quantidade marcada, ou None quando a notificação não é do usuário.
+    fn read_notification(&self, user_id: Uuid, id: Uuid) -> BoxFuture<'_, Option<u64>>;
+
+    // Marca como lidas todas as notificações do usuário, retornando a quantidade marcada.
+    fn read_all_notifications(&self, user_id: Uuid) -> BoxFuture<'_, u64>;
+
+    // Tipos de notificação registrados para o usuário.
+    fn notification_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> BoxFuture<'_, response::NotificationPreferences>;
+
+    // Altera os tipos de notificação registrados para o usuário.
+    // O erro é 422 quando o usuário não existe.
+    fn update_notification_preferences<'a>(
+        &'a self,
+        user_id: Uuid,
+        changes: &'a NotificationChanges,
+    ) -> BoxFuture<'a, response::NotificationPreferences>;
+}
+
+// Acesso às mensagens, ao histórico de edições e às curtidas.
+pub trait MessageRepository: Send + Sync {
+    // Página de mensagens, das mais novas para as mais antigas.
+    fn all_messages<'a>(&'a self, page: &'a Page<TimeKey>) -> BoxFuture<'a, Vec<schema::Message>>;
+
+    // Mensagem pelo id.
+    fn find_message(&self, id: Uuid) -> BoxFuture<'_, Option<schema::Message>>;
+
+    // Página de mensagens cujo conteúdo contém o texto.
+    fn find_messages_by_content<'a>(
+        &'a self,
+        content: &'a str,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>>;
+
+    // Página da busca textual pela consulta gerada por search::to_tsquery.
+    fn search_messages<'a>(
+        &'a self,
+        tsquery: &'a str,
+        language: search::Language,
+        page: &'a Page<RankKey>,
+    ) -> BoxFuture<'a, Vec<response::SearchResult>>;
+
+    // Página de mensagens de um usuário.
+    fn messages_from_user<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>>;
+
+    // Página das mensagens dos usuários seguidos, das mais novas para as mais antigas.
+    fn home_timeline<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>>;
+
+    // Página das mensagens com a hashtag, já normalizada, das mais novas para as mais antigas.
+    fn tag_timeline<'a>(
+        &'a self,
+        tag: &'a str,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>>;
+
+    // Página das mensagens que mencionam o usuário, das mais novas para as mais antigas.
+    fn mention_timeline<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>>;
+
+    // Mensagem pelo id, inclusive removida, com as respostas abaixo dela: uma
+    // página das respostas diretas, das mais antigas para as mais novas, e até
+    // `replies` respostas de cada uma delas, descendo até a profundidade `depth`.
+    fn thread<'a>(
+        &'a self,
+        id: Uuid,
+        depth: i32,
+        replies: i64,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Option<(schema::Message, Vec<schema::Message>)>>;
+
+    // Adiciona uma mensagem, opcionalmente em resposta a outra, com as entidades
+    // extraídas por entity::extract. As menções são resolvidas pelo nome do
+    // usuário e as que não correspondem a nenhum usuário são descartadas.
+    // O erro é 422 quando o usuário ou a mensagem respondida não existe.
+    fn add_message<'a>(
+        &'a self,
+        user_id: Uuid,
+        content: &'a str,
+        parent_id: Option<Uuid>,
+        entities: &'a [schema::Entity],
+    ) -> BoxFuture<'a, schema::Message>;
+
+    // Edita a mensagem do usuário guardando o conteúdo anterior no histórico
+    // e substituindo as entidades, resolvidas como em add_message.
+    // O erro é 404 quando a mensagem não existe e 403 quando é de outro usuário.
+    fn edit_message<'a>(
+        &'a self,
+        id: Uuid,
+        user_id: Uuid,
+        content: &'a str,
+        entities: &'a [schema::Entity],
+    ) -> BoxFuture<'a, schema::Message>;
+
+    // Remove a mensagem do usuário junto ao histórico e às curtidas. A mensagem
+    // com respostas é mantida sem conteúdo (tombstone) até perder as respostas.
+    // O erro é 404 quando a mensagem não existe e 403 quando é de outro usuário.
+    fn delete_message(&self, id: Uuid, user_id: Uuid) -> BoxFuture<'_, ()>;
+
+    // Página do histórico de edições, das revisões mais novas para as mais antigas.
+    fn message_history<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::MessageRevision>>;
+
+    // Curte a mensagem, retornando false quando a mensagem não existe
+    // ou já foi curtida. O erro é 422 quando o usuário não existe.
+    fn add_like(&self, message_id: Uuid, user_id: Uuid) -> BoxFuture<'_, bool>;
+
+    // Remove a curtida, retornando false quando ela não existe.
+    fn remove_like(&self, message_id: Uuid, user_id: Uuid) -> BoxFuture<'_, bool>;
+
+    // Página das curtidas de uma mensagem, das mais novas para as mais antigas.
+    fn message_likes<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Like>>;
+}
+
+// Acesso às conversas privadas e às mensagens diretas, sempre pelo
+// ponto de vista de um participante.
+pub trait ConversationRepository: Send + Sync {
+    // Cria a conversa do usuário com os participantes, que não incluem o próprio
+    // usuário. Com um único participante a conversa é direta e, se já existir,
+    // é retornada com false. O erro é 422 quando algum usuário não existe.
+    fn add_conversation<'a>(
+        &'a self,
+        user_id: Uuid,
+        members: &'a [Uuid],
+    ) -> BoxFuture<'a, (response::Conversation, bool)>;
+
+    // Indica se o usuário participa da conversa, ou None quando ela não existe.
+    fn is_member(&self, id: Uuid, user_id: Uuid) -> BoxFuture<'_, Option<bool>>;
+
+    // Conversa pelo id, com as mensagens não lidas pelo participante.
+    fn find_conversation(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> BoxFuture<'_, Option<response::Conversation>>;
+
+    // Página das conversas do usuário, da mensagem mais recente para a mais antiga.
+    fn conversations<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Conversation>>;
+
+    // Página das mensagens da conversa, das mais novas para as mais antigas.
+    fn direct_messages<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::DirectMessage>>;
+
+    // Envia a mensagem do participante, que passa a ter lido a conversa até ela.
+    // Retorna None quando o usuário não participa da conversa.
+    fn add_direct_message<'a>(
+        &'a self,
+        id: Uuid,
+        user_id: Uuid,
+        content: &'a str,
+    ) -> BoxFuture<'a, Option<response::DirectMessage>>;
+
+    // Marca a conversa como lida pelo participante até a mensagem mais recente.
+    fn read_conversation(&self, id: Uuid, user_id: Uuid) -> BoxFuture<'_, ()>;
+
+    // Mensagens não lidas pelo usuário em todas as conversas.
+    fn unread(&self, user_id: Uuid) -> BoxFuture<'_, response::Unread>;
+}
+
+// Método para montar a chave da conversa direta, com os usuários em
+// ordem, para que o mesmo par sempre tenha a mesma chave.
+fn direct_key(user_id: Uuid, other_id: Uuid) -> String {
+    let (low, high) = match user_id < other_id {
+        true => (user_id, other_id),
+        false => (other_id, user_id),
+    };
+    format!("{}:{}", low, high)
+}
+
+// Método para verificar se a mensagem existe e pertence ao usuário,
+// retornando o erro adequado quando não pertence.
+fn check_ownership(owner: Option<Uuid>, user_id: Uuid) -> Result<(), AppError> {
+    match owner {
+        Some(owner) if owner == user_id => Ok(()),
+        Some(_) => Err(AppError::Forbidden(
+            "Only the author can change this message!".to_string(),
+        )), // Retornando o erro que indica que a mensagem é de outro usuário.
+        None => Err(AppError::NotFound(
+            "This message doesn't exist!".to_string(),
+        )), // Retornando o erro que indica que a mensagem não existe.
+    }
+}
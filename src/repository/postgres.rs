@@ -0,0 +1,1481 @@
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as, types::Json, PgConnection, Pool, Postgres};
+use uuid::Uuid;
+
+use super::{
+    check_ownership, direct_key, BoxFuture, ConversationRepository, MessageRepository,
+    NotificationChanges, ProfileChanges, RankKey, TimeKey, UserKey, UserRepository,
+};
+use crate::{entity, error::AppError, pagination::Page, response, schema, search};
+
+// Repositório que executa as consultas no PostgreSQL.
+#[derive(Clone)]
+pub struct PostgresRepository {
+    db: Pool<Postgres>,
+}
+
+impl PostgresRepository {
+    pub fn new(db: Pool<Postgres>) -> Self {
+        Self { db }
+    }
+
+    // Método para extrair as entidades das mensagens gravadas antes da
+    // extração existir, em lotes, retornando a quantidade de mensagens.
+    pub async fn extract_entities(&self) -> Result<u64, AppError> {
+        let mut extracted = 0;
+
+        loop {
+            let mut tx = self.db.begin().await?;
+            let messages = query!(
+                "SELECT id, content, sended_at FROM messages WHERE entities IS NULL \
+                LIMIT 500 FOR UPDATE SKIP LOCKED"
+            )
+            .fetch_all(&mut tx)
+            .await?;
+
+            if messages.is_empty() {
+                return Ok(extracted);
+            }
+
+            // As menções são indexadas antes de gravar as entidades, sem gerar notificações.
+            for message in &messages {
+                let entities =
+                    resolve_mentions(&mut tx, &entity::extract(&message.content)).await?;
+                index_entities(&mut tx, message.id, message.sended_at, &entities).await?;
+                query!(
+                    "UPDATE messages SET entities = $2 WHERE id = $1",
+                    message.id,
+                    Json(&entities) as _
+                )
+                .execute(&mut tx)
+                .await?;
+            }
+
+            tx.commit().await?;
+            extracted += messages.len() as u64;
+        }
+    }
+}
+
+// Método para resolver as menções pelo nome dos usuários, descartando
+// as que não correspondem a nenhum usuário.
+async fn resolve_mentions(
+    conn: &mut PgConnection,
+    entities: &[schema::Entity],
+) -> Result<Vec<schema::Entity>, AppError> {
+    let names: Vec<String> = entities
+        .iter()
+        .filter(|e| e.kind == schema::EntityKind::Mention)
+        .map(|e| e.text.clone())
+        .collect();
+
+    let users = match names.is_empty() {
+        true => Vec::new(),
+        false => {
+            query!(
+                "SELECT id, name FROM rust_user WHERE name = ANY($1)",
+                &names[..]
+            )
+            .fetch_all(&mut *conn)
+            .await?
+        }
+    };
+
+    Ok(entities
+        .iter()
+        .filter_map(|e| match e.kind {
+            schema::EntityKind::Hashtag => Some(e.clone()),
+            schema::EntityKind::Mention => {
+                users
+                    .iter()
+                    .find(|u| u.name == e.text)
+                    .map(|u| schema::Entity {
+                        user_id: Some(u.id),
+                        ..e.clone()
+                    })
+            }
+        })
+        .collect())
+}
+
+// Método para atualizar as linhas da mensagem nas linhas do tempo das
+// hashtags e das menções, mantendo as que continuam no conteúdo para que
+// as menções já notificadas não sejam notificadas novamente.
+async fn index_entities(
+    conn: &mut PgConnection,
+    message_id: Uuid,
+    sended_at: DateTime<Utc>,
+    entities: &[schema::Entity],
+) -> Result<(), AppError> {
+    let tags: Vec<String> = entities
+        .iter()
+        .filter(|e| e.kind == schema::EntityKind::Hashtag)
+        .map(|e| e.text.clone())
+        .collect();
+    let users: Vec<Uuid> = entities.iter().filter_map(|e| e.user_id).collect();
+
+    query!(
+        "DELETE FROM message_tags WHERE message_id = $1 AND tag <> ALL($2)",
+        message_id,
+        &tags[..]
+    )
+    .execute(&mut *conn)
+    .await?;
+    query!(
+        "DELETE FROM message_mentions WHERE message_id = $1 AND user_id <> ALL($2)",
+        message_id,
+        &users[..]
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    query!(
+        "INSERT INTO message_tags (message_id, tag, sended_at) \
+        SELECT $1, tag, $2 FROM UNNEST($3::VARCHAR[]) tag ON CONFLICT DO NOTHING",
+        message_id,
+        sended_at,
+        &tags[..]
+    )
+    .execute(&mut *conn)
+    .await?;
+    query!(
+        "INSERT INTO message_mentions (message_id, user_id, sended_at) \
+        SELECT $1, user_id, $2 FROM UNNEST($3::UUID[]) user_id ON CONFLICT DO NOTHING",
+        message_id,
+        sended_at,
+        &users[..]
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+impl UserRepository for PostgresRepository {
+    fn all_users<'a>(&'a self, page: &'a Page<UserKey>) -> BoxFuture<'a, Vec<response::User>> {
+        Box::pin(async move {
+            let (after_name, after_id) = page.after.clone().unzip();
+
+            let users = query_as!(
+                response::User,
+                r#"SELECT id, name, created_at,
+                (SELECT COUNT(*) FROM messages
+                    WHERE user_id = rust_user.id AND deleted_at IS NULL) AS "message_count!"
+                FROM rust_user
+                WHERE $1::VARCHAR IS NULL OR (name, id) > ($1, $2)
+                ORDER BY name, id LIMIT $3"#,
+                after_name,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(users)
+        })
+    }
+
+    fn find_user(&self, id: Uuid) -> BoxFuture<'_, Option<response::User>> {
+        Box::pin(async move {
+            let user = query_as!(
+                response::User,
+                r#"SELECT id, name, created_at,
+                (SELECT COUNT(*) FROM messages
+                    WHERE user_id = rust_user.id AND deleted_at IS NULL) AS "message_count!"
+                FROM rust_user WHERE id = $1"#,
+                id
+            )
+            .fetch_optional(&self.db)
+            .await?;
+
+            Ok(user)
+        })
+    }
+
+    fn missing_users<'a>(&'a self, ids: &'a [Uuid]) -> BoxFuture<'a, Vec<Uuid>> {
+        Box::pin(async move {
+            // Todos os ids são verificados em uma única consulta.
+            let found: Vec<Uuid> = query!("SELECT id FROM rust_user WHERE id = ANY($1)", ids)
+                .fetch_all(&self.db)
+                .await?
+                .into_iter()
+                .map(|row| row.id)
+                .collect();
+
+            Ok(ids
+                .iter()
+                .filter(|id| !found.contains(id))
+                .copied()
+                .collect())
+        })
+    }
+
+    fn find_users_by_name<'a>(
+        &'a self,
+        name: &'a str,
+        page: &'a Page<UserKey>,
+    ) -> BoxFuture<'a, Vec<response::User>> {
+        Box::pin(async move {
+            let search_pattern = search::like_pattern(name); // Formatando o pattern para a pesquisa.
+            let (after_name, after_id) = page.after.clone().unzip();
+
+            let users = query_as!(
+                response::User,
+                r#"SELECT id, name, created_at,
+                (SELECT COUNT(*) FROM messages
+                    WHERE user_id = rust_user.id AND deleted_at IS NULL) AS "message_count!"
+                FROM rust_user
+                WHERE name LIKE $1 AND ($2::VARCHAR IS NULL OR (name, id) > ($2, $3))
+                ORDER BY name, id LIMIT $4"#,
+                search_pattern,
+                after_name,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(users)
+        })
+    }
+
+    fn find_profile(&self, id: Uuid) -> BoxFuture<'_, Option<response::Profile>> {
+        Box::pin(async move {
+            let profile = query_as!(
+                response::Profile,
+                r#"SELECT id, name, display_name, bio, avatar_url, created_at,
+                (SELECT COUNT(*) FROM messages
+                    WHERE user_id = rust_user.id AND deleted_at IS NULL) AS "message_count!",
+                (SELECT COUNT(*) FROM message_likes WHERE user_id = rust_user.id) AS "likes_given!",
+                (SELECT COALESCE(SUM(likes), 0) FROM messages WHERE user_id = rust_user.id) AS "likes_received!"
+                FROM rust_user WHERE id = $1"#,
+                id
+            )
+            .fetch_optional(&self.db)
+            .await?;
+
+            Ok(profile)
+        })
+    }
+
+    fn find_credentials<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Option<schema::User>> {
+        Box::pin(async move {
+            let user = query_as!(
+                schema::User,
+                "SELECT id, password FROM rust_user WHERE name = $1",
+                name
+            )
+            .fetch_optional(&self.db)
+            .await?;
+
+            Ok(user)
+        })
+    }
+
+    fn find_credentials_by_id(&self, id: Uuid) -> BoxFuture<'_, Option<schema::User>> {
+        Box::pin(async move {
+            let user = query_as!(
+                schema::User,
+                "SELECT id, password FROM rust_user WHERE id = $1",
+                id
+            )
+            .fetch_optional(&self.db)
+            .await?;
+
+            Ok(user)
+        })
+    }
+
+    fn add_user<'a>(&'a self, name: &'a str, hash: &'a str) -> BoxFuture<'a, response::User> {
+        Box::pin(async move {
+            // Um nome repetido viola a unicidade de rust_user.name e resulta em 409.
+            let user = query_as!(
+                response::User,
+                r#"INSERT INTO rust_user (name, password) VALUES ($1, $2)
+                RETURNING id, name, created_at, 0::BIGINT AS "message_count!""#,
+                name,
+                hash
+            )
+            .fetch_one(&self.db)
+            .await?;
+
+            Ok(user)
+        })
+    }
+
+    fn update_profile<'a>(
+        &'a self,
+        id: Uuid,
+        changes: &'a ProfileChanges<'a>,
+    ) -> BoxFuture<'a, bool> {
+        Box::pin(async move {
+            // Um nome repetido viola a unicidade de rust_user.name e resulta em 409.
+            let result = query!(
+                "UPDATE rust_user SET \
+                name = COALESCE($2, name), \
+                display_name = NULLIF(COALESCE($3, display_name), ''), \
+                bio = NULLIF(COALESCE($4, bio), ''), \
+                avatar_url = NULLIF(COALESCE($5, avatar_url), ''), \
+                password = COALESCE($6, password) \
+                WHERE id = $1",
+                id,
+                changes.name,
+                changes.display_name,
+                changes.bio,
+                changes.avatar_url,
+                changes.password
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn update_password<'a>(&'a self, id: Uuid, hash: &'a str) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            query!("UPDATE rust_user SET password = $1 WHERE id = $2", hash, id)
+                .execute(&self.db)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn delete_user(&self, id: Uuid) -> BoxFuture<'_, bool> {
+        Box::pin(async move {
+            let result = query!("DELETE FROM rust_user WHERE id = $1", id)
+                .execute(&self.db)
+                .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn add_refresh_token<'a>(
+        &'a self,
+        user_id: Uuid,
+        hash: &'a str,
+        expires_at: DateTime<Utc>,
+    ) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            query!(
+                "INSERT INTO refresh_tokens (user_id, token_hash, expires_at) VALUES ($1, $2, $3)",
+                user_id,
+                hash,
+                expires_at
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn consume_refresh_token<'a>(&'a self, hash: &'a str) -> BoxFuture<'a, Option<Uuid>> {
+        Box::pin(async move {
+            // Consumindo o token de forma atômica para evitar uso duplicado.
+            let consumed = query!(
+                "UPDATE refresh_tokens SET revoked_at = NOW() \
+                WHERE token_hash = $1 AND revoked_at IS NULL AND expires_at > NOW() \
+                RETURNING user_id",
+                hash
+            )
+            .fetch_optional(&self.db)
+            .await?;
+
+            Ok(consumed.map(|row| row.user_id))
+        })
+    }
+
+    fn revoke_reused_refresh_token<'a>(&'a self, hash: &'a str) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            query!(
+                "UPDATE refresh_tokens SET revoked_at = NOW() \
+                WHERE revoked_at IS NULL AND user_id = \
+                (SELECT user_id FROM refresh_tokens WHERE token_hash = $1 AND revoked_at IS NOT NULL)",
+                hash
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn revoke_refresh_token<'a>(&'a self, hash: &'a str, user_id: Uuid) -> BoxFuture<'a, bool> {
+        Box::pin(async move {
+            let result = query!(
+                "UPDATE refresh_tokens SET revoked_at = NOW() \
+                WHERE token_hash = $1 AND user_id = $2 AND revoked_at IS NULL",
+                hash,
+                user_id
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn revoke_all_refresh_tokens(&self, user_id: Uuid) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            query!(
+                "UPDATE refresh_tokens SET revoked_at = NOW() \
+                WHERE user_id = $1 AND revoked_at IS NULL",
+                user_id
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn add_follow(&self, follower_id: Uuid, followed_id: Uuid) -> BoxFuture<'_, bool> {
+        Box::pin(async move {
+            // A chave primária garante que o usuário seja seguido uma única vez.
+            let result = query!(
+                "INSERT INTO follows (follower_id, followed_id) \
+                SELECT $1, id FROM rust_user WHERE id = $2 \
+                ON CONFLICT DO NOTHING",
+                follower_id,
+                followed_id
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn remove_follow(&self, follower_id: Uuid, followed_id: Uuid) -> BoxFuture<'_, bool> {
+        Box::pin(async move {
+            let result = query!(
+                "DELETE FROM follows WHERE follower_id = $1 AND followed_id = $2",
+                follower_id,
+                followed_id
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn followers<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Follow>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            let followers = query_as!(
+                response::Follow,
+                "SELECT f.follower_id AS user_id, u.name, f.followed_at \
+                FROM follows f JOIN rust_user u ON u.id = f.follower_id \
+                WHERE f.followed_id = $1 \
+                AND ($2::TIMESTAMPTZ IS NULL OR (f.followed_at, f.follower_id) < ($2, $3)) \
+                ORDER BY f.followed_at DESC, f.follower_id DESC LIMIT $4",
+                id,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(followers)
+        })
+    }
+
+    fn following<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Follow>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            let following = query_as!(
+                response::Follow,
+                "SELECT f.followed_id AS user_id, u.name, f.followed_at \
+                FROM follows f JOIN rust_user u ON u.id = f.followed_id \
+                WHERE f.follower_id = $1 \
+                AND ($2::TIMESTAMPTZ IS NULL OR (f.followed_at, f.followed_id) < ($2, $3)) \
+                ORDER BY f.followed_at DESC, f.followed_id DESC LIMIT $4",
+                id,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(following)
+        })
+    }
+
+    fn notifications<'a>(
+        &'a self,
+        user_id: Uuid,
+        unread: bool,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Notification>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            // Agrupando pelo tipo e pela mensagem, com os usuários mais recentes de cada grupo.
+            let groups = query!(
+                r#"WITH groups AS (
+                    SELECT kind, message_id, MAX(created_at) AS created_at,
+                    (ARRAY_AGG(id ORDER BY created_at DESC, id DESC))[1] AS id,
+                    COUNT(DISTINCT actor_id) AS actor_count,
+                    BOOL_AND(read_at IS NOT NULL) AS read
+                    FROM notifications WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+                    GROUP BY kind, message_id
+                )
+                SELECT g.id AS "id!", g.kind AS "kind!: schema::NotificationKind", g.message_id,
+                g.created_at AS "created_at!", g.actor_count AS "actor_count!", g.read AS "read!",
+                a.actors AS "actors!: Json<Vec<response::Actor>>"
+                FROM groups g CROSS JOIN LATERAL (
+                    SELECT COALESCE(JSON_AGG(JSON_BUILD_OBJECT('user_id', r.id, 'name', r.name)
+                        ORDER BY r.created_at DESC), '[]') AS actors
+                    FROM (
+                        SELECT u.id, u.name, MAX(n.created_at) AS created_at
+                        FROM notifications n JOIN rust_user u ON u.id = n.actor_id
+                        WHERE n.user_id = $1 AND n.kind = g.kind
+                        AND n.message_id IS NOT DISTINCT FROM g.message_id
+                        AND (NOT $2 OR n.read_at IS NULL)
+                        GROUP BY u.id, u.name ORDER BY created_at DESC LIMIT $6
+                    ) r
+                ) a
+                WHERE $3::TIMESTAMPTZ IS NULL OR (g.created_at, g.id) < ($3, $4)
+                ORDER BY g.created_at DESC, g.id DESC LIMIT $5"#,
+                user_id,
+                unread,
+                after_at,
+                after_id,
+                page.fetch(),
+                response::NOTIFICATION_ACTORS as i64
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(groups
+                .into_iter()
+                .map(|g| {
+                    response::Notification::new(
+                        g.id,
+                        g.kind,
+                        g.message_id,
+                        g.actors.0,
+                        g.actor_count,
+                        g.read,
+                        g.created_at,
+                    )
+                })
+                .collect())
+        })
+    }
+
+    fn read_notification(&self, user_id: Uuid, id: Uuid) -> BoxFuture<'_, Option<u64>> {
+        Box::pin(async move {
+            let result = query!(
+                r#"WITH target AS (
+                    SELECT id, kind, message_id, created_at FROM notifications
+                    WHERE id = $1 AND user_id = $2
+                ), marked AS (
+                    UPDATE notifications n SET read_at = NOW() FROM target t
+                    WHERE n.user_id = $2 AND n.kind = t.kind
+                    AND n.message_id IS NOT DISTINCT FROM t.message_id
+                    AND (n.created_at, n.id) <= (t.created_at, t.id) AND n.read_at IS NULL
+                    RETURNING n.id
+                )
+                SELECT EXISTS (SELECT 1 FROM target) AS "found!",
+                (SELECT COUNT(*) FROM marked) AS "marked!""#,
+                id,
+                user_id
+            )
+            .fetch_one(&self.db)
+            .await?;
+
+            Ok(result.found.then_some(result.marked as u64))
+        })
+    }
+
+    fn read_all_notifications(&self, user_id: Uuid) -> BoxFuture<'_, u64> {
+        Box::pin(async move {
+            let result = query!(
+                "UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND read_at IS NULL",
+                user_id
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(result.rows_affected())
+        })
+    }
+
+    fn notification_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> BoxFuture<'_, response::NotificationPreferences> {
+        Box::pin(async move {
+            let preferences = query_as!(
+                response::NotificationPreferences,
+                "SELECT likes, replies, mentions, follows FROM notification_preferences \
+                WHERE user_id = $1",
+                user_id
+            )
+            .fetch_optional(&self.db)
+            .await?;
+
+            Ok(preferences.unwrap_or_default())
+        })
+    }
+
+    fn update_notification_preferences<'a>(
+        &'a self,
+        user_id: Uuid,
+        changes: &'a NotificationChanges,
+    ) -> BoxFuture<'a, response::NotificationPreferences> {
+        Box::pin(async move {
+            let preferences = query_as!(
+                response::NotificationPreferences,
+                "INSERT INTO notification_preferences (user_id, likes, replies, mentions, follows) \
+                VALUES ($1, COALESCE($2, TRUE), COALESCE($3, TRUE), COALESCE($4, TRUE), COALESCE($5, TRUE)) \
+                ON CONFLICT (user_id) DO UPDATE SET \
+                likes = COALESCE($2, notification_preferences.likes), \
+                replies = COALESCE($3, notification_preferences.replies), \
+                mentions = COALESCE($4, notification_preferences.mentions), \
+                follows = COALESCE($5, notification_preferences.follows) \
+                RETURNING likes, replies, mentions, follows",
+                user_id,
+                changes.likes,
+                changes.replies,
+                changes.mentions,
+                changes.follows
+            )
+            .fetch_one(&self.db)
+            .await?;
+
+            Ok(preferences)
+        })
+    }
+}
+
+impl MessageRepository for PostgresRepository {
+    fn all_messages<'a>(&'a self, page: &'a Page<TimeKey>) -> BoxFuture<'a, Vec<schema::Message>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            let messages = query_as!(
+                schema::Message,
+                "SELECT id, likes, user_id, content, sended_at, edited_at, parent_id, reply_count, \
+                deleted_at, entities AS \"entities!: _\" FROM messages WHERE deleted_at IS NULL \
+                AND ($1::TIMESTAMPTZ IS NULL OR (sended_at, id) < ($1, $2)) \
+                ORDER BY sended_at DESC, id DESC LIMIT $3",
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(messages)
+        })
+    }
+
+    fn find_message(&self, id: Uuid) -> BoxFuture<'_, Option<schema::Message>> {
+        Box::pin(async move {
+            let message = query_as!(
+                schema::Message,
+                "SELECT id, likes, user_id, content, sended_at, edited_at, parent_id, reply_count, \
+                deleted_at, entities AS \"entities!: _\" FROM messages WHERE id = $1 AND deleted_at IS NULL",
+                id
+            )
+            .fetch_optional(&self.db)
+            .await?;
+
+            Ok(message)
+        })
+    }
+
+    fn find_messages_by_content<'a>(
+        &'a self,
+        content: &'a str,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>> {
+        Box::pin(async move {
+            let pattern = search::like_pattern(content); // Formatando o pattern para a pesquisa.
+            let (after_at, after_id) = page.after.unzip();
+
+            let messages = query_as!(
+                schema::Message,
+                "SELECT id, likes, user_id, content, sended_at, edited_at, parent_id, reply_count, \
+                deleted_at, entities AS \"entities!: _\" FROM messages \
+                WHERE content LIKE $1 AND deleted_at IS NULL \
+                AND ($2::TIMESTAMPTZ IS NULL OR (sended_at, id) < ($2, $3)) \
+                ORDER BY sended_at DESC, id DESC LIMIT $4",
+                pattern,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(messages)
+        })
+    }
+
+    fn search_messages<'a>(
+        &'a self,
+        tsquery: &'a str,
+        language: search::Language,
+        page: &'a Page<RankKey>,
+    ) -> BoxFuture<'a, Vec<response::SearchResult>> {
+        Box::pin(async move {
+            let (after_rank, after_at, after_id) = match page.after {
+                Some((rank, at, id)) => (Some(rank), Some(at), Some(id)),
+                None => (None, None, None),
+            };
+
+            // Os trechos são gerados apenas para as linhas da página.
+            let rows = query!(
+                r#"SELECT p.id AS "id!", p.likes AS "likes!", p.user_id AS "user_id!",
+                p.content AS "content!", p.sended_at AS "sended_at!", p.edited_at, p.parent_id,
+                p.reply_count AS "reply_count!", p.deleted_at,
+                p.entities AS "entities!: Json<Vec<schema::Entity>>", p.rank AS "rank!",
+                ts_headline($1::REGCONFIG, p.content, to_tsquery($1::REGCONFIG, $2),
+                    'StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=20, MinWords=5') AS "snippet!"
+                FROM (
+                    SELECT id, likes, user_id, content, sended_at, edited_at, parent_id,
+                    reply_count, deleted_at, entities, ts_rank_cd(search_vector, query) AS rank
+                    FROM messages, to_tsquery($1::REGCONFIG, $2) query
+                    WHERE search_vector @@ query AND deleted_at IS NULL
+                ) p
+                WHERE $3::REAL IS NULL OR (p.rank, p.sended_at, p.id) < ($3, $4, $5)
+                ORDER BY p.rank DESC, p.sended_at DESC, p.id DESC LIMIT $6"#,
+                language.regconfig() as _,
+                tsquery,
+                after_rank,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(rows
+                .into_iter()
+                .map(|row| response::SearchResult {
+                    message: schema::Message {
+                        id: row.id,
+                        likes: row.likes,
+                        user_id: row.user_id,
+                        content: row.content,
+                        sended_at: row.sended_at,
+                        edited_at: row.edited_at,
+                        parent_id: row.parent_id,
+                        reply_count: row.reply_count,
+                        deleted_at: row.deleted_at,
+                        entities: row.entities,
+                    },
+                    rank: row.rank,
+                    snippet: row.snippet,
+                })
+                .collect())
+        })
+    }
+
+    fn messages_from_user<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            let messages = query_as!(
+                schema::Message,
+                "SELECT id, likes, user_id, content, sended_at, edited_at, parent_id, reply_count, \
+                deleted_at, entities AS \"entities!: _\" FROM messages \
+                WHERE user_id = $1 AND deleted_at IS NULL \
+                AND ($2::TIMESTAMPTZ IS NULL OR (sended_at, id) < ($2, $3)) \
+                ORDER BY sended_at DESC, id DESC LIMIT $4",
+                user_id,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(messages)
+        })
+    }
+
+    fn home_timeline<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            // Cada usuário seguido contribui com no máximo uma página, lida
+            // pelo índice (user_id, sended_at, id), e só então as páginas são
+            // combinadas. O custo cresce com a quantidade de seguidos vezes o
+            // tamanho da página, e não com o total de mensagens deles.
+            let messages = query_as!(
+                schema::Message,
+                r#"SELECT m.id AS "id!", m.likes AS "likes!", m.user_id AS "user_id!",
+                m.content AS "content!", m.sended_at AS "sended_at!", m.edited_at, m.parent_id,
+                m.reply_count AS "reply_count!", m.deleted_at, m.entities AS "entities!: _"
+                FROM follows f
+                CROSS JOIN LATERAL (
+                    SELECT id, likes, user_id, content, sended_at, edited_at, parent_id,
+                    reply_count, deleted_at, entities FROM messages
+                    WHERE user_id = f.followed_id AND deleted_at IS NULL
+                    AND ($2::TIMESTAMPTZ IS NULL OR (sended_at, id) < ($2, $3))
+                    ORDER BY sended_at DESC, id DESC LIMIT $4
+                ) m
+                WHERE f.follower_id = $1
+                ORDER BY m.sended_at DESC, m.id DESC LIMIT $4"#,
+                user_id,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(messages)
+        })
+    }
+
+    fn tag_timeline<'a>(
+        &'a self,
+        tag: &'a str,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            let messages = query_as!(
+                schema::Message,
+                r#"SELECT m.id, m.likes, m.user_id, m.content, m.sended_at, m.edited_at, m.parent_id,
+                m.reply_count, m.deleted_at, m.entities AS "entities!: _"
+                FROM message_tags t JOIN messages m ON m.id = t.message_id
+                WHERE t.tag = $1 AND m.deleted_at IS NULL
+                AND ($2::TIMESTAMPTZ IS NULL OR (t.sended_at, t.message_id) < ($2, $3))
+                ORDER BY t.sended_at DESC, t.message_id DESC LIMIT $4"#,
+                tag,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(messages)
+        })
+    }
+
+    fn mention_timeline<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::Message>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            let messages = query_as!(
+                schema::Message,
+                r#"SELECT m.id, m.likes, m.user_id, m.content, m.sended_at, m.edited_at, m.parent_id,
+                m.reply_count, m.deleted_at, m.entities AS "entities!: _"
+                FROM message_mentions mm JOIN messages m ON m.id = mm.message_id
+                WHERE mm.user_id = $1 AND m.deleted_at IS NULL
+                AND ($2::TIMESTAMPTZ IS NULL OR (mm.sended_at, mm.message_id) < ($2, $3))
+                ORDER BY mm.sended_at DESC, mm.message_id DESC LIMIT $4"#,
+                user_id,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(messages)
+        })
+    }
+
+    fn thread<'a>(
+        &'a self,
+        id: Uuid,
+        depth: i32,
+        replies: i64,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Option<(schema::Message, Vec<schema::Message>)>> {
+        Box::pin(async move {
+            let root = query_as!(
+                schema::Message,
+                "SELECT id, likes, user_id, content, sended_at, edited_at, parent_id, reply_count, \
+                deleted_at, entities AS \"entities!: _\" FROM messages WHERE id = $1",
+                id
+            )
+            .fetch_optional(&self.db)
+            .await?;
+
+            let Some(root) = root else {
+                return Ok(None);
+            };
+
+            let (after_at, after_id) = page.after.unzip();
+
+            // Cada nível é lido pelo índice (parent_id, sended_at, id), com
+            // no máximo `replies` respostas por mensagem abaixo da página.
+            let descendants = query_as!(
+                schema::Message,
+                r#"WITH RECURSIVE tree AS (
+                    SELECT page.*, 1 AS depth FROM (
+                        SELECT id, likes, user_id, content, sended_at, edited_at, parent_id,
+                        reply_count, deleted_at, entities FROM messages
+                        WHERE parent_id = $1
+                        AND ($2::TIMESTAMPTZ IS NULL OR (sended_at, id) > ($2, $3))
+                        ORDER BY sended_at, id LIMIT $4
+                    ) page
+                    UNION ALL
+                    SELECT child.*, tree.depth + 1 FROM tree
+                    CROSS JOIN LATERAL (
+                        SELECT id, likes, user_id, content, sended_at, edited_at, parent_id,
+                        reply_count, deleted_at, entities FROM messages
+                        WHERE parent_id = tree.id
+                        ORDER BY sended_at, id LIMIT $5
+                    ) child
+                    WHERE tree.depth < $6
+                )
+                SELECT id AS "id!", likes AS "likes!", user_id AS "user_id!",
+                content AS "content!", sended_at AS "sended_at!", edited_at, parent_id,
+                reply_count AS "reply_count!", deleted_at, entities AS "entities!: _" FROM tree"#,
+                id,
+                after_at,
+                after_id,
+                page.fetch(),
+                replies,
+                depth
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(Some((root, descendants)))
+        })
+    }
+
+    fn add_message<'a>(
+        &'a self,
+        user_id: Uuid,
+        content: &'a str,
+        parent_id: Option<Uuid>,
+        entities: &'a [schema::Entity],
+    ) -> BoxFuture<'a, schema::Message> {
+        Box::pin(async move {
+            let mut tx = self.db.begin().await?;
+            let entities = resolve_mentions(&mut tx, entities).await?;
+
+            // O contador de respostas da mensagem respondida é mantido por um gatilho.
+            let message = query_as!(
+                schema::Message,
+                "INSERT INTO messages (user_id, content, parent_id, entities) VALUES ($1, $2, $3, $4) \
+                RETURNING id, likes, user_id, content, sended_at, edited_at, parent_id, reply_count, \
+                deleted_at, entities AS \"entities!: _\"",
+                user_id,
+                content,
+                parent_id,
+                Json(&entities) as _
+            )
+            .fetch_one(&mut tx)
+            .await?;
+
+            index_entities(&mut tx, message.id, message.sended_at, &entities).await?;
+
+            tx.commit().await?;
+            Ok(message)
+        })
+    }
+
+    fn edit_message<'a>(
+        &'a self,
+        id: Uuid,
+        user_id: Uuid,
+        content: &'a str,
+        entities: &'a [schema::Entity],
+    ) -> BoxFuture<'a, schema::Message> {
+        Box::pin(async move {
+            let mut tx = self.db.begin().await?;
+
+            // Travando a mensagem para que edições simultâneas não percam revisões.
+            let current = query!(
+                r#"SELECT user_id, content, COALESCE(edited_at, sended_at) AS "written_at!"
+                FROM messages WHERE id = $1 AND deleted_at IS NULL FOR UPDATE"#,
+                id
+            )
+            .fetch_optional(&mut tx)
+            .await?;
+
+            check_ownership(current.as_ref().map(|c| c.user_id), user_id)?;
+            let current = current.expect("check_ownership checks the message exists");
+            let entities = resolve_mentions(&mut tx, entities).await?;
+
+            query!(
+                "INSERT INTO message_revisions (message_id, content, written_at) VALUES ($1, $2, $3)",
+                id,
+                current.content,
+                current.written_at
+            )
+            .execute(&mut tx)
+            .await?;
+
+            let message = query_as!(
+                schema::Message,
+                "UPDATE messages SET content = $2, entities = $3, edited_at = NOW() WHERE id = $1 \
+                RETURNING id, likes, user_id, content, sended_at, edited_at, parent_id, reply_count, \
+                deleted_at, entities AS \"entities!: _\"",
+                id,
+                content,
+                Json(&entities) as _
+            )
+            .fetch_one(&mut tx)
+            .await?;
+
+            index_entities(&mut tx, id, message.sended_at, &entities).await?;
+
+            tx.commit().await?;
+            Ok(message)
+        })
+    }
+
+    fn delete_message(&self, id: Uuid, user_id: Uuid) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            // Diferenciando uma mensagem inexistente de uma mensagem de outro usuário.
+            let mut tx = self.db.begin().await?;
+
+            // Travando a mensagem para que uma resposta simultânea não fique sem a mensagem respondida.
+            let current = query!(
+                "SELECT user_id, reply_count FROM messages \
+                WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+                id
+            )
+            .fetch_optional(&mut tx)
+            .await?;
+
+            check_ownership(current.as_ref().map(|c| c.user_id), user_id)?;
+
+            // A mensagem com respostas vira um tombstone, mantendo a conversa.
+            let result = match current.is_some_and(|c| c.reply_count > 0) {
+                true => {
+                    query!("DELETE FROM message_likes WHERE message_id = $1", id)
+                        .execute(&mut tx)
+                        .await?;
+                    query!("DELETE FROM message_revisions WHERE message_id = $1", id)
+                        .execute(&mut tx)
+                        .await?;
+                    index_entities(&mut tx, id, Utc::now(), &[]).await?;
+                    query!(
+                        "UPDATE messages SET content = '', entities = '[]', edited_at = NULL, \
+                        deleted_at = NOW() WHERE id = $1 AND user_id = $2",
+                        id,
+                        user_id
+                    )
+                    .execute(&mut tx)
+                    .await?
+                }
+                false => {
+                    query!(
+                        "DELETE FROM messages WHERE id = $1 AND user_id = $2",
+                        id,
+                        user_id
+                    )
+                    .execute(&mut tx)
+                    .await?
+                }
+            };
+
+            tx.commit().await?;
+
+            // Verificando se alguma linha foi afetada.
+            if result.rows_affected() == 0 {
+                return Err(AppError::NotFound(
+                    "This message doesn't exist!".to_string(),
+                )); // Retornando o erro que indica que a mensagem já foi removida.
+            }
+
+            Ok(())
+        })
+    }
+
+    fn message_history<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<schema::MessageRevision>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            let revisions = query_as!(
+                schema::MessageRevision,
+                "SELECT * FROM message_revisions \
+                WHERE message_id = $1 AND ($2::TIMESTAMPTZ IS NULL OR (replaced_at, id) < ($2, $3)) \
+                ORDER BY replaced_at DESC, id DESC LIMIT $4",
+                id,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(revisions)
+        })
+    }
+
+    fn add_like(&self, message_id: Uuid, user_id: Uuid) -> BoxFuture<'_, bool> {
+        Box::pin(async move {
+            // A chave primária garante uma única curtida por usuário mesmo em requisições simultâneas.
+            // O contador de messages.likes é mantido por um gatilho do banco de dados.
+            let result = query!(
+                "INSERT INTO message_likes (message_id, user_id) \
+                SELECT id, $2 FROM messages WHERE id = $1 AND deleted_at IS NULL \
+                ON CONFLICT DO NOTHING",
+                message_id,
+                user_id
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn remove_like(&self, message_id: Uuid, user_id: Uuid) -> BoxFuture<'_, bool> {
+        Box::pin(async move {
+            let result = query!(
+                "DELETE FROM message_likes WHERE message_id = $1 AND user_id = $2",
+                message_id,
+                user_id
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    fn message_likes<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Like>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            let likes = query_as!(
+                response::Like,
+                "SELECT l.user_id, u.name, l.liked_at \
+                FROM message_likes l JOIN rust_user u ON u.id = l.user_id \
+                WHERE l.message_id = $1 \
+                AND ($2::TIMESTAMPTZ IS NULL OR (l.liked_at, l.user_id) < ($2, $3)) \
+                ORDER BY l.liked_at DESC, l.user_id DESC LIMIT $4",
+                id,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(likes)
+        })
+    }
+}
+
+// Método para obter a conversa pelo ponto de vista do participante,
+// retornando None quando o usuário não participa dela.
+async fn find_conversation(
+    conn: &mut PgConnection,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<response::Conversation>, AppError> {
+    let conversation = query!(
+        r#"SELECT c.id, c.direct_key IS NOT NULL AS "direct!", c.created_at, c.last_message_at,
+        (SELECT JSON_AGG(JSON_BUILD_OBJECT('user_id', u.id, 'name', u.name, 'last_read_at', m.last_read_at)
+            ORDER BY u.name, u.id)
+            FROM conversation_members m JOIN rust_user u ON u.id = m.user_id
+            WHERE m.conversation_id = c.id) AS "members!: Json<Vec<response::Member>>",
+        (SELECT COUNT(*) FROM direct_messages d
+            WHERE d.conversation_id = c.id AND d.user_id <> me.user_id
+            AND d.sended_at > COALESCE(me.last_read_at, '-infinity')) AS "unread!"
+        FROM conversations c
+        JOIN conversation_members me ON me.conversation_id = c.id AND me.user_id = $2
+        WHERE c.id = $1"#,
+        id,
+        user_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(conversation.map(|c| response::Conversation {
+        id: c.id,
+        direct: c.direct,
+        members: c.members.0,
+        created_at: c.created_at,
+        last_message_at: c.last_message_at,
+        unread: c.unread,
+    }))
+}
+
+impl ConversationRepository for PostgresRepository {
+    fn add_conversation<'a>(
+        &'a self,
+        user_id: Uuid,
+        members: &'a [Uuid],
+    ) -> BoxFuture<'a, (response::Conversation, bool)> {
+        Box::pin(async move {
+            let mut tx = self.db.begin().await?;
+
+            // A chave única garante uma única conversa direta por par mesmo em
+            // requisições simultâneas. As conversas em grupo não têm chave.
+            let key = match members {
+                [other_id] => Some(direct_key(user_id, *other_id)),
+                _ => None,
+            };
+            let inserted = query!(
+                "INSERT INTO conversations (direct_key) VALUES ($1) \
+                ON CONFLICT (direct_key) DO NOTHING RETURNING id",
+                key
+            )
+            .fetch_optional(&mut tx)
+            .await?;
+
+            let (id, created) = match inserted {
+                Some(conversation) => {
+                    let users: Vec<Uuid> = members.iter().copied().chain([user_id]).collect();
+                    query!(
+                        "INSERT INTO conversation_members (conversation_id, user_id) \
+                        SELECT $1, UNNEST($2::UUID[])",
+                        conversation.id,
+                        &users[..]
+                    )
+                    .execute(&mut tx)
+                    .await?;
+                    (conversation.id, true)
+                }
+                None => {
+                    let existing =
+                        query!("SELECT id FROM conversations WHERE direct_key = $1", key)
+                            .fetch_one(&mut tx)
+                            .await?;
+                    (existing.id, false)
+                }
+            };
+
+            let conversation = find_conversation(&mut tx, id, user_id)
+                .await?
+                .ok_or_else(AppError::missing_reference)?;
+
+            tx.commit().await?;
+            Ok((conversation, created))
+        })
+    }
+
+    fn is_member(&self, id: Uuid, user_id: Uuid) -> BoxFuture<'_, Option<bool>> {
+        Box::pin(async move {
+            let conversation = query!(
+                r#"SELECT EXISTS (
+                    SELECT 1 FROM conversation_members WHERE conversation_id = $1 AND user_id = $2
+                ) AS "member!" FROM conversations WHERE id = $1"#,
+                id,
+                user_id
+            )
+            .fetch_optional(&self.db)
+            .await?;
+
+            Ok(conversation.map(|c| c.member))
+        })
+    }
+
+    fn find_conversation(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> BoxFuture<'_, Option<response::Conversation>> {
+        Box::pin(async move {
+            let mut conn = self.db.acquire().await?;
+            find_conversation(&mut conn, id, user_id).await
+        })
+    }
+
+    fn conversations<'a>(
+        &'a self,
+        user_id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::Conversation>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            let conversations = query!(
+                r#"SELECT c.id, c.direct_key IS NOT NULL AS "direct!", c.created_at, c.last_message_at,
+                (SELECT JSON_AGG(JSON_BUILD_OBJECT('user_id', u.id, 'name', u.name, 'last_read_at', m.last_read_at)
+                    ORDER BY u.name, u.id)
+                    FROM conversation_members m JOIN rust_user u ON u.id = m.user_id
+                    WHERE m.conversation_id = c.id) AS "members!: Json<Vec<response::Member>>",
+                (SELECT COUNT(*) FROM direct_messages d
+                    WHERE d.conversation_id = c.id AND d.user_id <> me.user_id
+                    AND d.sended_at > COALESCE(me.last_read_at, '-infinity')) AS "unread!"
+                FROM conversation_members me JOIN conversations c ON c.id = me.conversation_id
+                WHERE me.user_id = $1
+                AND ($2::TIMESTAMPTZ IS NULL OR (c.last_message_at, c.id) < ($2, $3))
+                ORDER BY c.last_message_at DESC, c.id DESC LIMIT $4"#,
+                user_id,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(conversations
+                .into_iter()
+                .map(|c| response::Conversation {
+                    id: c.id,
+                    direct: c.direct,
+                    members: c.members.0,
+                    created_at: c.created_at,
+                    last_message_at: c.last_message_at,
+                    unread: c.unread,
+                })
+                .collect())
+        })
+    }
+
+    fn direct_messages<'a>(
+        &'a self,
+        id: Uuid,
+        page: &'a Page<TimeKey>,
+    ) -> BoxFuture<'a, Vec<response::DirectMessage>> {
+        Box::pin(async move {
+            let (after_at, after_id) = page.after.unzip();
+
+            let messages = query_as!(
+                response::DirectMessage,
+                r#"SELECT d.id, d.conversation_id, d.user_id, d.content, d.sended_at,
+                ARRAY(SELECT m.user_id FROM conversation_members m
+                    WHERE m.conversation_id = d.conversation_id AND m.user_id <> d.user_id
+                    AND m.last_read_at >= d.sended_at ORDER BY m.user_id) AS "read_by!"
+                FROM direct_messages d
+                WHERE d.conversation_id = $1
+                AND ($2::TIMESTAMPTZ IS NULL OR (d.sended_at, d.id) < ($2, $3))
+                ORDER BY d.sended_at DESC, d.id DESC LIMIT $4"#,
+                id,
+                after_at,
+                after_id,
+                page.fetch()
+            )
+            .fetch_all(&self.db)
+            .await?;
+
+            Ok(messages)
+        })
+    }
+
+    fn add_direct_message<'a>(
+        &'a self,
+        id: Uuid,
+        user_id: Uuid,
+        content: &'a str,
+    ) -> BoxFuture<'a, Option<response::DirectMessage>> {
+        Box::pin(async move {
+            let mut tx = self.db.begin().await?;
+
+            let message = query!(
+                "INSERT INTO direct_messages (conversation_id, user_id, content) \
+                SELECT conversation_id, user_id, $3 FROM conversation_members \
+                WHERE conversation_id = $1 AND user_id = $2 \
+                RETURNING id, conversation_id, user_id, content, sended_at",
+                id,
+                user_id,
+                content
+            )
+            .fetch_optional(&mut tx)
+            .await?;
+
+            let Some(message) = message else {
+                return Ok(None);
+            };
+
+            // O autor leu a conversa até a própria mensagem.
+            query!(
+                "UPDATE conversations SET last_message_at = GREATEST(last_message_at, $2) WHERE id = $1",
+                id,
+                message.sended_at
+            )
+            .execute(&mut tx)
+            .await?;
+            query!(
+                "UPDATE conversation_members SET last_read_at = GREATEST(last_read_at, $3) \
+                WHERE conversation_id = $1 AND user_id = $2",
+                id,
+                user_id,
+                message.sended_at
+            )
+            .execute(&mut tx)
+            .await?;
+
+            tx.commit().await?;
+            Ok(Some(response::DirectMessage {
+                id: message.id,
+                conversation_id: message.conversation_id,
+                user_id: message.user_id,
+                content: message.content,
+                sended_at: message.sended_at,
+                read_by: Vec::new(),
+            }))
+        })
+    }
+
+    fn read_conversation(&self, id: Uuid, user_id: Uuid) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            query!(
+                "UPDATE conversation_members SET last_read_at = GREATEST(last_read_at, \
+                (SELECT MAX(sended_at) FROM direct_messages WHERE conversation_id = $1)) \
+                WHERE conversation_id = $1 AND user_id = $2",
+                id,
+                user_id
+            )
+            .execute(&self.db)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn unread(&self, user_id: Uuid) -> BoxFuture<'_, response::Unread> {
+        Box::pin(async move {
+            let unread = query_as!(
+                response::Unread,
+                r#"SELECT COUNT(*) AS "messages!", COUNT(DISTINCT d.conversation_id) AS "conversations!"
+                FROM conversation_members m JOIN direct_messages d ON d.conversation_id = m.conversation_id
+                WHERE m.user_id = $1 AND d.user_id <> $1
+                AND d.sended_at > COALESCE(m.last_read_at, '-infinity')"#,
+                user_id
+            )
+            .fetch_one(&self.db)
+            .await?;
+
+            Ok(unread)
+        })
+    }
+}
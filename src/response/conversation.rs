@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Participante de uma conversa com a confirmação de leitura: as
+// mensagens enviadas até last_read_at foram lidas por ele.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct Member {
+    pub user_id: Uuid,
+    pub name: String,
+    pub last_read_at: Option<DateTime<Utc>>,
+}
+
+// Conversa privada com os participantes e a quantidade de mensagens
+// ainda não lidas pelo usuário autenticado.
+#[derive(Serialize, ToSchema)]
+pub struct Conversation {
+    pub id: Uuid,
+    pub direct: bool,
+    pub members: Vec<Member>,
+    pub created_at: DateTime<Utc>,
+    pub last_message_at: DateTime<Utc>,
+    pub unread: i64,
+}
+
+// Mensagem de uma conversa com os participantes que já a leram,
+// sem contar o autor.
+#[derive(Clone, Serialize, FromRow, ToSchema)]
+pub struct DirectMessage {
+    pub id: Uuid,
+    pub conversation_id: Uuid,
+    pub user_id: Uuid,
+    pub content: String,
+    pub sended_at: DateTime<Utc>,
+    pub read_by: Vec<Uuid>,
+}
+
+// Mensagens não lidas pelo usuário em todas as conversas.
+#[derive(Serialize, FromRow, ToSchema)]
+pub struct Unread {
+    pub messages: i64,
+    pub conversations: i64,
+}
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Usuário de uma lista de seguidores ou de seguidos,
+// com a hora em que passou a seguir ou a ser seguido.
+#[derive(Serialize, FromRow, ToSchema)]
+pub struct Follow {
+    pub user_id: Uuid,
+    pub name: String,
+    pub followed_at: DateTime<Utc>,
+}
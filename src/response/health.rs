@@ -0,0 +1,52 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// Estrutura para retornar a prontidão do servidor
+// junto ao estado de cada componente verificado.
+#[derive(Serialize, ToSchema)]
+pub struct Health {
+    pub server: Component,
+    pub database: Component,
+    pub migrations: Component,
+}
+
+// Estado de um componente: "up", "down" ou "unknown".
+#[derive(Serialize, ToSchema)]
+pub struct Component {
+    #[schema(example = "up")]
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl Component {
+    pub fn up(latency_ms: Option<f64>) -> Self {
+        Self {
+            status: "up".to_string(),
+            latency_ms,
+            message: None,
+        }
+    }
+
+    pub fn down(message: &str) -> Self {
+        Self {
+            status: "down".to_string(),
+            latency_ms: None,
+            message: Some(message.to_string()),
+        }
+    }
+
+    pub fn unknown(message: &str) -> Self {
+        Self {
+            status: "unknown".to_string(),
+            latency_ms: None,
+            message: Some(message.to_string()),
+        }
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.status == "up"
+    }
+}
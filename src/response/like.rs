@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Representação pública de uma curtida, com o
+// nome do usuário que curtiu a mensagem.
+#[derive(Serialize, FromRow, ToSchema)]
+pub struct Like {
+    pub user_id: Uuid,
+    pub name: String,
+    pub liked_at: DateTime<Utc>,
+}
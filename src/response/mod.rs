@@ -1,5 +1,25 @@
+mod conversation;
+mod follow;
+mod health;
+mod like;
+mod notification;
+mod profile;
+mod search_result;
 mod server_error;
+mod session;
 mod success;
+mod thread;
+mod user;
 
+pub use conversation::*; // Exportando as classes de Conversation.
+pub use follow::*; // Exportando a classe de Follow.
+pub use health::*; // Exportando as classes de Health.
+pub use like::*; // Exportando a classe pública de Like.
+pub use notification::*; // Exportando as classes de Notification.
+pub use profile::*; // Exportando a classe de Profile.
+pub use search_result::*; // Exportando a classe de SearchResult.
 pub use server_error::*; // Exportando a classe de ServerError.
+pub use session::*; // Exportando a classe de Session.
 pub use success::*; // Exportando a classe de Success.
+pub use thread::*; // Exportando a classe de Thread.
+pub use user::*; // Exportando a classe pública de User.
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::schema::NotificationKind;
+
+// Usuário que gerou uma notificação.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct Actor {
+    pub user_id: Uuid,
+    pub name: String,
+}
+
+// Notificações do mesmo tipo sobre a mesma mensagem, agrupadas como em
+// "5 people liked your message". Os follows formam um único grupo, sem
+// mensagem. O id é o da notificação mais recente, usado para marcar o
+// grupo como lido.
+#[derive(Serialize, ToSchema)]
+pub struct Notification {
+    pub id: Uuid,
+    pub kind: NotificationKind,
+    pub message_id: Option<Uuid>,
+    #[schema(example = "5 people liked your message")]
+    pub summary: String,
+    #[schema(max_items = 3)]
+    pub actors: Vec<Actor>, // Os mais recentes, até NOTIFICATION_ACTORS.
+    pub actor_count: i64,
+    pub read: bool,
+    pub created_at: DateTime<Utc>, // Da notificação mais recente.
+}
+
+// Quantidade de usuários listados em cada grupo de notificações.
+pub const NOTIFICATION_ACTORS: usize = 3;
+
+impl Notification {
+    pub fn new(
+        id: Uuid,
+        kind: NotificationKind,
+        message_id: Option<Uuid>,
+        actors: Vec<Actor>,
+        actor_count: i64,
+        read: bool,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        let action = match kind {
+            NotificationKind::Like => "liked your message",
+            NotificationKind::Reply => "replied to your message",
+            NotificationKind::Mention => "mentioned you",
+            NotificationKind::Follow => "followed you",
+        };
+        let summary = match actors.first() {
+            Some(actor) if actor_count == 1 => format!("{} {}", actor.name, action),
+            _ => format!("{} people {}", actor_count, action),
+        };
+
+        Self {
+            id,
+            kind,
+            message_id,
+            summary,
+            actors,
+            actor_count,
+            read,
+            created_at,
+        }
+    }
+}
+
+// Tipos de notificação registrados para o usuário.
+#[derive(Clone, Serialize, FromRow, ToSchema)]
+pub struct NotificationPreferences {
+    pub likes: bool,
+    pub replies: bool,
+    pub mentions: bool,
+    pub follows: bool,
+}
+
+// Sem preferências gravadas, todos os tipos são registrados.
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            likes: true,
+            replies: true,
+            mentions: true,
+            follows: true,
+        }
+    }
+}
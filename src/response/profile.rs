@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Perfil público de um usuário, com as quantidades de mensagens
+// publicadas, curtidas feitas e curtidas recebidas.
+#[derive(Serialize, FromRow, ToSchema)]
+pub struct Profile {
+    pub id: Uuid,
+    pub name: String,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub message_count: i64,
+    pub likes_given: i64,
+    pub likes_received: i64,
+}
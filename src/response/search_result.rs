@@ -0,0 +1,14 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::schema;
+
+// Mensagem encontrada pela busca textual junto à relevância
+// e a um trecho com os termos destacados por <mark>.
+#[derive(Serialize, ToSchema)]
+pub struct SearchResult {
+    pub message: schema::Message,
+    pub rank: f32,
+    #[schema(example = "Aprendendo <mark>Rust</mark>")]
+    pub snippet: String,
+}
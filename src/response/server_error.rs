@@ -1,10 +1,12 @@
 use chrono::{DateTime, Utc};
 use serde::Serialize;
+use utoipa::ToSchema;
 
 // Estrutura para retornar alguma resposta
 // quando ouver erro,
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ServerError {
+    #[schema(example = "not found")]
     pub status: String,
     pub message: String,
     pub timestamp: DateTime<Utc>,
@@ -18,14 +20,4 @@ impl ServerError {
             timestamp: Utc::now(),        // Definindo a hora em que ocorreu o error.
         }
     }
-
-    // Retorna um erro a partir de um error do sqlx
-    // evitando aumentos no codigo de origem.
-    pub fn from_sqlx_error(err: sqlx::Error) -> Self {
-        Self {
-            status: "internal server error".to_string(), // Definindo o titulo devido a incerteza sobre o erro.
-            message: err.to_string(), // Definindo a mensagem do erro desconhecido.
-            timestamp: Utc::now(),    // Definindo a hora em que ocorreu o erro.
-        }
-    }
 }
@@ -0,0 +1,14 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// Estrutura para retornar os tokens emitidos
+// no login e na atualização da sessão.
+#[derive(Serialize, ToSchema)]
+pub struct Session {
+    #[schema(example = "Bearer")]
+    pub token_type: String,
+    pub access_token: String,
+    pub expires_in: i64,
+    pub refresh_token: String,
+    pub refresh_expires_in: i64,
+}
@@ -1,13 +1,20 @@
 use chrono::{DateTime, Utc};
 use serde::Serialize;
+use utoipa::ToSchema;
 
 // Estrutura para retornar respostas de sucesso
 // em alguma operação junto a um status, um
 // valor generico e a hora da requisição.
-#[derive(Serialize)]
+// Listas paginadas também carregam o cursor
+// da próxima página e se ela existe.
+#[derive(Serialize, ToSchema)]
 pub struct Success<T> {
     pub status: String,
     pub response: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_more: Option<bool>,
     pub timestamp: DateTime<Utc>,
 }
 
@@ -16,6 +23,18 @@ impl<T> Success<T> {
         Self {
             status: status.to_string(), // Definindo o status do retorno esperado.
             response,                   // Definindo o valor de sucesso da resposta.
+            next_cursor: None,          // Respostas simples não são paginadas.
+            has_more: None,
+            timestamp: Utc::now(), // Definindo a hora em que a requisição foi respondida.
+        }
+    }
+
+    pub fn page(status: &str, response: T, next_cursor: Option<String>) -> Self {
+        Self {
+            status: status.to_string(), // Definindo o status do retorno esperado.
+            response,                   // Definindo os itens da página.
+            has_more: Some(next_cursor.is_some()), // Indicando se há uma próxima página.
+            next_cursor,                // Definindo o cursor da próxima página.
             timestamp: Utc::now(),      // Definindo a hora em que a requisição foi respondida.
         }
     }
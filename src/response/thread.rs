@@ -0,0 +1,14 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::schema;
+
+// Mensagem de uma conversa com as respostas abaixo dela. As respostas
+// além da profundidade pedida são indicadas apenas pelo reply_count.
+#[derive(Serialize, ToSchema)]
+pub struct Thread {
+    #[serde(flatten)]
+    pub message: schema::Message,
+    #[schema(no_recursion)]
+    pub replies: Vec<Thread>,
+}
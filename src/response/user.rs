@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Representação pública de um usuário retornada
+// pelas rotas, sem o hash da senha.
+#[derive(Serialize, FromRow, ToSchema)]
+pub struct User {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub message_count: i64,
+}
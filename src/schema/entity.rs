@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityKind {
+    Hashtag,
+    Mention,
+}
+
+// Hashtag ou menção encontrada no conteúdo de uma mensagem. As posições
+// são em bytes, de "start" até antes de "end", incluindo o "#" ou o "@".
+// O texto da hashtag fica em minúsculas e a menção traz o usuário.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize, ToSchema)]
+#[schema(example = json!({ "kind": "hashtag", "text": "rust", "start": 10, "end": 15 }))]
+pub struct Entity {
+    pub kind: EntityKind,
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+}
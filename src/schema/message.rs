@@ -1,13 +1,36 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::prelude::FromRow;
+use sqlx::{prelude::FromRow, types::Json};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, FromRow)]
+use super::Entity;
+
+// Mensagem, opcionalmente em resposta a outra, com as hashtags e as
+// menções do conteúdo. A mensagem removida que ainda tem respostas
+// fica sem conteúdo, com deleted_at.
+#[derive(Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Message {
     pub id: Uuid,
     pub likes: i32,
     pub user_id: Uuid,
     pub content: String,
     pub sended_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub parent_id: Option<Uuid>,
+    pub reply_count: i32,
+    pub deleted_at: Option<DateTime<Utc>>,
+    #[schema(value_type = Vec<Entity>)]
+    pub entities: Json<Vec<Entity>>,
+}
+
+// Conteúdo anterior de uma mensagem editada, com a hora
+// em que foi escrito e a hora em que foi substituído.
+#[derive(Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct MessageRevision {
+    pub id: Uuid,
+    pub message_id: Uuid,
+    pub content: String,
+    pub written_at: DateTime<Utc>,
+    pub replaced_at: DateTime<Utc>,
 }
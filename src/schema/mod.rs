@@ -1,5 +1,9 @@
+mod entity;
 mod message;
+mod notification;
 mod user;
 
+pub use entity::*;
 pub use message::*;
+pub use notification::*;
 pub use user::*;
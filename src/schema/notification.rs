@@ -0,0 +1,13 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// Tipo de uma notificação, como o enum notification_kind do PostgreSQL.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "notification_kind", rename_all = "lowercase")]
+pub enum NotificationKind {
+    Like,
+    Reply,
+    Mention,
+    Follow,
+}
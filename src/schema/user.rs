@@ -1,10 +1,11 @@
-use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
 use uuid::Uuid;
 
-#[derive(Deserialize, Serialize, FromRow)]
+// Credenciais de um usuário em rust_user, lidas na autenticação.
+// Não implementa Serialize para que o hash da senha nunca possa
+// ser enviado em uma resposta HTTP; use response::User.
+#[derive(Clone, FromRow)]
 pub struct User {
     pub id: Uuid,
-    pub name: String,
     pub password: String,
 }
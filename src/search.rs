@@ -0,0 +1,138 @@
+use serde::Deserialize;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+// Idiomas aceitos pela busca textual, indexados em messages.search_vector.
+#[derive(Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[serde(alias = "pt")]
+    Portuguese,
+    #[serde(alias = "en")]
+    English,
+}
+
+impl Language {
+    // Nome da configuração de busca textual do PostgreSQL.
+    pub fn regconfig(&self) -> &'static str {
+        match self {
+            Language::Portuguese => "portuguese",
+            Language::English => "english",
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "portuguese" | "pt" => Ok(Language::Portuguese),
+            "english" | "en" => Ok(Language::English),
+            _ => Err(()),
+        }
+    }
+}
+
+// Separa um termo digitado pelo usuário em lexemas seguros para to_tsquery,
+// descartando qualquer caractere que seja um operador da consulta.
+pub fn lexemes(term: &str) -> Vec<String> {
+    term.split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_lowercase())
+        .collect()
+}
+
+// Converte a busca do usuário em uma consulta de to_tsquery.
+// Suporta:
+//   palavra      -> todas as palavras devem aparecer;
+//   "uma frase"  -> palavras em sequência;
+//   prefi*       -> palavras que começam com o prefixo;
+//   -palavra     -> a palavra não pode aparecer;
+//   a or b       -> qualquer uma das alternativas.
+// Retorna None quando não sobra nenhum termo pesquisável.
+pub fn to_tsquery(input: &str) -> Option<String> {
+    let mut groups: Vec<Vec<String>> = vec![Vec::new()]; // Alternativas separadas por "or".
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        // Lendo o próximo token, com uma frase entre aspas sendo um único token.
+        let negated = c == '-';
+        if negated {
+            chars.next();
+        }
+
+        let (token, phrase) = if chars.peek() == Some(&'"') {
+            chars.next();
+            let token: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            (token, true)
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            (token, false)
+        };
+
+        if !phrase && !negated && token.eq_ignore_ascii_case("or") {
+            groups.push(Vec::new());
+            continue;
+        }
+
+        let prefix = !phrase && token.ends_with('*');
+        let words = lexemes(&token);
+        if words.is_empty() {
+            continue;
+        }
+
+        let mut term = words.join(" <-> ");
+        if prefix {
+            term.push_str(":*");
+        }
+        if words.len() > 1 {
+            term = format!("({})", term);
+        }
+        if negated {
+            term = format!("!{}", term);
+        }
+
+        if let Some(group) = groups.last_mut() {
+            group.push(term);
+        }
+    }
+
+    let groups: Vec<String> = groups
+        .into_iter()
+        .filter(|group| !group.is_empty())
+        .map(|group| format!("({})", group.join(" & ")))
+        .collect();
+
+    match groups.is_empty() {
+        true => None,
+        false => Some(groups.join(" | ")),
+    }
+}
+
+// Monta o pattern de LIKE para buscar o texto em qualquer posição,
+// escapando os curingas "%" e "_" digitados pelo usuário.
+pub fn like_pattern(input: &str) -> String {
+    let mut pattern = String::with_capacity(input.len() + 2);
+    pattern.push('%');
+    for c in input.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            pattern.push('\\'); // Escape padrão do LIKE no PostgreSQL.
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
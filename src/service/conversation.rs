@@ -0,0 +1,293 @@
+use actix_web::{
+    web::{Data, Json, Path, Query},
+    HttpResponse,
+};
+use chrono::{DateTime, Utc};
+use tracing::info;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthUser,
+    error::AppError,
+    model,
+    openapi::{scope, Scope},
+    pagination, response,
+};
+
+// Quantidade máxima de participantes de uma conversa, incluindo quem a cria.
+pub const MAX_MEMBERS: usize = 10;
+// Tamanho máximo do conteúdo de uma mensagem direta, em caracteres.
+pub const CONTENT_MAX: usize = 1000;
+
+// Método para verificar se o usuário participa da conversa.
+// O erro ocorre quando a conversa não existe ou quando o usuário não participa dela.
+async fn check_member(data: &crate::AppState, id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+    match data.conversations.is_member(id, user_id).await? {
+        Some(true) => Ok(()),
+        Some(false) => Err(AppError::Forbidden(
+            "Only the members can access this conversation!".to_string(),
+        )), // Retornando o erro que indica uma conversa de outros usuários.
+        None => Err(AppError::NotFound(
+            "This conversation doesn't exist!".to_string(),
+        )), // Retornando o erro que indica que a conversa não existe.
+    }
+}
+
+// Método para obter a conversa pelo ponto de vista do participante.
+// O erro ocorre quando a conversa não existe ou quando o usuário não participa dela.
+async fn find_conversation(
+    data: &crate::AppState,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<response::Conversation, AppError> {
+    check_member(data, id, user_id).await?;
+
+    data.conversations
+        .find_conversation(id, user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This conversation doesn't exist!".to_string()))
+    // Retornando o erro que indica que a conversa deixou de existir.
+}
+
+// Método para criar uma conversa do usuário autenticado com outros
+// usuários, retornando uma resposta HTTP contendo a conversa. Com um
+// único usuário a conversa é direta, e a conversa direta já existente
+// é retornada com o status 200.
+// O erro ocorre quando não há outros usuários, quando há usuários
+// demais ou quando algum usuário não existe.
+pub async fn add_conversation(
+    user: AuthUser,
+    body: Json<model::Conversation>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Ignorando o próprio usuário e os usuários repetidos.
+    let mut members: Vec<Uuid> = Vec::new();
+    for id in body.user_ids.iter().filter(|id| **id != user.id) {
+        if !members.contains(id) {
+            members.push(*id);
+        }
+    }
+
+    if members.is_empty() || members.len() >= MAX_MEMBERS {
+        return Err(AppError::BadRequest(format!(
+            "A conversation must have between 2 and {} members!",
+            MAX_MEMBERS
+        ))); // Retornando o erro que indica uma quantidade inválida de participantes.
+    }
+
+    if !data.users.missing_users(&members).await?.is_empty() {
+        return Err(AppError::NotFound("This user doesn't exist!".to_string())); // Retornando o erro que indica que algum usuário não existe.
+    }
+
+    let (conversation, created) = data
+        .conversations
+        .add_conversation(user.id, &members)
+        .await?;
+
+    match created {
+        true => Ok(HttpResponse::Created().json(response::Success::new("created", conversation))),
+        false => Ok(HttpResponse::Ok().json(response::Success::new("success", conversation))),
+    } // Retornando a conversa criada ou a conversa direta existente.
+}
+
+// Método para obter as conversas do usuário autenticado, da mensagem
+// mais recente para a mais antiga, retornando uma resposta HTTP
+// contendo uma página de response::Conversation.
+// O erro ocorre quando a paginação é inválida.
+pub async fn get_conversations(
+    user: AuthUser,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+
+    let conversations = data.conversations.conversations(user.id, &page).await?;
+
+    let (conversations, next) =
+        pagination::finish(conversations, &page, |c| (c.last_message_at, c.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", conversations, next)))
+    // Retornando a página de conversas.
+}
+
+// Método para obter uma conversa do usuário autenticado, retornando
+// uma resposta HTTP contendo a conversa com as confirmações de leitura.
+// O erro ocorre quando o UUID é inválido, quando a conversa não existe
+// ou quando o usuário não participa dela.
+pub async fn get_conversation(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("read"))?; // Retornando o erro que indica UUID inválido.
+
+    let conversation = find_conversation(&data, id, user.id).await?;
+
+    Ok(HttpResponse::Ok().json(response::Success::new("success", conversation)))
+    // Retornando a conversa.
+}
+
+// Método para obter as mensagens de uma conversa, das mais novas para
+// as mais antigas, retornando uma resposta HTTP contendo uma página de
+// response::DirectMessage.
+// O erro ocorre quando o UUID é inválido, quando a conversa não existe,
+// quando o usuário não participa dela ou quando a paginação é inválida.
+pub async fn get_direct_messages(
+    user: AuthUser,
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("list"))?; // Retornando o erro que indica UUID inválido.
+
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+
+    check_member(&data, id, user.id).await?;
+
+    let messages = data.conversations.direct_messages(id, &page).await?;
+
+    let (messages, next) = pagination::finish(messages, &page, |m| (m.sended_at, m.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", messages, next)))
+    // Retornando a página de mensagens.
+}
+
+// Método para enviar uma mensagem a uma conversa do usuário autenticado,
+// retornando uma resposta HTTP contendo a mensagem enviada.
+// O erro ocorre quando o UUID é inválido, quando a mensagem está vazia
+// ou é longa demais, quando a conversa não existe ou quando o usuário
+// não participa dela.
+pub async fn add_direct_message(
+    user: AuthUser,
+    path: Path<String>,
+    body: Json<model::DirectMessage>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("send"))?; // Retornando o erro que indica UUID inválido.
+
+    if body.content.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "The message can't be empty!".to_string(),
+        )); // Retornando o erro que indica uma mensagem vazia.
+    }
+    if body.content.chars().count() > CONTENT_MAX {
+        return Err(AppError::BadRequest(format!(
+            "The message must have at most {} characters!",
+            CONTENT_MAX
+        ))); // Retornando o erro que indica uma mensagem longa demais.
+    }
+
+    check_member(&data, id, user.id).await?;
+
+    // A participação é verificada novamente ao inserir a mensagem.
+    let message = data
+        .conversations
+        .add_direct_message(id, user.id, &body.content)
+        .await?
+        .ok_or_else(|| {
+            AppError::Forbidden("Only the members can access this conversation!".to_string())
+        })?;
+
+    Ok(HttpResponse::Created().json(response::Success::new("created", message)))
+    // Retornando a mensagem enviada.
+}
+
+// Método para marcar uma conversa como lida pelo usuário autenticado até
+// a mensagem mais recente, retornando uma resposta HTTP contendo a conversa.
+// O erro ocorre quando o UUID é inválido, quando a conversa não existe
+// ou quando o usuário não participa dela.
+pub async fn read_conversation(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("read"))?; // Retornando o erro que indica UUID inválido.
+
+    check_member(&data, id, user.id).await?;
+    data.conversations.read_conversation(id, user.id).await?;
+
+    let conversation = find_conversation(&data, id, user.id).await?;
+
+    Ok(HttpResponse::Ok().json(response::Success::new("read", conversation))) // Retornando a conversa lida.
+}
+
+// Método para obter a quantidade de mensagens não lidas pelo usuário
+// autenticado, retornando uma resposta HTTP contendo response::Unread.
+pub async fn get_unread(
+    user: AuthUser,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    let unread = data.conversations.unread(user.id).await?;
+
+    Ok(HttpResponse::Ok().json(response::Success::new("success", unread))) // Retornando as mensagens não lidas.
+}
+
+// Documento OpenAPI das rotas do escopo "dm".
+#[derive(OpenApi)]
+#[openapi(paths(
+    crate::controller::conversation::post_conversation,
+    crate::controller::conversation::get_conversations,
+    crate::controller::conversation::get_conversation,
+    crate::controller::conversation::get_direct_messages,
+    crate::controller::conversation::post_direct_message,
+    crate::controller::conversation::read_conversation,
+    crate::controller::conversation::get_unread,
+))]
+pub struct Api;
+
+// Método utilizado no escopo principal para obter o escopo "dm".
+pub fn get_scope() -> Scope {
+    scope("/dm")
+        .service(crate::controller::conversation::post_conversation) // Criar uma conversa.   POST    ("api/dm/conversations")
+        .service(crate::controller::conversation::get_conversations) // Obter as conversas.   GET     ("api/dm/conversations")
+        .service(crate::controller::conversation::get_conversation) // Obter uma conversa.    GET     ("api/dm/conversations/{id}")
+        .service(crate::controller::conversation::get_direct_messages) // Obter as mensagens. GET     ("api/dm/conversations/{id}/messages")
+        .service(crate::controller::conversation::post_direct_message) // Enviar mensagem.    POST    ("api/dm/conversations/{id}/messages")
+        .service(crate::controller::conversation::read_conversation) // Marcar como lida.     POST    ("api/dm/conversations/{id}/read")
+        .service(crate::controller::conversation::get_unread) // Obter as não lidas.          GET     ("api/dm/unread")
+}
+
+// Logs da rota.
+pub fn logs() {
+    info!("loading direct message route");
+    info!(
+        method = "POST",
+        route = "api/dm/conversations",
+        "Add a conversation with other users."
+    );
+    info!(
+        method = "GET",
+        route = "api/dm/conversations",
+        "Return the conversations of the authenticated user."
+    );
+    info!(
+        method = "GET",
+        route = "api/dm/conversations/{id}",
+        "Return one conversation by id."
+    );
+    info!(
+        method = "GET",
+        route = "api/dm/conversations/{id}/messages",
+        "Return the messages of a conversation."
+    );
+    info!(
+        method = "POST",
+        route = "api/dm/conversations/{id}/messages",
+        "Send a message to a conversation."
+    );
+    info!(
+        method = "POST",
+        route = "api/dm/conversations/{id}/read",
+        "Mark a conversation as read."
+    );
+    info!(
+        method = "GET",
+        route = "api/dm/unread",
+        "Return the number of unread direct messages."
+    );
+}
@@ -0,0 +1,110 @@
+use actix_web::{
+    http::header::{CacheControl, CacheDirective},
+    web::Path,
+    HttpResponse,
+};
+use tracing::info;
+use utoipa::OpenApi;
+
+use crate::{error::AppError, openapi};
+
+// Versão do Swagger UI copiada do pacote swagger-ui-dist do npm para
+// "assets/swagger-ui", junto da licença. Para atualizar, substitua os
+// arquivos pelos da nova versão e altere esta constante.
+pub const SWAGGER_UI_VERSION: &str = "5.17.14";
+
+// Arquivos do Swagger UI embutidos no binário: nome, tipo e conteúdo.
+const SWAGGER_UI_ASSETS: [(&str, &str, &str); 2] = [
+    (
+        "swagger-ui.css",
+        "text/css; charset=utf-8",
+        include_str!("../../assets/swagger-ui/swagger-ui.css"),
+    ),
+    (
+        "swagger-ui-bundle.js",
+        "text/javascript; charset=utf-8",
+        include_str!("../../assets/swagger-ui/swagger-ui-bundle.js"),
+    ),
+];
+
+// Página do Swagger UI, carregando os arquivos servidos pelo próprio
+// binário e apontando para o documento ao lado ("api/openapi.json").
+const DOCS_PAGE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <title>Global Rust Server API</title>
+    <link rel="stylesheet" href="swagger-ui/swagger-ui.css" />
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="swagger-ui/swagger-ui-bundle.js"></script>
+    <script>
+        window.onload = () => {
+            window.ui = SwaggerUIBundle({ url: "openapi.json", dom_id: "#swagger-ui" });
+        };
+    </script>
+</body>
+</html>
+"##;
+
+// Método para obter o documento OpenAPI 3 da api.
+pub async fn get_openapi() -> HttpResponse {
+    HttpResponse::Ok().json(openapi::document())
+}
+
+// Método para obter a página interativa do documento OpenAPI.
+pub async fn get_docs() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(DOCS_PAGE)
+}
+
+// Método para obter um arquivo do Swagger UI pelo nome.
+// O erro ocorre quando o arquivo não faz parte dos embutidos.
+pub async fn get_swagger_ui_asset(path: Path<String>) -> Result<HttpResponse, AppError> {
+    let file = path.into_inner();
+    let (_, content_type, content) = SWAGGER_UI_ASSETS
+        .iter()
+        .find(|(name, _, _)| *name == file)
+        .ok_or_else(|| AppError::NotFound("This file doesn't exist!".to_string()))?; // Retornando o erro que indica que o arquivo não existe.
+
+    Ok(HttpResponse::Ok()
+        .content_type(*content_type)
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(86400),
+        ]))
+        .body(*content)) // Retornando o arquivo, que só muda com uma nova versão do binário.
+}
+
+// Documento OpenAPI das rotas de documentação, registradas no escopo "api".
+#[derive(OpenApi)]
+#[openapi(paths(
+    crate::controller::docs::get_openapi,
+    crate::controller::docs::get_docs,
+    crate::controller::docs::get_swagger_ui_asset,
+))]
+pub struct Api;
+
+// Logs da rota.
+pub fn logs() {
+    info!("loading docs route");
+    info!(
+        method = "GET",
+        route = "api/openapi.json",
+        "Return the OpenAPI document."
+    );
+    info!(
+        method = "GET",
+        route = "api/docs",
+        "Return the interactive API documentation."
+    );
+    info!(
+        method = "GET",
+        route = "api/swagger-ui/{file}",
+        version = SWAGGER_UI_VERSION,
+        "Return a file of the bundled Swagger UI."
+    );
+}
@@ -0,0 +1,125 @@
+use actix_web::{
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{auth::AuthUser, error::AppError, model, pagination, response};
+
+// Método para obter um usuário pelo id, usado para diferenciar
+// um usuário inexistente de um follow inexistente.
+// O erro ocorre quando o usuário não existe.
+async fn find_user(data: &crate::AppState, id: Uuid) -> Result<response::User, AppError> {
+    data.users
+        .find_user(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This user doesn't exist!".to_string()))
+    // Retornando o erro que indica que o usuário não existe.
+}
+
+// Método para seguir um usuário com o usuário autenticado
+// retornando uma resposta HTTP contendo o usuário seguido.
+// O erro ocorre quando o UUID é inválido, quando o usuário tenta
+// seguir a si mesmo, quando o usuário não existe ou quando já é seguido.
+pub async fn follow_user(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("follow"))?; // Retornando o erro que indica UUID inválido.
+
+    if id == user.id {
+        return Err(AppError::BadRequest(
+            "A user can't follow themselves!".to_string(),
+        )); // Retornando o erro que indica um follow de si mesmo.
+    }
+
+    // Um único follow por par de usuários é garantido mesmo em requisições simultâneas.
+    let inserted = data.users.add_follow(user.id, id).await?;
+
+    let followed = find_user(&data, id).await?;
+    if !inserted {
+        return Err(AppError::Conflict(
+            "This user is already followed!".to_string(),
+        )); // Retornando o erro que indica um follow repetido.
+    }
+
+    Ok(HttpResponse::Created().json(response::Success::new("followed", followed)))
+    // Retornando o usuário seguido.
+}
+
+// Método para deixar de seguir um usuário com o usuário autenticado
+// retornando uma resposta HTTP contendo o usuário.
+// O erro ocorre quando o UUID é inválido, quando o usuário não
+// existe ou quando não é seguido.
+pub async fn unfollow_user(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("unfollow"))?; // Retornando o erro que indica UUID inválido.
+
+    let deleted = data.users.remove_follow(user.id, id).await?;
+
+    let unfollowed = find_user(&data, id).await?;
+    if !deleted {
+        return Err(AppError::NotFound("This user isn't followed!".to_string()));
+        // Retornando o erro que indica que o follow não existe.
+    }
+
+    Ok(HttpResponse::Ok().json(response::Success::new("unfollowed", unfollowed)))
+    // Retornando o usuário que deixou de ser seguido.
+}
+
+// Método para obter os seguidores de um usuário, dos follows mais
+// novos para os mais antigos, retornando uma resposta HTTP contendo
+// uma página de response::Follow.
+// O erro ocorre quando o UUID é inválido, quando o usuário não
+// existe ou quando a paginação é inválida.
+pub async fn get_followers(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("search"))?; // Retornando o erro que indica UUID inválido.
+
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+
+    find_user(&data, id).await?;
+
+    let followers = data.users.followers(id, &page).await?;
+
+    let (followers, next) = pagination::finish(followers, &page, |f| (f.followed_at, f.user_id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", followers, next)))
+    // Retornando a página de seguidores.
+}
+
+// Método para obter os usuários seguidos por um usuário, dos follows
+// mais novos para os mais antigos, retornando uma resposta HTTP
+// contendo uma página de response::Follow.
+// O erro ocorre quando o UUID é inválido, quando o usuário não
+// existe ou quando a paginação é inválida.
+pub async fn get_following(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("search"))?; // Retornando o erro que indica UUID inválido.
+
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+
+    find_user(&data, id).await?;
+
+    let following = data.users.following(id, &page).await?;
+
+    let (following, next) = pagination::finish(following, &page, |f| (f.followed_at, f.user_id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", following, next)))
+    // Retornando a página de usuários seguidos.
+}
@@ -0,0 +1,109 @@
+use actix_web::{rt::time::timeout, web::Data, HttpResponse};
+use sqlx::query_scalar;
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+use utoipa::OpenApi;
+
+use crate::{
+    migration,
+    openapi::{scope, Scope},
+    response::{self, Component},
+};
+
+// Tempo máximo de cada verificação da prontidão.
+const READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Método para indicar que o processo está de pé, sem
+// depender do banco de dados ou de outro componente.
+pub async fn live() -> HttpResponse {
+    HttpResponse::Ok().json(response::Success::new("alive", "The process is up"))
+}
+
+// Método para verificar se o servidor pode receber requisições,
+// consultando o banco de dados e as migrações aplicadas.
+// Retorna 503 com o estado de cada componente quando algum deles
+// está indisponível ou quando o servidor está sendo encerrado.
+pub async fn ready(data: Data<crate::AppState>) -> HttpResponse {
+    let server = match data.draining.load(Ordering::SeqCst) {
+        true => Component::down("The server is shutting down"),
+        false => Component::up(None),
+    };
+
+    let start = Instant::now();
+    let database = match timeout(
+        READINESS_TIMEOUT,
+        query_scalar::<_, i32>("SELECT 1").fetch_one(&data.db),
+    )
+    .await
+    {
+        Ok(Ok(_)) => Component::up(Some(start.elapsed().as_secs_f64() * 1000.0)),
+        Ok(Err(err)) => {
+            warn!("readiness database check failed: {}", err); // Propagando um log de aviso.
+            Component::down("The database is unavailable")
+        }
+        Err(_) => Component::down("The database didn't answer in time"),
+    };
+
+    // As migrações só podem ser verificadas com o banco de dados disponível.
+    let migrations = match database.is_up() {
+        true => match timeout(READINESS_TIMEOUT, migration::status(&data.db)).await {
+            Ok(Ok(status)) if status.pending.is_empty() && status.unknown.is_empty() => {
+                Component::up(None)
+            }
+            Ok(Ok(status)) => Component::down(&format!(
+                "Pending migrations: {:?}, unknown migrations: {:?}",
+                status.pending, status.unknown
+            )),
+            Ok(Err(err)) => {
+                warn!("readiness migration check failed: {}", err); // Propagando um log de aviso.
+                Component::down("Unable to read the applied migrations")
+            }
+            Err(_) => Component::down("The migration check didn't answer in time"),
+        },
+        false => Component::unknown("Depends on the database"),
+    };
+
+    let ready = server.is_up() && database.is_up() && migrations.is_up();
+    let health = response::Health {
+        server,
+        database,
+        migrations,
+    };
+
+    match ready {
+        true => HttpResponse::Ok().json(response::Success::new("ready", health)), // Retornando a prontidão.
+        false => {
+            HttpResponse::ServiceUnavailable().json(response::Success::new("unavailable", health))
+        } // Retornando os componentes indisponíveis.
+    }
+}
+
+// Documento OpenAPI das rotas do escopo "health".
+#[derive(OpenApi)]
+#[openapi(paths(crate::controller::health::live, crate::controller::health::ready,))]
+pub struct Api;
+
+// Método utilizado no escopo principal para obter o escopo "health".
+pub fn get_scope() -> Scope {
+    scope("/health")
+        .service(crate::controller::health::live) //  Processo de pé.            GET     ("health/live")
+        .service(crate::controller::health::ready) // Pronto para requisições.   GET     ("health/ready")
+}
+
+// Logs da rota.
+pub fn logs() {
+    info!("loading health route");
+    info!(
+        method = "GET",
+        route = "health/live",
+        "Return whether the process is up."
+    );
+    info!(
+        method = "GET",
+        route = "health/ready",
+        "Return whether the server can take requests."
+    );
+}
@@ -0,0 +1,104 @@
+use actix_web::{
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{auth::AuthUser, error::AppError, feed, model, pagination, response, schema};
+
+// Método para obter uma mensagem pelo id, usado para diferenciar
+// uma mensagem inexistente de uma curtida inexistente.
+// O erro ocorre quando a mensagem não existe.
+async fn find_message(data: &crate::AppState, id: Uuid) -> Result<schema::Message, AppError> {
+    data.messages
+        .find_message(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This message doesn't exist!".to_string()))
+    // Retornando o erro que indica que a mensagem não existe.
+}
+
+// Método para curtir uma mensagem com o usuário autenticado
+// retornando uma resposta HTTP contendo a mensagem atualizada.
+// O contador é mantido pelo repositório a cada curtida inserida.
+// O erro ocorre quando o UUID é inválido, quando a mensagem não
+// existe ou quando o usuário já curtiu a mensagem.
+pub async fn like_message(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("like"))?; // Retornando o erro que indica UUID inválido.
+
+    // Uma única curtida por usuário é garantida mesmo em requisições simultâneas.
+    let inserted = data.messages.add_like(id, user.id).await?;
+
+    let message = find_message(&data, id).await?;
+    if !inserted {
+        return Err(AppError::Conflict(
+            "This user already liked this message!".to_string(),
+        )); // Retornando o erro que indica uma curtida repetida.
+    }
+
+    // Publicando a curtida para as conexões do feed em tempo real.
+    if let Some(hub) = &data.feed {
+        hub.do_send(feed::Publish::like(&message, user.id));
+    }
+
+    Ok(HttpResponse::Created().json(response::Success::new("liked", message))) // Retornando a mensagem com o contador atualizado.
+}
+
+// Método para remover a curtida do usuário autenticado
+// retornando uma resposta HTTP contendo a mensagem atualizada.
+// O erro ocorre quando o UUID é inválido, quando a mensagem não
+// existe ou quando o usuário não curtiu a mensagem.
+pub async fn unlike_message(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("unlike"))?; // Retornando o erro que indica UUID inválido.
+
+    let deleted = data.messages.remove_like(id, user.id).await?;
+
+    let message = find_message(&data, id).await?;
+    if !deleted {
+        return Err(AppError::NotFound(
+            "This user didn't like this message!".to_string(),
+        )); // Retornando o erro que indica que a curtida não existe.
+    }
+
+    // Publicando a remoção da curtida para as conexões do feed em tempo real.
+    if let Some(hub) = &data.feed {
+        hub.do_send(feed::Publish::unlike(&message, user.id));
+    }
+
+    Ok(HttpResponse::Ok().json(response::Success::new("unliked", message))) // Retornando a mensagem com o contador atualizado.
+}
+
+// Método para obter os usuários que curtiram uma mensagem, das
+// curtidas mais novas para as mais antigas, retornando uma resposta
+// HTTP contendo uma página de response::Like.
+// O erro ocorre quando o UUID é inválido, quando a mensagem não
+// existe ou quando a paginação é inválida.
+pub async fn get_message_likes(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("search"))?; // Retornando o erro que indica UUID inválido.
+
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+
+    find_message(&data, id).await?;
+
+    let likes = data.messages.message_likes(id, &page).await?;
+
+    let (likes, next) = pagination::finish(likes, &page, |l| (l.liked_at, l.user_id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", likes, next)))
+    // Retornando a página de curtidas.
+}
@@ -1,225 +1,587 @@
 use actix_web::{
-    web::{scope, Data, Json, Path},
-    HttpResponse, Scope,
+    http::header,
+    web::{Data, Json, Path, Payload, Query},
+    HttpRequest, HttpResponse,
 };
-use sqlx::{query, query_as};
+use actix_web_actors::ws;
+use chrono::{DateTime, Utc};
+use tracing::info;
+use utoipa::OpenApi;
 use uuid::Uuid;
 
-use crate::{model, response, schema};
+use crate::{
+    auth::AuthUser,
+    entity,
+    error::AppError,
+    feed, model,
+    openapi::{scope, Scope},
+    pagination, response, search,
+};
 
-// Método para obter todas as mensagens da Tech-Connect
-// retornando uma resposta HTTP contendo uma lista de schema::Message.
-pub async fn get_all_messages(data: Data<crate::AppState>) -> HttpResponse {
-    match query_as!(schema::Message, "SELECT * FROM messages")
-        .fetch_all(&data.db)
-        .await
-    {
-        Ok(messages) => HttpResponse::Ok().json(response::Success::new("success", messages)), // Retornando a lista de mensagens.
-        Err(err) => {
-            HttpResponse::InternalServerError().json(response::ServerError::from_sqlx_error(err))
-        } // Retornando erro inesperado pelo sqlx.
+// Tamanho máximo do conteúdo de uma mensagem, em caracteres.
+pub const CONTENT_MAX: usize = 1000;
+
+// Método para validar o conteúdo de uma mensagem adicionada ou editada.
+// O erro ocorre quando o conteúdo está vazio ou excede o tamanho máximo.
+fn validate_content(content: &str) -> Result<(), AppError> {
+    if content.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "The message can't be empty!".to_string(),
+        ));
+    }
+    if content.chars().count() > CONTENT_MAX {
+        return Err(AppError::BadRequest(format!(
+            "The message must have at most {} characters!",
+            CONTENT_MAX
+        )));
     }
+
+    Ok(())
+}
+
+// Método para obter as mensagens da Tech-Connect, das mais novas
+// para as mais antigas, retornando uma resposta HTTP contendo uma
+// página de schema::Message.
+// O erro ocorre quando a paginação é inválida.
+pub async fn get_all_messages(
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+    let messages = data.messages.all_messages(&page).await?;
+
+    let (messages, next) = pagination::finish(messages, &page, |m| (m.sended_at, m.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", messages, next)))
+    // Retornando a página de mensagens.
 }
 
 // Método para obter uma mensagem a partir do seu id (UUID)
 // retornando um schema::Message.
 // O erro ocorre quando o UUID não é valido ou quando nenhuma
 // mensagem for encontrada.
-pub async fn get_message_by_id(path: Path<String>, data: Data<crate::AppState>) -> HttpResponse {
+pub async fn get_message_by_id(
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
     // Passando a variável de path para UUID.
-    match Uuid::parse_str(&path.into_inner()) {
-        Ok(uuid) => {
-            match query_as!(
-                schema::Message,
-                "SELECT * FROM messages WHERE id = $1",
-                uuid
-            )
-            .fetch_one(&data.db)
-            .await
-            {
-                Ok(message) => HttpResponse::Ok().json(response::Success::new("found", message)), // Retornando o usuário que corresponde ao UUID.
-                Err(_) => HttpResponse::NotFound().json(response::ServerError::new(
-                    "not found",
-                    "This UUID doesn't exist in the database!",
-                )), // Retornando o erro no qual indica que a mensagem correspondente não foi encontrada.
-            }
-        }
-        Err(_) => HttpResponse::BadRequest().json(response::ServerError::new(
-            "bad request",
-            "Invalid UUID format, unable to search!",
-        )), // Retornando o erro que indca UUID inválido.,
-    }
+    let uuid = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("search"))?; // Retornando o erro que indca UUID inválido.
+
+    let message = data.messages.find_message(uuid).await?.ok_or_else(|| {
+        AppError::NotFound("This UUID doesn't exist in the database!".to_string())
+    })?; // Retornando o erro no qual indica que a mensagem correspondente não foi encontrada.
+
+    Ok(HttpResponse::Ok().json(response::Success::new("found", message))) // Retornando a mensagem que corresponde ao UUID.
 }
 
 // Método para obter algumas mensagens a partir de conteúdo
 // presente dentro da mensagem, retorna uma resposta HTTP
-// contendo uma lista de mensagens.
-// O erro ocorre quando não há nenhuma mensagem correspondente.
+// contendo uma página de mensagens.
+// O erro ocorre quando não há nenhuma mensagem correspondente
+// ou quando a paginação é inválida.
 pub async fn get_messages_by_content(
     path: Path<String>,
+    query: Query<model::Page>,
     data: Data<crate::AppState>,
-) -> HttpResponse {
-    // Formatando o pattern para a pesquisa.
-    let pattern = format!("%{}%", path.into_inner());
-
-    match query_as!(
-        schema::Message,
-        "SELECT * FROM messages WHERE content LIKE $1",
-        pattern
-    )
-    .fetch_all(&data.db)
-    .await
-    {
-        Ok(messages) => {
-            if messages.is_empty() {
-                // Retornando not found quando nenhuma mensagem é
-                // correspondida a determinado conteúdo
-                HttpResponse::NotFound().json(response::ServerError::new(
-                    "not found",
-                    "Found no messages with this content!",
-                ))
-            } else {
-                // Retornando a lista das mensagens correspondentes.
-                HttpResponse::Ok().json(response::Success::new("found", messages))
-            }
-        }
-        Err(err) => {
-            // Retornando o erro inesperado pelo sqlx.
-            HttpResponse::InternalServerError().json(response::ServerError::from_sqlx_error(err))
-        }
+) -> Result<HttpResponse, AppError> {
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+    let messages = data.messages.find_messages_by_content(&path, &page).await?;
+
+    if messages.is_empty() && page.after.is_none() {
+        // Retornando not found quando nenhuma mensagem é
+        // correspondida a determinado conteúdo
+        return Err(AppError::NotFound(
+            "Found no messages with this content!".to_string(),
+        ));
     }
+
+    // Retornando a página das mensagens correspondentes.
+    let (messages, next) = pagination::finish(messages, &page, |m| (m.sended_at, m.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("found", messages, next)))
+}
+
+// Método para buscar mensagens por texto completo, ordenadas pela
+// relevância, retornando uma resposta HTTP contendo uma página de
+// response::SearchResult com trechos destacados.
+// O erro ocorre quando a busca não possui termos pesquisáveis ou
+// quando a paginação é inválida.
+pub async fn search_messages(
+    search: Query<model::Search>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Convertendo a busca do usuário para uma consulta de to_tsquery.
+    let tsquery = search::to_tsquery(&search.q).ok_or_else(|| {
+        AppError::BadRequest("The search must contain at least one word!".to_string())
+    })?; // Retornando o erro que indica uma busca vazia.
+    let language = search.language.unwrap_or(data.search_language);
+
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(f32, DateTime<Utc>, Uuid)>(&query)?;
+    let results = data
+        .messages
+        .search_messages(&tsquery, language, &page)
+        .await?;
+
+    let (results, next) = pagination::finish(results, &page, |r| {
+        (r.rank, r.message.sended_at, r.message.id)
+    });
+    Ok(HttpResponse::Ok().json(response::Success::page("found", results, next)))
+    // Retornando a página de resultados.
 }
 
 // Método para obter todas as mensagens de determinado
 // usuário pelo id retornando uma resposta HTTP contendo
-// uma lista de mensagens.
-// O erro ocorre quando o UUID é inválido ou quando o
-// usuario não existe.
+// uma página de mensagens.
+// O erro ocorre quando o UUID é inválido, quando o
+// usuario não existe ou quando a paginação é inválida.
 pub async fn get_messages_from_user_id(
     path: Path<String>,
+    query: Query<model::Page>,
     data: Data<crate::AppState>,
-) -> HttpResponse {
+) -> Result<HttpResponse, AppError> {
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+
     // Convertendo o ID do caminho para UUID
-    match Uuid::parse_str(&path.into_inner()) {
-        Ok(uuid) => {
-            // Verifica se o usuário com o UUID fornecido existe
-            match query_as!(schema::User, "SELECT * FROM rust_user WHERE id = $1", uuid)
-                .fetch_optional(&data.db)
-                .await
-            {
-                Ok(Some(_)) => {
-                    // O usuário existe, agora buscar as mensagens
-                    match query_as!(
-                        schema::Message,
-                        "SELECT * FROM messages WHERE user_id = $1",
-                        uuid
-                    )
-                    .fetch_all(&data.db)
-                    .await
-                    {
-                        Ok(messages) => {
-                            // Retorna as mensagens do usuário
-                            HttpResponse::Ok().json(response::Success::new("success", messages))
-                        }
-                        Err(err) => {
-                            // Erro ao consultar as mensagens
-                            HttpResponse::InternalServerError()
-                                .json(response::ServerError::from_sqlx_error(err))
-                        }
-                    }
-                }
-                Ok(None) => {
-                    // Erro quando o usuário não existe.
-                    HttpResponse::NotFound().json(response::ServerError::new(
-                        "not found",
-                        "This user doesn't exist!",
-                    ))
-                }
-                Err(err) => {
-                    // Erro ao consultar o usuário no banco de dados
-                    HttpResponse::InternalServerError()
-                        .json(response::ServerError::from_sqlx_error(err))
-                }
-            }
-        }
-        Err(_) => {
-            // Erro indicando que o UUID é inválido.
-            HttpResponse::BadRequest().json(response::ServerError::new(
-                "bad request",
-                "Invalid UUID format, unable to search!",
-            ))
-        }
-    }
+    let uuid = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("search"))?;
+
+    // Verifica se o usuário com o UUID fornecido existe
+    data.users
+        .find_user(uuid)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This user doesn't exist!".to_string()))?;
+
+    // O usuário existe, agora buscar as mensagens
+    let messages = data.messages.messages_from_user(uuid, &page).await?;
+
+    // Retorna a página de mensagens do usuário
+    let (messages, next) = pagination::finish(messages, &page, |m| (m.sended_at, m.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", messages, next)))
+}
+
+// Método para obter as mensagens com uma hashtag, com ou sem o "#",
+// das mais novas para as mais antigas, retornando uma resposta HTTP
+// contendo uma página de mensagens.
+// O erro ocorre quando a hashtag ou a paginação são inválidas.
+pub async fn get_messages_by_tag(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Normalizando a hashtag como na extração do conteúdo.
+    let tag = entity::normalize_tag(&path.into_inner()).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "The tag must have up to {} letters, digits or underscores, not only digits!",
+            entity::TAG_MAX
+        ))
+    })?; // Retornando o erro que indica uma hashtag inválida.
+
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+    let messages = data.messages.tag_timeline(&tag, &page).await?;
+
+    let (messages, next) = pagination::finish(messages, &page, |m| (m.sended_at, m.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", messages, next)))
+    // Retornando a página de mensagens da hashtag.
+}
+
+// Método para obter as mensagens que mencionam um usuário, das mais
+// novas para as mais antigas, retornando uma resposta HTTP contendo
+// uma página de mensagens.
+// O erro ocorre quando o UUID é inválido, quando o usuário não
+// existe ou quando a paginação é inválida.
+pub async fn get_mentions(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("search"))?; // Retornando o erro que indica UUID inválido.
+
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+
+    data.users
+        .find_user(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This user doesn't exist!".to_string()))?; // Retornando o erro que indica que o usuário não existe.
+
+    let messages = data.messages.mention_timeline(id, &page).await?;
+
+    let (messages, next) = pagination::finish(messages, &page, |m| (m.sended_at, m.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", messages, next)))
+    // Retornando a página de menções.
+}
+
+// Método para obter a linha do tempo do usuário autenticado, com as
+// mensagens dos usuários que ele segue das mais novas para as mais
+// antigas, retornando uma resposta HTTP contendo uma página de mensagens.
+// O erro ocorre quando a paginação é inválida.
+pub async fn get_home_timeline(
+    user: AuthUser,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+    let messages = data.messages.home_timeline(user.id, &page).await?;
+
+    let (messages, next) = pagination::finish(messages, &page, |m| (m.sended_at, m.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", messages, next)))
+    // Retornando a página da linha do tempo.
 }
 
-// Método para adicionar mensagem via autênticação por model::Message
-// retornando uma resposta HTTP contendo a mensagem adicionada.
-// O erro ocorre quando a autênticação falha.
+// Método para adicionar mensagem do usuário autenticado via model::Message,
+// opcionalmente em resposta a outra, retornando uma resposta HTTP contendo
+// a mensagem adicionada.
+// O erro ocorre quando o conteúdo é inválido ou quando a mensagem
+// respondida não existe.
 pub async fn add_message_by_model(
+    user: AuthUser,
     body: Json<model::Message>,
     data: Data<crate::AppState>,
-) -> HttpResponse {
-    // Autenticando o usuário proprietário.
-    match query!(
-        "SELECT * FROM rust_user WHERE id = $1 AND password = $2",
-        body.user_id,
-        body.password, // Considere usar um hash seguro para senhas
-    )
-    .fetch_optional(&data.db)
-    .await
-    {
-        Ok(Some(_)) => {
-            // Inserindo a nova mensagem
-            match query_as!(
-                schema::Message,
-                "INSERT INTO messages (user_id, content) VALUES ($1, $2) RETURNING *",
-                body.user_id,
-                body.content
-            )
-            .fetch_one(&data.db)
-            .await
-            {
-                Ok(message) => {
-                    // Mensagem criada com sucesso, retornando o status 201 Created
-                    HttpResponse::Created().json(response::Success::new("created", message))
-                }
-                Err(err) => {
-                    // Erro inesperado na inserção
-                    HttpResponse::InternalServerError()
-                        .json(response::ServerError::from_sqlx_error(err))
-                }
-            }
-        }
-        Ok(None) => {
-            // Usuário não encontrado ou senha inválida, retornando erro de autenticação
-            HttpResponse::Unauthorized().json(response::ServerError::new(
-                "unauthorized",
-                "This user doesn't exist or the password is invalid!",
-            ))
-        }
-        Err(err) => {
-            // Erro inesperado do sqlx ao tentar buscar o usuário
-            HttpResponse::InternalServerError().json(response::ServerError::from_sqlx_error(err))
-        }
+) -> Result<HttpResponse, AppError> {
+    validate_content(&body.content)?; // Retornando o erro que indica um conteúdo inválido.
+
+    // Verificando se a mensagem respondida existe e não foi removida.
+    if let Some(parent_id) = body.parent_id {
+        data.messages
+            .find_message(parent_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("The replied message doesn't exist!".to_string()))?;
+        // Retornando o erro que indica que a mensagem respondida não existe.
     }
+
+    // Inserindo a nova mensagem com as hashtags e as menções do conteúdo.
+    let entities = entity::extract(&body.content);
+    let message = data
+        .messages
+        .add_message(user.id, &body.content, body.parent_id, &entities)
+        .await?;
+
+    data.metrics.message_posted();
+
+    // Publicando a mensagem para as conexões do feed em tempo real.
+    if let Some(hub) = &data.feed {
+        hub.do_send(feed::Publish::message(&message));
+    }
+
+    // Mensagem criada com sucesso, retornando o status 201 Created
+    Ok(HttpResponse::Created().json(response::Success::new("created", message)))
+}
+
+// Método para editar uma mensagem do usuário autenticado, guardando
+// o conteúdo anterior no histórico e retornando uma resposta HTTP
+// contendo a mensagem editada.
+// O erro ocorre quando o UUID ou o conteúdo são inválidos, quando a
+// mensagem não existe ou quando pertence a outro usuário.
+pub async fn edit_message(
+    user: AuthUser,
+    path: Path<String>,
+    body: Json<model::MessageEdit>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("edit"))?; // Retornando o erro que indica UUID inválido.
+    validate_content(&body.content)?; // Retornando o erro que indica um conteúdo inválido.
+
+    // A revisão e a edição são gravadas juntas, sem perder edições simultâneas.
+    let entities = entity::extract(&body.content);
+    let message = data
+        .messages
+        .edit_message(id, user.id, &body.content, &entities)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response::Success::new("edited", message))) // Retornando a mensagem editada.
+}
+
+// Método para remover uma mensagem do usuário autenticado
+// retornando uma resposta HTTP contendo o resultado da deleção.
+// O histórico e as curtidas da mensagem são removidos junto, e a
+// mensagem com respostas é mantida sem conteúdo na conversa.
+// O erro ocorre quando o UUID é inválido, quando a mensagem não
+// existe ou quando pertence a outro usuário.
+pub async fn delete_message(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("delete"))?; // Retornando o erro que indica UUID inválido.
+
+    // A mensagem é lida antes da remoção para os filtros do feed em tempo real.
+    let message = match &data.feed {
+        Some(_) => data.messages.find_message(id).await?,
+        None => None,
+    };
+
+    // Diferenciando uma mensagem inexistente de uma mensagem de outro usuário.
+    data.messages.delete_message(id, user.id).await?;
+
+    // Publicando a remoção para as conexões do feed em tempo real.
+    if let (Some(hub), Some(message)) = (&data.feed, message) {
+        hub.do_send(feed::Publish::delete(&message));
+    }
+
+    Ok(HttpResponse::Ok().json(response::Success::new(
+        "success",
+        "Message deleted successfully",
+    ))) // Retornando a mensagem que indica o sucesso na deleção.
+}
+
+// Método para obter o histórico de edições de uma mensagem, das
+// revisões mais novas para as mais antigas, retornando uma resposta
+// HTTP contendo uma página de schema::MessageRevision.
+// O erro ocorre quando o UUID é inválido, quando a mensagem não
+// existe ou quando a paginação é inválida.
+pub async fn get_message_history(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("search"))?; // Retornando o erro que indica UUID inválido.
+
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+
+    data.messages
+        .find_message(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This message doesn't exist!".to_string()))?; // Retornando o erro que indica que a mensagem não existe.
+
+    let revisions = data.messages.message_history(id, &page).await?;
+
+    let (revisions, next) = pagination::finish(revisions, &page, |r| (r.replaced_at, r.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", revisions, next)))
+    // Retornando a página do histórico.
+}
+
+// Método para abrir a conexão WebSocket do feed de mensagens novas,
+// já inscrita no feed global ("?global=true") e/ou no feed de um
+// usuário ("?user_id=UUID"). Os tópicos também podem ser alterados
+// pela conexão com {"action": "subscribe" | "unsubscribe", "user_id"?: UUID}.
+pub async fn open_feed(
+    req: HttpRequest,
+    stream: Payload,
+    query: Query<model::Feed>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // O feed pode ser desabilitado pela configuração "feed".
+    let hub = data
+        .feed
+        .clone()
+        .ok_or_else(|| AppError::NotFound("The real-time feed is disabled!".to_string()))?;
+
+    let mut topics = Vec::new();
+    if query.global.unwrap_or(false) {
+        topics.push(feed::Topic::Global);
+    }
+    if let Some(user_id) = query.user_id {
+        topics.push(feed::Topic::User(user_id));
+    }
+
+    match ws::start(feed::Session::new(hub, topics), &req, stream) {
+        Ok(response) => Ok(response), // Retornando a resposta do handshake.
+        Err(err) => Ok(err.error_response()), // Retornando o erro de um handshake inválido.
+    }
+}
+
+// Método para abrir a conexão Server-Sent Events com os eventos das
+// mensagens novas, das curtidas e das remoções, filtrados pelo autor
+// ("?user_id=UUID") e/ou pela hashtag ("?tag=rust"). Com o cabeçalho
+// Last-Event-ID ("?last_event_id=") os eventos perdidos são reenviados,
+// enquanto estiverem na janela de retomada.
+// O erro ocorre quando a hashtag ou o Last-Event-ID são inválidos.
+pub async fn open_stream(
+    req: HttpRequest,
+    query: Query<model::Stream>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // O feed pode ser desabilitado pela configuração "feed".
+    let hub = data
+        .feed
+        .clone()
+        .ok_or_else(|| AppError::NotFound("The real-time feed is disabled!".to_string()))?;
+
+    // Normalizando a hashtag como na extração do conteúdo.
+    let tag = match &query.tag {
+        Some(tag) => Some(entity::normalize_tag(tag).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "The tag must have up to {} letters, digits or underscores, not only digits!",
+                entity::TAG_MAX
+            ))
+        })?), // Retornando o erro que indica uma hashtag inválida.
+        None => None,
+    };
+
+    // O navegador envia o cabeçalho ao reconectar, com o id do último evento recebido.
+    let last_event_id = match req.headers().get("Last-Event-ID") {
+        Some(value) => Some(
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .ok_or_else(|| AppError::BadRequest("Invalid Last-Event-ID!".to_string()))?,
+        ), // Retornando o erro que indica um Last-Event-ID inválido.
+        None => query.last_event_id,
+    };
+
+    let filter = feed::Filter {
+        user_id: query.user_id,
+        tag,
+    };
+    let stream = feed::EventStream::open(&hub, filter, last_event_id)
+        .await
+        .map_err(AppError::internal)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .insert_header(("X-Accel-Buffering", "no")) // Desabilitando o buffer do nginx.
+        .body(stream)) // Retornando a conexão aberta.
 }
 
+// Documento OpenAPI das rotas do escopo "tech".
+#[derive(OpenApi)]
+#[openapi(paths(
+    crate::controller::message::get_all_messages,
+    crate::controller::message::get_one_message,
+    crate::controller::message::get_some_messages,
+    crate::controller::message::search_messages,
+    crate::controller::message::get_messages_from_user,
+    crate::controller::message::get_tag_messages,
+    crate::controller::message::get_mentions,
+    crate::controller::message::get_home_feed,
+    crate::controller::message::post_message,
+    crate::controller::message::open_feed,
+    crate::controller::message::open_stream,
+    crate::controller::message::patch_message,
+    crate::controller::message::delete_message,
+    crate::controller::message::get_message_history,
+    crate::controller::thread::get_thread,
+    crate::controller::like::like_message,
+    crate::controller::like::unlike_message,
+    crate::controller::like::get_message_likes,
+))]
+pub struct Api;
+
 // Método utilizado no escopo principal para obter o escopo "tech".
 pub fn get_scope() -> Scope {
     scope("/tech")
         .service(crate::controller::message::get_all_messages) // Obter todos as mensagens.     GET     ("api/tech/all")
         .service(crate::controller::message::get_one_message) // Obter uma única mensagem.      GET     ("api/tech/one/{id}")
         .service(crate::controller::message::get_some_messages) // Obter algumas mensagens.     GET     ("api/tech/some/{content}")
+        .service(crate::controller::message::search_messages) // Buscar por texto completo.    GET     ("api/tech/search")
         .service(crate::controller::message::get_messages_from_user) // Obter do usuário.       GET     ("api/tech/from/{user_id}")
+        .service(crate::controller::message::get_tag_messages) // Obter por hashtag.          GET     ("api/tech/tag/{tag}")
+        .service(crate::controller::message::get_mentions) // Obter as menções.                GET     ("api/tech/mentions/{user_id}")
+        .service(crate::controller::message::get_home_feed) // Obter a linha do tempo.          GET     ("api/tech/feed")
         .service(crate::controller::message::post_message) // Adicionar uma mensagem.           POST    ("api/tech/manage")
+        .service(crate::controller::message::open_feed) // Feed em tempo real.                  GET     ("api/tech/ws")
+        .service(crate::controller::message::open_stream) // Eventos em tempo real.             GET     ("api/tech/stream")
+        .service(crate::controller::message::patch_message) // Editar uma mensagem.            PATCH   ("api/tech/manage/{id}")
+        .service(crate::controller::message::delete_message) // Deletar uma mensagem.          DELETE  ("api/tech/manage/{id}")
+        .service(crate::controller::message::get_message_history) // Obter o histórico.        GET     ("api/tech/history/{id}")
+        .service(crate::controller::thread::get_thread) // Obter a conversa.                     GET     ("api/tech/thread/{id}")
+        .service(crate::controller::like::like_message) // Curtir uma mensagem.                 POST    ("api/tech/like/{id}")
+        .service(crate::controller::like::unlike_message) // Descurtir uma mensagem.            DELETE  ("api/tech/like/{id}")
+        .service(crate::controller::like::get_message_likes) // Obter quem curtiu.              GET     ("api/tech/likes/{id}")
 }
 
 // Logs da rota.
 pub fn logs() {
-    println!("\n# Loading tech-connect route...");
-    println!("@ROUTE('api/tech/all')                GET     : Return all messages.");
-    println!("@ROUTE('api/tech/one/{{id}}')           GET     : Return one message by id.");
-    println!("@ROUTE('api/tech/some/{{content}}')     GET     : Return some messages by content.");
-    println!("@ROUTE('api/tech/from/{{user_id}}')     GET     : Return some messages by user_id.");
-    println!("@ROUTE('api/tech/from/manage')        POST    : Add message by Model and return.");
+    info!("loading tech-connect route");
+    info!(
+        method = "GET",
+        route = "api/tech/all",
+        "Return all messages."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/one/{id}",
+        "Return one message by id."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/some/{content}",
+        "Return some messages by content."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/search?q=",
+        "Return messages by full-text search."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/from/{user_id}",
+        "Return some messages by user_id."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/tag/{tag}",
+        "Return the messages with a hashtag."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/mentions/{user_id}",
+        "Return the messages that mention a user."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/feed",
+        "Return the messages of the followed users."
+    );
+    info!(
+        method = "POST",
+        route = "api/tech/manage",
+        "Add message of the authenticated user."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/ws",
+        "Open the WebSocket feed of new messages."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/stream",
+        "Open the Server-Sent Events stream of messages, likes and deletions."
+    );
+    info!(
+        method = "PATCH",
+        route = "api/tech/manage/{id}",
+        "Edit a message of the authenticated user."
+    );
+    info!(
+        method = "DELETE",
+        route = "api/tech/manage/{id}",
+        "Delete a message of the authenticated user."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/history/{id}",
+        "Return the edit history of a message."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/thread/{id}",
+        "Return the replies of a message as a tree."
+    );
+    info!(
+        method = "POST",
+        route = "api/tech/like/{id}",
+        "Like a message as the authenticated user."
+    );
+    info!(
+        method = "DELETE",
+        route = "api/tech/like/{id}",
+        "Unlike a message as the authenticated user."
+    );
+    info!(
+        method = "GET",
+        route = "api/tech/likes/{id}",
+        "Return the users who liked a message."
+    );
 }
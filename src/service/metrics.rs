@@ -0,0 +1,45 @@
+use actix_web::{web::Data, HttpResponse};
+use tracing::info;
+use utoipa::OpenApi;
+
+use crate::{
+    error::AppError,
+    openapi::{scope, Scope},
+};
+
+// Método para obter as métricas do servidor no formato de texto
+// do Prometheus, retornando uma resposta HTTP com o conteúdo.
+// O erro ocorre quando as métricas estão desabilitadas.
+pub async fn get_metrics(data: Data<crate::AppState>) -> Result<HttpResponse, AppError> {
+    // As métricas podem ser desabilitadas pela configuração "metrics".
+    if !data.metrics_enabled {
+        return Err(AppError::NotFound("The metrics are disabled!".to_string()));
+    }
+
+    let body = data.metrics.render(&data.db).map_err(AppError::internal)?; // Retornando o erro ao gerar as métricas.
+
+    Ok(HttpResponse::Ok()
+        .content_type(prometheus::TEXT_FORMAT)
+        .body(body)) // Retornando as métricas.
+}
+
+// Documento OpenAPI da rota de métricas.
+#[derive(OpenApi)]
+#[openapi(paths(crate::controller::metrics::get_metrics))]
+pub struct Api;
+
+// Método utilizado no escopo principal para obter o escopo "metrics",
+// fora da api, no caminho esperado pelo Prometheus.
+pub fn get_scope() -> Scope {
+    scope("/metrics").service(crate::controller::metrics::get_metrics) // Métricas. GET ("metrics")
+}
+
+// Logs da rota.
+pub fn logs() {
+    info!("loading metrics route");
+    info!(
+        method = "GET",
+        route = "metrics",
+        "Return the Prometheus metrics."
+    );
+}
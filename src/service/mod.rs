@@ -1,14 +1,44 @@
+pub mod conversation; // Exportando a lógica das rotas de mensagens diretas.
+pub mod docs; // Exportando a lógica das rotas de documentação.
+pub mod follow; // Exportando a lógica das rotas de follows.
+pub mod health; // Exportando a lógica das rotas de saúde.
+pub mod like; // Exportando a lógica das rotas de curtidas.
 pub mod message; // Exportando a lógica das rotas sociais.
+pub mod metrics; // Exportando a lógica da rota de métricas.
+pub mod notification; // Exportando a lógica das rotas de notificações.
+pub mod thread; // Exportando a lógica da rota de conversas.
 pub mod user; // Exportando a lógica das rotas de usuário.
 
-use actix_web::web::{scope, ServiceConfig};
+use actix_web::web::ServiceConfig;
+use tracing::debug;
+
+use crate::openapi::{scope, Scope};
+
+// Método para obter os escopos das rotas. Cada controlador registrado
+// precisa estar documentado com #[utoipa::path].
+pub fn scopes() -> Vec<Scope> {
+    vec![
+        // Escope principal para a api.
+        scope("/api")
+            .nest(user::get_scope())
+            .nest(message::get_scope())
+            .nest(conversation::get_scope())
+            .service(crate::controller::docs::get_openapi) // Documento OpenAPI.     GET     ("api/openapi.json")
+            .service(crate::controller::docs::get_docs) //   Swagger UI.            GET     ("api/docs")
+            .service(crate::controller::docs::get_swagger_ui_asset), // Arquivos do Swagger UI.  GET     ("api/swagger-ui/{file}")
+        // Rotas de saúde fora da api, consultadas pelo orquestrador.
+        health::get_scope(),
+        // Rota de métricas fora da api, no caminho esperado pelo Prometheus.
+        metrics::get_scope(),
+    ]
+}
 
 // Função principal para definir os escopos das rotas.
 pub fn config(conf: &mut ServiceConfig) {
-    // Escope principal para a api.
-    conf.service(
-        scope("/api")
-            .service(user::get_scope())
-            .service(message::get_scope()),
-    );
+    for scope in scopes() {
+        for route in scope.routes() {
+            debug!(method = route.method, route = %route.path, "registering route");
+        }
+        conf.service(scope);
+    }
 }
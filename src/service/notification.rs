@@ -0,0 +1,102 @@
+use actix_web::{
+    web::{Data, Json, Path, Query},
+    HttpResponse,
+};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthUser, error::AppError, model, pagination, repository::NotificationChanges, response,
+};
+
+// Método para obter as notificações do usuário autenticado agrupadas
+// pelo tipo e pela mensagem, dos grupos mais recentes para os mais
+// antigos, retornando uma resposta HTTP contendo uma página de
+// response::Notification. Com "unread" apenas as não lidas são consideradas.
+// O erro ocorre quando a paginação é inválida.
+pub async fn get_notifications(
+    user: AuthUser,
+    filter: Query<model::Notifications>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+    let unread = filter.unread.unwrap_or(false);
+
+    let notifications = data.users.notifications(user.id, unread, &page).await?;
+
+    let (notifications, next) = pagination::finish(notifications, &page, |n| (n.created_at, n.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", notifications, next)))
+    // Retornando a página de notificações.
+}
+
+// Método para marcar como lido um grupo de notificações até a
+// notificação informada, retornando uma resposta HTTP contendo a
+// quantidade de notificações marcadas.
+// O erro ocorre quando o UUID é inválido ou quando a notificação
+// não existe ou é de outro usuário.
+pub async fn read_notification(
+    user: AuthUser,
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("read"))?; // Retornando o erro que indica UUID inválido.
+
+    let marked = data
+        .users
+        .read_notification(user.id, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This notification doesn't exist!".to_string()))?; // Retornando o erro que indica que a notificação não existe.
+
+    Ok(HttpResponse::Ok().json(response::Success::new("read", marked))) // Retornando a quantidade marcada.
+}
+
+// Método para marcar como lidas todas as notificações do usuário
+// autenticado, retornando uma resposta HTTP contendo a quantidade
+// de notificações marcadas.
+pub async fn read_all_notifications(
+    user: AuthUser,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    let marked = data.users.read_all_notifications(user.id).await?;
+
+    Ok(HttpResponse::Ok().json(response::Success::new("read", marked))) // Retornando a quantidade marcada.
+}
+
+// Método para obter os tipos de notificação registrados para o
+// usuário autenticado, retornando uma resposta HTTP contendo
+// response::NotificationPreferences.
+pub async fn get_preferences(
+    user: AuthUser,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    let preferences = data.users.notification_preferences(user.id).await?;
+
+    Ok(HttpResponse::Ok().json(response::Success::new("success", preferences))) // Retornando as preferências.
+}
+
+// Método para alterar os tipos de notificação registrados para o
+// usuário autenticado, retornando uma resposta HTTP contendo
+// response::NotificationPreferences atualizado. Os tipos desativados
+// deixam de ser registrados, mantendo as notificações anteriores.
+// O erro ocorre quando o usuário não existe mais.
+pub async fn update_preferences(
+    user: AuthUser,
+    body: Json<model::NotificationPreferences>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    let changes = NotificationChanges {
+        likes: body.likes,
+        replies: body.replies,
+        mentions: body.mentions,
+        follows: body.follows,
+    };
+    let preferences = data
+        .users
+        .update_notification_preferences(user.id, &changes)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response::Success::new("updated", preferences))) // Retornando as preferências atualizadas.
+}
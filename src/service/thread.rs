@@ -0,0 +1,96 @@
+use actix_web::{
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{error::AppError, model, pagination, response, schema};
+
+// Níveis de respostas quando "depth" não é informado.
+pub const DEFAULT_DEPTH: i32 = 3;
+// Quantidade máxima de níveis de respostas.
+pub const MAX_DEPTH: i32 = 10;
+// Respostas mostradas de cada resposta abaixo da página. As demais
+// são obtidas pela conversa da própria resposta.
+pub const NESTED_REPLIES: i64 = 5;
+
+// Método para montar a árvore da mensagem com as respostas
+// agrupadas pela mensagem respondida.
+fn build(
+    message: schema::Message,
+    replies: &mut HashMap<Uuid, Vec<schema::Message>>,
+) -> response::Thread {
+    let children = replies.remove(&message.id).unwrap_or_default();
+
+    response::Thread {
+        message,
+        replies: children
+            .into_iter()
+            .map(|reply| build(reply, replies))
+            .collect(),
+    }
+}
+
+// Método para obter a conversa de uma mensagem retornando uma
+// resposta HTTP contendo a mensagem com as respostas em árvore.
+// A página se refere às respostas diretas, das mais antigas para as
+// mais novas. Mensagens removidas que ainda têm respostas aparecem
+// sem conteúdo, com deleted_at.
+// O erro ocorre quando o UUID é inválido, quando a mensagem não
+// existe ou quando a profundidade ou a paginação são inválidas.
+pub async fn get_thread(
+    path: Path<String>,
+    thread: Query<model::Thread>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Passando a variável de path para UUID.
+    let id = Uuid::parse_str(&path.into_inner()).map_err(|_| AppError::invalid_uuid("search"))?; // Retornando o erro que indica UUID inválido.
+
+    let depth = thread.depth.unwrap_or(DEFAULT_DEPTH);
+    if !(1..=MAX_DEPTH).contains(&depth) {
+        return Err(AppError::BadRequest(format!(
+            "The depth must be between 1 and {}!",
+            MAX_DEPTH
+        ))); // Retornando o erro que indica uma profundidade inválida.
+    }
+
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(DateTime<Utc>, Uuid)>(&query)?;
+
+    let (root, descendants) = data
+        .messages
+        .thread(id, depth, NESTED_REPLIES, &page)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This message doesn't exist!".to_string()))?; // Retornando o erro que indica que a mensagem não existe.
+
+    // Separando as respostas diretas, paginadas, das respostas abaixo delas.
+    let (mut direct, nested): (Vec<_>, Vec<_>) = descendants
+        .into_iter()
+        .partition(|m| m.parent_id == Some(root.id));
+    direct.sort_by_key(|m| (m.sended_at, m.id));
+
+    let mut replies: HashMap<Uuid, Vec<schema::Message>> = HashMap::new();
+    for reply in nested {
+        if let Some(parent_id) = reply.parent_id {
+            replies.entry(parent_id).or_default().push(reply);
+        }
+    }
+    for children in replies.values_mut() {
+        children.sort_by_key(|m| (m.sended_at, m.id));
+    }
+
+    let (direct, next) = pagination::finish(direct, &page, |m| (m.sended_at, m.id));
+    let thread = response::Thread {
+        message: root,
+        replies: direct
+            .into_iter()
+            .map(|reply| build(reply, &mut replies))
+            .collect(),
+    };
+
+    Ok(HttpResponse::Ok().json(response::Success::page("success", thread, next)))
+    // Retornando a conversa.
+}
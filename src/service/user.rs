@@ -1,171 +1,538 @@
 use actix_web::{
-    web::{scope, Data, Json, Path},
-    HttpResponse, Scope,
+    web::{Data, Json, Path, Query},
+    HttpResponse,
 };
-use sqlx::{query, query_as};
+use tracing::{info, warn};
+use utoipa::OpenApi;
 use uuid::Uuid;
 
-use crate::{model, response, schema};
+use crate::{
+    auth::AuthUser,
+    error::AppError,
+    model,
+    openapi::{scope, Scope},
+    pagination,
+    password::Verification,
+    repository::ProfileChanges,
+    response, schema, token,
+};
 
-// Método para obter uma resposta HTTP contendo a lista
-// de todos os itens de rust_user.
-pub async fn get_all_users(data: Data<crate::AppState>) -> HttpResponse {
-    match query_as!(schema::User, "SELECT * FROM rust_user")
-        .fetch_all(&data.db)
-        .await
-    {
-        Ok(users) => HttpResponse::Ok().json(response::Success::new("success", users)), // Retornando a lista de sucesso.
-        Err(err) => {
-            HttpResponse::InternalServerError().json(response::ServerError::from_sqlx_error(err))
-        } // Retornando o erro inesperado pelo sqlx,
+// Tamanhos máximos das colunas do perfil em rust_user.
+pub const NAME_MAX: usize = 255;
+pub const DISPLAY_NAME_MAX: usize = 64;
+pub const BIO_MAX: usize = 280;
+pub const AVATAR_URL_MAX: usize = 2048;
+
+// Método para autenticar um usuário a partir do nome e da senha,
+// retornando o usuário quando a senha confere.
+// Senhas legadas em texto puro ou com custos antigos são
+// substituídas por um novo hash após a autenticação.
+pub async fn authenticate(
+    data: &crate::AppState,
+    name: &str,
+    password: &str,
+) -> Result<Option<schema::User>, AppError> {
+    let user = match data.users.find_credentials(name).await? {
+        Some(user) => user,
+        None => {
+            // Verificando o hash fictício para não revelar pelo tempo
+            // de resposta quais nomes de usuário existem.
+            data.hasher.verify(password, data.hasher.dummy()).await;
+            return Ok(None); // Usuário inexistente.
+        }
+    };
+
+    match data.hasher.verify(password, &user.password).await {
+        Verification::Valid => Ok(Some(user)),
+        Verification::Outdated => {
+            // Atualizando o hash armazenado sem impedir a autenticação em caso de falha.
+            match data.hasher.hash(password).await {
+                Ok(hash) => {
+                    if let Err(err) = data.users.update_password(user.id, &hash).await {
+                        warn!(user_id = %user.id, "failed to store the rehashed password: {}", err);
+                    }
+                }
+                Err(err) => warn!(user_id = %user.id, "failed to rehash the password: {}", err),
+            }
+            Ok(Some(user))
+        }
+        Verification::Invalid => Ok(None),
+    }
+}
+
+// Método para emitir um novo token de acesso e um novo token
+// de atualização para o usuário, armazenando o de atualização.
+async fn issue_session(
+    data: &crate::AppState,
+    user_id: Uuid,
+) -> Result<response::Session, AppError> {
+    let access_token = data.tokens.access(user_id).map_err(AppError::internal)?; // Retornando o erro ao assinar o token.
+
+    let refresh = data.tokens.refresh();
+
+    data.users
+        .add_refresh_token(user_id, &refresh.hash, refresh.expires_at)
+        .await?;
+
+    Ok(response::Session {
+        token_type: "Bearer".to_string(),
+        access_token,
+        expires_in: data.tokens.access_ttl(),
+        refresh_token: refresh.token,
+        refresh_expires_in: data.tokens.refresh_ttl(),
+    })
+}
+
+// Método para autenticar via nome e senha retornando uma
+// resposta HTTP contendo os tokens da nova sessão.
+// O erro ocorre quando a autenticação falha.
+pub async fn login(
+    body: Json<model::Login>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    let user = authenticate(&data, &body.name, &body.password)
+        .await?
+        .ok_or_else(|| {
+            AppError::InvalidCredentials(
+                "This user doesn't exist or the password is invalid!".to_string(),
+            )
+        })?; // Retornando o erro de autenticação.
+
+    let session = issue_session(&data, user.id).await?;
+    Ok(HttpResponse::Ok().json(response::Success::new("authenticated", session)))
+    // Retornando os tokens da sessão.
+}
+
+// Método para trocar um token de atualização por uma nova sessão,
+// revogando o token utilizado.
+// O erro ocorre quando o token é inválido, expirado ou já utilizado;
+// reutilizar um token revogado revoga todas as sessões do usuário.
+pub async fn refresh(
+    body: Json<model::Refresh>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    let hash = token::hash_refresh(&body.refresh_token);
+
+    // Consumindo o token de forma atômica para evitar uso duplicado.
+    match data.users.consume_refresh_token(&hash).await? {
+        Some(user_id) => {
+            let session = issue_session(&data, user_id).await?;
+            Ok(HttpResponse::Ok().json(response::Success::new("refreshed", session)))
+            // Retornando os tokens da nova sessão.
+        }
+        None => {
+            // Revogando todas as sessões caso um token já revogado seja reapresentado.
+            data.users.revoke_reused_refresh_token(&hash).await?;
+
+            Err(AppError::InvalidCredentials(
+                "Invalid, expired or revoked refresh token!".to_string(),
+            )) // Retornando o erro de autenticação.
+        }
+    }
+}
+
+// Método para encerrar a sessão do usuário autenticado
+// revogando o token de atualização informado.
+// O erro ocorre quando o token não pertence ao usuário ou já foi revogado.
+pub async fn logout(
+    user: AuthUser,
+    body: Json<model::Refresh>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    let revoked = data
+        .users
+        .revoke_refresh_token(&token::hash_refresh(&body.refresh_token), user.id)
+        .await?;
+
+    // Verificando se alguma sessão foi revogada.
+    if !revoked {
+        return Err(AppError::NotFound(
+            "No active session found for this refresh token".to_string(),
+        )); // Retornando o erro que indica que a sessão não existe.
     }
+
+    Ok(HttpResponse::Ok().json(response::Success::new("success", "Logged out successfully")))
+}
+
+// Método para obter uma resposta HTTP contendo uma página
+// dos itens de rust_user ordenados pelo nome.
+// O erro ocorre quando a paginação é inválida.
+pub async fn get_all_users(
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(String, Uuid)>(&query)?;
+    let users = data.users.all_users(&page).await?;
+
+    let (users, next) = pagination::finish(users, &page, |u| (u.name.clone(), u.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", users, next)))
+    // Retornando a página de sucesso.
 }
 
 // Método para pesquisar um usuário a partir de seu id
 // e retornando uma resposta HTTP contendo o usuário.
 // O erro acontece se não for encontrado ou se o uuid for inválido.
-pub async fn find_user_by_id(path: Path<String>, data: Data<crate::AppState>) -> HttpResponse {
+pub async fn find_user_by_id(
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
     // Transferindo a variavel de url em Uuid para a pesquisa.
-    match Uuid::parse_str(&path) {
-        Ok(uuid) => match query_as!(schema::User, "SELECT * FROM rust_user WHERE id = $1", uuid)
-            .fetch_one(&data.db)
-            .await
-        {
-            Ok(user) => HttpResponse::Ok().json(response::Success::new("found", user)), // Retornando o usuário que corresponde ao UUID.
-            Err(_) => HttpResponse::NotFound().json(response::ServerError::new(
-                "not found",
-                "This UUID doesn't exist in the database!",
-            )), // Retornando o erro no qual indica que o usuário correspondente não foi encontrado.
-        },
-        Err(_) => HttpResponse::BadRequest().json(response::ServerError::new(
-            "bad request",
-            "Invalid UUID format, unable to search!",
-        )), // Retornando o erro que indca UUID inválido.
-    }
+    let uuid = Uuid::parse_str(&path).map_err(|_| AppError::invalid_uuid("search"))?; // Retornando o erro que indca UUID inválido.
+
+    let user = data.users.find_user(uuid).await?.ok_or_else(|| {
+        AppError::NotFound("This UUID doesn't exist in the database!".to_string())
+    })?; // Retornando o erro no qual indica que o usuário correspondente não foi encontrado.
+
+    Ok(HttpResponse::Ok().json(response::Success::new("found", user))) // Retornando o usuário que corresponde ao UUID.
+}
+
+// Método para obter o perfil de um usuário a partir de seu id,
+// retornando uma resposta HTTP contendo o response::Profile com as
+// quantidades de mensagens e curtidas.
+// O erro acontece se não for encontrado ou se o uuid for inválido.
+pub async fn get_profile(
+    path: Path<String>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    let uuid = Uuid::parse_str(&path).map_err(|_| AppError::invalid_uuid("search"))?; // Retornando o erro que indca UUID inválido.
+
+    let profile = data
+        .users
+        .find_profile(uuid)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This user doesn't exist!".to_string()))?; // Retornando o erro que indica que o usuário não existe.
+
+    Ok(HttpResponse::Ok().json(response::Success::new("found", profile))) // Retornando o perfil.
 }
 
 // Método para pesquisa por um ou mais usuários cujo nome se assemelha
 // a variavel determinada na url e retornando uma resposta HTTP contendo
-// uma lista dos usuários encontrados.
-// O erro acontece caso não seja encontrado nenhum usuário.
-pub async fn find_users_by_name(path: Path<String>, data: Data<crate::AppState>) -> HttpResponse {
-    let search_pattern = format!("%{}%", path.to_string()); // Formatando o pattern para a pesquisa.
-
-    match query_as!(
-        schema::User,
-        "SELECT * FROM rust_user WHERE name LIKE $1",
-        search_pattern
-    )
-    .fetch_all(&data.db)
-    .await
-    {
-        Ok(users) => {
-            // Verificando se foi encontrado algum usuário.
-            if !users.is_empty() {
-                HttpResponse::Ok().json(response::Success::new("success", users))
-            // Retornando os usuaŕios encontrados.
-            } else {
-                HttpResponse::NotFound().json(response::ServerError::new(
-                    "not found",
-                    "Have no users like this name!",
-                )) // Retornando o erro no qual indica que nenhum semelhante ao nome foi encontrado.
-            }
-        }
-        Err(_) => HttpResponse::InternalServerError().json(response::ServerError::new(
-            "internal server error",
-            "Error while fetching users from the database.",
-        )), // Retornando o erro inesperado pelo sqlx.
+// uma página dos usuários encontrados.
+// O erro acontece caso não seja encontrado nenhum usuário
+// ou quando a paginação é inválida.
+pub async fn find_users_by_name(
+    path: Path<String>,
+    query: Query<model::Page>,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    // Validando o limite e o cursor da página.
+    let page = pagination::parse::<(String, Uuid)>(&query)?;
+    let users = data.users.find_users_by_name(&path, &page).await?;
+
+    // Verificando se foi encontrado algum usuário.
+    if users.is_empty() && page.after.is_none() {
+        return Err(AppError::NotFound(
+            "Have no users like this name!".to_string(),
+        )); // Retornando o erro no qual indica que nenhum semelhante ao nome foi encontrado.
     }
+
+    let (users, next) = pagination::finish(users, &page, |u| (u.name.clone(), u.id));
+    Ok(HttpResponse::Ok().json(response::Success::page("success", users, next)))
+    // Retornando os usuaŕios encontrados.
 }
 
 // Método para adicionar um usuário em rust_user e retornando uma
 // resposta HTTP contendo o usuário adicionado.
-// O error ocorre quando o nome está vazio ou a
-// senha contem menos que 8 caracteres.
+// O error ocorre quando o nome está vazio, quando a senha
+// contem menos que 8 caracteres ou quando o nome já existe.
 pub async fn add_user_by_model(
     body: Json<model::User>,
     data: Data<crate::AppState>,
-) -> HttpResponse {
+) -> Result<HttpResponse, AppError> {
     // Validação do tamanho do nome e da senha.
     if body.name.is_empty() || body.password.len() < 8 {
-        return HttpResponse::NotAcceptable().json(response::ServerError::new(
-            "not acceptable",
-            "The name cannot be empty and the password should be bigger than 7 characters!",
+        return Err(AppError::NotAcceptable(
+            "The name cannot be empty and the password should be bigger than 7 characters!"
+                .to_string(),
         )); // Retornando o erro indicando que o usuário requisitado não é aceitável.
     }
 
-    match query_as!(
-        schema::User,
-        "INSERT INTO rust_user (name, password) VALUES ($1, $2) RETURNING *",
-        body.name,
-        body.password
-    )
-    .fetch_one(&data.db)
-    .await
-    {
-        Ok(user) => HttpResponse::Created().json(response::Success::new("created", user)), // Retornando o usuario que foi criado.
-        Err(err) => {
-            HttpResponse::InternalServerError().json(response::ServerError::from_sqlx_error(err))
-        } // Retornando o erro inesperado pelo sqlx.
+    // Gerando o hash que será armazenado no lugar da senha.
+    let hash = data
+        .hasher
+        .hash(&body.password)
+        .await
+        .map_err(AppError::internal)?; // Retornando o erro ao gerar o hash.
+
+    // Um nome repetido resulta em 409.
+    let user = data.users.add_user(&body.name, &hash).await?;
+
+    data.metrics.user_created();
+    Ok(HttpResponse::Created().json(response::Success::new("created", user))) // Retornando o usuario que foi criado.
+}
+
+// Método para validar os campos alterados do perfil.
+// O erro indica o primeiro campo inválido.
+fn validate_profile(body: &model::UserUpdate) -> Result<(), AppError> {
+    let length = |value: &Option<String>| value.as_deref().map(|v| v.chars().count());
+    let invalid = |message: &str| Err(AppError::NotAcceptable(message.to_string()));
+
+    if length(&body.name).is_some_and(|n| n == 0 || n > NAME_MAX) {
+        return invalid("The name must have between 1 and 255 characters!");
+    }
+    if length(&body.display_name).is_some_and(|n| n > DISPLAY_NAME_MAX) {
+        return invalid("The display name must have at most 64 characters!");
+    }
+    if length(&body.bio).is_some_and(|n| n > BIO_MAX) {
+        return invalid("The bio must have at most 280 characters!");
     }
+
+    // O avatar precisa ser um endereço http(s), ou vazio para removê-lo.
+    if let Some(url) = body.avatar_url.as_deref().filter(|url| !url.is_empty()) {
+        let scheme = url.starts_with("https://") || url.starts_with("http://");
+        if !scheme || url.chars().any(char::is_whitespace) || url.len() > AVATAR_URL_MAX {
+            return invalid(
+                "The avatar must be an http or https URL with at most 2048 characters!",
+            );
+        }
+    }
+
+    if body.password.as_deref().is_some_and(|p| p.len() < 8) {
+        return invalid("The password should be bigger than 7 characters!");
+    }
+
+    Ok(())
 }
 
-// Método para remover o usuário a partir de autenticação
-// via id, nome e senha retornando uma resposta HTTP contendo
-// o resultado da pesquisa.
-// O erro é retornado quando nenhuma linha é afetada.
-pub async fn delete_user_by_schema(
-    body: Json<schema::User>,
+// Método para alterar o perfil do usuário autenticado, retornando uma
+// resposta HTTP contendo o response::Profile atualizado.
+// O nome segue a mesma regra de unicidade do cadastro, e a troca de
+// senha exige a senha atual e encerra todas as sessões do usuário.
+// O erro ocorre quando algum campo é inválido, quando o nome já existe
+// ou quando a senha atual está ausente ou não confere.
+pub async fn update_user(
+    user: AuthUser,
+    body: Json<model::UserUpdate>,
     data: Data<crate::AppState>,
-) -> HttpResponse {
-    match query!(
-        "DELETE FROM rust_user WHERE id = $1 AND name = $2 AND password = $3",
-        body.id,
-        body.name,
-        body.password
-    )
-    .execute(&data.db)
-    .await
-    {
-        Ok(result) => {
-            // Verificando se alguma linha foi afetada.
-            if result.rows_affected() > 0 {
-                // Retornando a mensagem que indica sobr eo sucesso na deleção.
-                HttpResponse::Ok().json(response::Success::new(
-                    "success",
-                    "User deleted successfully",
-                ))
-            } else {
-                HttpResponse::NotFound().json(response::ServerError::new(
-                    "not found",
-                    "No user found with the given credentials",
-                )) // Retornando erro que indica a não correspondencia na deleção.
+) -> Result<HttpResponse, AppError> {
+    validate_profile(&body)?;
+
+    let password = match &body.password {
+        Some(password) => {
+            let current = body.current_password.as_deref().ok_or_else(|| {
+                AppError::BadRequest(
+                    "The current password is required to change the password!".to_string(),
+                )
+            })?; // Retornando o erro que indica a senha atual ausente.
+
+            let credentials = data
+                .users
+                .find_credentials_by_id(user.id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("This user doesn't exist anymore".to_string()))?;
+
+            // A senha incorreta conta como uma falha de credenciais no limite de
+            // requisições, impedindo que a senha seja adivinhada por esta rota.
+            if data.hasher.verify(current, &credentials.password).await == Verification::Invalid {
+                return Err(AppError::InvalidCredentials(
+                    "The current password is invalid!".to_string(),
+                )); // Retornando o erro que indica a senha atual incorreta.
             }
+
+            Some(
+                data.hasher
+                    .hash(password)
+                    .await
+                    .map_err(AppError::internal)?, // Retornando o erro ao gerar o hash.
+            )
         }
-        Err(err) => {
-            HttpResponse::InternalServerError().json(response::ServerError::from_sqlx_error(err))
-        } // Retornando o erro inesperado pelo sqlx.
+        None => None,
+    };
+
+    let changes = ProfileChanges {
+        name: body.name.as_deref(),
+        display_name: body.display_name.as_deref(),
+        bio: body.bio.as_deref(),
+        avatar_url: body.avatar_url.as_deref(),
+        password: password.as_deref(),
+    };
+
+    // Um nome repetido resulta em 409.
+    if !data.users.update_profile(user.id, &changes).await? {
+        return Err(AppError::NotFound(
+            "This user doesn't exist anymore".to_string(),
+        )); // Retornando o erro que indica que o usuário foi removido.
+    }
+
+    // Os tokens de acesso já emitidos continuam válidos até expirarem.
+    if password.is_some() {
+        data.users.revoke_all_refresh_tokens(user.id).await?;
     }
+
+    let profile = data
+        .users
+        .find_profile(user.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("This user doesn't exist anymore".to_string()))?;
+
+    Ok(HttpResponse::Ok().json(response::Success::new("updated", profile))) // Retornando o perfil atualizado.
 }
 
+// Método para remover o usuário autenticado retornando
+// uma resposta HTTP contendo o resultado da deleção.
+// O erro é retornado quando nenhuma linha é afetada.
+pub async fn delete_user(
+    user: AuthUser,
+    data: Data<crate::AppState>,
+) -> Result<HttpResponse, AppError> {
+    let deleted = data.users.delete_user(user.id).await?;
+
+    // Verificando se o usuário foi removido.
+    if !deleted {
+        return Err(AppError::NotFound(
+            "This user doesn't exist anymore".to_string(),
+        )); // Retornando erro que indica a não correspondencia na deleção.
+    }
+
+    // Retornando a mensagem que indica sobr eo sucesso na deleção.
+    Ok(HttpResponse::Ok().json(response::Success::new(
+        "success",
+        "User deleted successfully",
+    )))
+}
+
+// Documento OpenAPI das rotas do escopo "user".
+#[derive(OpenApi)]
+#[openapi(paths(
+    crate::controller::user::get_all_users,
+    crate::controller::user::get_some_users,
+    crate::controller::user::get_one_user,
+    crate::controller::user::post_one_user,
+    crate::controller::user::patch_one_user,
+    crate::controller::user::delete_one_user,
+    crate::controller::user::get_profile,
+    crate::controller::user::login,
+    crate::controller::user::logout,
+    crate::controller::user::refresh,
+    crate::controller::notification::get_notifications,
+    crate::controller::notification::read_notification,
+    crate::controller::notification::read_all_notifications,
+    crate::controller::notification::get_notification_preferences,
+    crate::controller::notification::patch_notification_preferences,
+    crate::controller::follow::follow_user,
+    crate::controller::follow::unfollow_user,
+    crate::controller::follow::get_followers,
+    crate::controller::follow::get_following,
+))]
+pub struct Api;
+
 // Método utilizado no escopo principal para obter o escopo "user".
 pub fn get_scope() -> Scope {
     scope("/user")
-        .service(crate::controller::user::get_all_users) //   Obter todos os usuários.  GET     ("api/user/all")
-        .service(crate::controller::user::get_some_users) //  Pesquisa alguns usuários. GET     ("api/user/some/{name}")
-        .service(crate::controller::user::get_one_user) //    Pesquisa um usuário.      GET     ("api/user/one/{id}")
-        .service(crate::controller::user::post_one_user) //   Adiciona um usuário.      POST    ("api/user/manage")
-        .service(crate::controller::user::delete_one_user) // Deleta um usuário.        DELETE  ("api/user/manage")
+        .service(crate::controller::user::get_all_users) //   Obter todos os usuários.             GET     ("api/user/all")
+        .service(crate::controller::user::get_some_users) //  Pesquisa alguns usuários.            GET     ("api/user/some/{name}")
+        .service(crate::controller::user::get_one_user) //    Pesquisa um usuário.                 GET     ("api/user/one/{id}")
+        .service(crate::controller::user::post_one_user) //   Adiciona um usuário.                 POST    ("api/user/manage")
+        .service(crate::controller::user::patch_one_user) //  Altera o perfil.                     PATCH   ("api/user/manage")
+        .service(crate::controller::user::delete_one_user) // Deleta um usuário.                   DELETE  ("api/user/manage")
+        .service(crate::controller::user::get_profile) //     Obtém um perfil.                     GET     ("api/user/profile/{id}")
+        .service(crate::controller::user::login) //           Inicia uma sessão.                   POST    ("api/user/login")
+        .service(crate::controller::user::logout) //          Encerra uma sessão.                  POST    ("api/user/logout")
+        .service(crate::controller::user::refresh) //         Renova uma sessão.                   POST    ("api/user/refresh")
+        .service(crate::controller::notification::get_notifications) // Obtém as notificações.     GET     ("api/user/notifications")
+        .service(crate::controller::notification::read_notification) // Marca um grupo como lido.  POST    ("api/user/notifications/read/{id}")
+        .service(crate::controller::notification::read_all_notifications) // Marca todas.          POST    ("api/user/notifications/read")
+        .service(crate::controller::notification::get_notification_preferences) // Obtém os tipos. GET     ("api/user/notifications/preferences")
+        .service(crate::controller::notification::patch_notification_preferences) // Altera tipos. PATCH   ("api/user/notifications/preferences")
+        .service(crate::controller::follow::follow_user) //   Segue um usuário.                    POST    ("api/user/follow/{id}")
+        .service(crate::controller::follow::unfollow_user) // Deixa de seguir.                     DELETE  ("api/user/follow/{id}")
+        .service(crate::controller::follow::get_followers) // Obtém os seguidores.                 GET     ("api/user/followers/{id}")
+        .service(crate::controller::follow::get_following) // Obtém os seguidos.                   GET     ("api/user/following/{id}")
 }
 
 // Logs da rota.
 pub fn logs() {
-    println!("\n# Loading user route...");
-    println!("@ROUTE('api/user/all')                GET     : Return all users. ");
-    println!("@ROUTE('api/user/some/{{name}}')        GET     : Return some users by name.");
-    println!("@ROUTE('api/user/one/{{id}}')           GET     : Return one user by id.");
-    println!("@ROUTE('api/user/manage')             POST    : Add one user and return.");
-    println!(
-        "@ROUTE('api/user/manage')             DELETE  : Delete one user by id, name and password."
+    info!("loading user route");
+    info!(method = "GET", route = "api/user/all", "Return all users.");
+    info!(
+        method = "GET",
+        route = "api/user/some/{name}",
+        "Return some users by name."
+    );
+    info!(
+        method = "GET",
+        route = "api/user/one/{id}",
+        "Return one user by id."
+    );
+    info!(
+        method = "POST",
+        route = "api/user/manage",
+        "Add one user and return."
+    );
+    info!(
+        method = "PATCH",
+        route = "api/user/manage",
+        "Update the profile of the authenticated user."
+    );
+    info!(
+        method = "DELETE",
+        route = "api/user/manage",
+        "Delete the authenticated user."
+    );
+    info!(
+        method = "GET",
+        route = "api/user/profile/{id}",
+        "Return the profile of one user by id."
+    );
+    info!(
+        method = "POST",
+        route = "api/user/login",
+        "Return tokens for name and password."
+    );
+    info!(
+        method = "POST",
+        route = "api/user/logout",
+        "Revoke one refresh token."
+    );
+    info!(
+        method = "POST",
+        route = "api/user/refresh",
+        "Exchange a refresh token for new tokens."
+    );
+    info!(
+        method = "POST",
+        route = "api/user/follow/{id}",
+        "Follow a user as the authenticated user."
+    );
+    info!(
+        method = "DELETE",
+        route = "api/user/follow/{id}",
+        "Unfollow a user as the authenticated user."
+    );
+    info!(
+        method = "GET",
+        route = "api/user/followers/{id}",
+        "Return the followers of a user."
+    );
+    info!(
+        method = "GET",
+        route = "api/user/following/{id}",
+        "Return the users followed by a user."
+    );
+    info!(
+        method = "GET",
+        route = "api/user/notifications",
+        "Return the notifications of the authenticated user."
+    );
+    info!(
+        method = "POST",
+        route = "api/user/notifications/read/{id}",
+        "Mark a group of notifications as read."
+    );
+    info!(
+        method = "POST",
+        route = "api/user/notifications/read",
+        "Mark every notification as read."
+    );
+    info!(
+        method = "GET",
+        route = "api/user/notifications/preferences",
+        "Return the notification preferences."
+    );
+    info!(
+        method = "PATCH",
+        route = "api/user/notifications/preferences",
+        "Update the notification preferences."
     );
 }
@@ -0,0 +1,48 @@
+use std::{
+    io::{stdout, IsTerminal},
+    str::FromStr,
+};
+use tracing_subscriber::EnvFilter;
+
+// Formato dos logs emitidos pelo servidor.
+#[derive(Clone, Copy)]
+pub enum Format {
+    Human, // Linhas legíveis, com os campos dos spans entre chaves.
+    Json,  // Um objeto JSON por linha, para agregadores de logs.
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            _ => Err(()),
+        }
+    }
+}
+
+// Método para validar o filtro de níveis ("info", "info,sqlx=warn", ...).
+pub fn valid_level(level: &str) -> bool {
+    EnvFilter::try_new(level).is_ok()
+}
+
+// Método para iniciar o subscriber global do tracing. Os logs do crate
+// "log", usados por dependências como o sqlx, também são redirecionados.
+pub fn init(format: Format, level: &str) {
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::new(level))
+        .with_target(false)
+        .with_ansi(stdout().is_terminal()); // Cores apenas em terminais.
+
+    match format {
+        Format::Human => builder.init(),
+        Format::Json => builder
+            .json()
+            .flatten_event(true) // Campos do evento no nível principal do objeto.
+            .with_current_span(true) // Campos da requisição, como o request_id.
+            .with_span_list(false)
+            .init(),
+    }
+}
@@ -0,0 +1,151 @@
+// Testes do carregamento da configuração em camadas.
+use std::{collections::HashMap, env, fs};
+
+use crate::config::Config;
+
+// Variáveis obrigatórias, sem valor padrão.
+const REQUIRED: &[(&str, &str)] = &[
+    ("DATABASE_URL", "postgres://localhost/global"),
+    ("TOKEN_SECRET", "secret"),
+];
+
+// Método para carregar a configuração a partir de argumentos e
+// variáveis explícitos, sem depender do ambiente do processo.
+fn load(args: &[&str], vars: &[(&str, &str)]) -> Result<Config, Vec<String>> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    Config::from_sources(args.iter().map(|arg| arg.to_string()), &vars)
+}
+
+// Método para escrever um arquivo temporário, retornando o caminho.
+fn file(name: &str, content: &str) -> String {
+    let path = env::temp_dir().join(format!("global-config-{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+#[test]
+fn layers_follow_the_precedence_order() {
+    let path = file(
+        "precedence.toml",
+        "workers = 2\nkeep_alive = 3\naccess_token_ttl = 60\n",
+    );
+    let config = load(
+        &["--config", &path, "--access-token-ttl=180"],
+        &[
+            REQUIRED,
+            &[("KEEP_ALIVE", "7"), ("ACCESS_TOKEN_TTL", "120")],
+        ]
+        .concat(),
+    )
+    .unwrap();
+
+    assert_eq!(config.shutdown_delay, 5); // Padrão.
+    assert_eq!(config.workers, Some(2)); // Arquivo sobre o padrão.
+    assert_eq!(config.keep_alive, Some(7)); // Ambiente sobre o arquivo.
+    assert_eq!(config.access_token_ttl, 180); // Argumento sobre o ambiente.
+}
+
+#[test]
+fn secrets_are_read_from_files() {
+    let url = file("database_url", "postgres://file/global\n");
+    let secret = file("token_secret", "  from-file\n");
+    let config = load(
+        &[],
+        &[
+            ("DATABASE_URL_FILE", url.as_str()),
+            ("TOKEN_SECRET_FILE", &secret),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(config.database.url, "postgres://file/global");
+    assert_eq!(config.token_secret, "from-file");
+
+    // A variável e a sua variante "_FILE" não podem ser usadas juntas.
+    let errors = load(&[], &[REQUIRED, &[("TOKEN_SECRET_FILE", &secret)]].concat())
+        .err()
+        .unwrap();
+    assert_eq!(
+        errors,
+        ["@ENV('TOKEN_SECRET_FILE'): cannot be used together with TOKEN_SECRET"]
+    );
+
+    let errors = load(
+        &[],
+        &[
+            ("DATABASE_URL", "postgres://localhost/global"),
+            ("TOKEN_SECRET_FILE", "/nonexistent/token"),
+        ],
+    )
+    .err()
+    .unwrap();
+    assert!(errors[0]
+        .starts_with("@ENV('TOKEN_SECRET_FILE'): cannot read the file '/nonexistent/token'"));
+}
+
+#[test]
+fn aliases_and_tables_resolve_to_the_same_keys() {
+    let config = load(&[], &[REQUIRED, &[("MAX_CONNECTIONS", "4")]].concat()).unwrap();
+    assert_eq!(config.database.max_connections, 4);
+
+    // O nome novo tem precedência sobre o antigo na mesma camada.
+    let vars = [
+        REQUIRED,
+        &[("MAX_CONNECTIONS", "4"), ("DATABASE_MAX_CONNECTIONS", "6")],
+    ]
+    .concat();
+    assert_eq!(load(&[], &vars).unwrap().database.max_connections, 6);
+
+    let path = file(
+        "tables.toml",
+        "max_connections = 8\n[database]\nurl = \"postgres://table/global\"\nmin_connections = 2\n",
+    );
+    let config = load(&["--config", &path], &[("TOKEN_SECRET", "secret")]).unwrap();
+    assert_eq!(config.database.url, "postgres://table/global");
+    assert_eq!(config.database.max_connections, 8);
+    assert_eq!(config.database.min_connections, 2);
+
+    let config = load(&["--max-connections", "9"], REQUIRED).unwrap();
+    assert_eq!(config.database.max_connections, 9);
+}
+
+#[test]
+fn every_invalid_key_is_reported_together() {
+    let path = file("invalid.toml", "workers = \"many\"\nunknown = 1\n");
+    let errors = load(
+        &[
+            "--config",
+            &path,
+            "--keep-alive",
+            "soon",
+            "--bogus=1",
+            "stray",
+        ],
+        &[
+            ("DATABASE_URL", "postgres://localhost/global"),
+            ("FEED", "maybe"),
+        ],
+    )
+    .err()
+    .unwrap();
+
+    for expected in [
+        "unknown argument 'stray'",
+        "'unknown': unknown setting",
+        "--bogus: unknown setting",
+        "'workers' (file): invalid value 'many'",
+        "'keep_alive' (cli): invalid value 'soon'",
+        "'feed' (env): invalid value 'maybe'",
+        "'token_secret': must be set",
+    ] {
+        assert!(
+            errors.iter().any(|error| error.ends_with(expected)),
+            "missing '{}' in {:?}",
+            expected,
+            errors
+        );
+    }
+}
@@ -0,0 +1,208 @@
+// Testes das conversas, das mensagens diretas e das confirmações de leitura.
+use actix_web::{http::StatusCode, test};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{bearer, call, signup, state};
+use crate::{
+    create,
+    service::conversation::{CONTENT_MAX, MAX_MEMBERS},
+};
+
+// Método para criar uma conversa com os usuários.
+fn open(token: &str, user_ids: &[&str]) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri("/api/dm/conversations")
+        .insert_header(bearer(token))
+        .set_json(json!({ "user_ids": user_ids }))
+}
+
+// Método para enviar uma mensagem à conversa.
+fn send(token: &str, id: &str, content: &str) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri(&format!("/api/dm/conversations/{}/messages", id))
+        .insert_header(bearer(token))
+        .set_json(json!({ "content": content }))
+}
+
+// Método para obter as mensagens não lidas.
+fn unread(token: &str) -> test::TestRequest {
+    test::TestRequest::get()
+        .uri("/api/dm/unread")
+        .insert_header(bearer(token))
+}
+
+#[actix_web::test]
+async fn direct_conversations_are_reused_and_groups_are_limited() {
+    let app = test::init_service(create::app(state())).await;
+    let (ana, ana_token) = signup(&app, "ana").await;
+    let (bob, bob_token) = signup(&app, "bob").await;
+
+    let created = call(&app, open(&ana_token, &[&bob, &bob, &ana])).await;
+    assert_eq!(created.status, StatusCode::CREATED, "{}", created.text);
+    assert_eq!(created.body["response"]["direct"], true);
+    assert_eq!(created.body["response"]["members"][0]["name"], "ana");
+    assert_eq!(created.body["response"]["members"][1]["name"], "bob");
+
+    // A conversa direta é a mesma para os dois usuários.
+    let existing = call(&app, open(&bob_token, &[&ana])).await;
+    assert_eq!(existing.status, StatusCode::OK);
+    assert_eq!(
+        existing.body["response"]["id"],
+        created.body["response"]["id"]
+    );
+
+    let alone = call(&app, open(&ana_token, &[&ana])).await;
+    assert_eq!(alone.status, StatusCode::BAD_REQUEST);
+
+    let missing = Uuid::new_v4().to_string();
+    let unknown = call(&app, open(&ana_token, &[&bob, &missing])).await;
+    assert_eq!(unknown.status, StatusCode::NOT_FOUND);
+
+    let mut ids = Vec::new();
+    for n in 0..MAX_MEMBERS {
+        ids.push(signup(&app, &format!("user{}", n)).await.0);
+    }
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+    let crowded = call(&app, open(&ana_token, &ids)).await;
+    assert_eq!(crowded.status, StatusCode::BAD_REQUEST);
+
+    // Duas conversas em grupo com os mesmos usuários são distintas.
+    let group = call(&app, open(&ana_token, &ids[..MAX_MEMBERS - 1])).await;
+    assert_eq!(group.status, StatusCode::CREATED);
+    assert_eq!(group.body["response"]["direct"], false);
+    assert_eq!(
+        group.body["response"]["members"].as_array().unwrap().len(),
+        MAX_MEMBERS
+    );
+    let again = call(&app, open(&ana_token, &ids[..MAX_MEMBERS - 1])).await;
+    assert_eq!(again.status, StatusCode::CREATED);
+    assert_ne!(again.body["response"]["id"], group.body["response"]["id"]);
+}
+
+#[actix_web::test]
+async fn only_members_read_and_send_private_messages() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, ana_token) = signup(&app, "ana").await;
+    let (bob, _) = signup(&app, "bob").await;
+    let (_, cid_token) = signup(&app, "cid").await;
+
+    let created = call(&app, open(&ana_token, &[&bob])).await;
+    let id = created.body["response"]["id"].as_str().unwrap().to_string();
+
+    let sent = call(&app, send(&ana_token, &id, "segredo")).await;
+    assert_eq!(sent.status, StatusCode::CREATED, "{}", sent.text);
+
+    let blank = call(&app, send(&ana_token, &id, "  ")).await;
+    assert_eq!(blank.status, StatusCode::BAD_REQUEST);
+    let long = call(&app, send(&ana_token, &id, &"a".repeat(CONTENT_MAX + 1))).await;
+    assert_eq!(long.status, StatusCode::BAD_REQUEST);
+
+    for request in [
+        test::TestRequest::get().uri(&format!("/api/dm/conversations/{}", id)),
+        test::TestRequest::get().uri(&format!("/api/dm/conversations/{}/messages", id)),
+        test::TestRequest::post().uri(&format!("/api/dm/conversations/{}/read", id)),
+    ] {
+        let reply = call(&app, request.insert_header(bearer(&cid_token))).await;
+        assert_eq!(reply.status, StatusCode::FORBIDDEN);
+    }
+    let intruder = call(&app, send(&cid_token, &id, "olá")).await;
+    assert_eq!(intruder.status, StatusCode::FORBIDDEN);
+
+    let missing = test::TestRequest::get()
+        .uri(&format!(
+            "/api/dm/conversations/{}/messages",
+            Uuid::new_v4()
+        ))
+        .insert_header(bearer(&ana_token));
+    assert_eq!(call(&app, missing).await.status, StatusCode::NOT_FOUND);
+
+    let anonymous = test::TestRequest::get().uri("/api/dm/conversations");
+    assert_eq!(call(&app, anonymous).await.status, StatusCode::UNAUTHORIZED);
+
+    // As mensagens diretas não aparecem nas mensagens públicas.
+    let all = call(&app, test::TestRequest::get().uri("/api/tech/all")).await;
+    assert_eq!(all.body["response"], json!([]));
+    let some = call(&app, test::TestRequest::get().uri("/api/tech/some/segredo")).await;
+    assert_eq!(some.status, StatusCode::NOT_FOUND);
+
+    let conversations = test::TestRequest::get()
+        .uri("/api/dm/conversations")
+        .insert_header(bearer(&cid_token));
+    assert_eq!(call(&app, conversations).await.body["response"], json!([]));
+}
+
+#[actix_web::test]
+async fn receipts_and_unread_counts_follow_the_reads() {
+    let app = test::init_service(create::app(state())).await;
+    let (ana, ana_token) = signup(&app, "ana").await;
+    let (bob, bob_token) = signup(&app, "bob").await;
+    let (cid, cid_token) = signup(&app, "cid").await;
+
+    let direct = call(&app, open(&ana_token, &[&bob])).await;
+    let direct = direct.body["response"]["id"].as_str().unwrap().to_string();
+    let group = call(&app, open(&bob_token, &[&ana, &cid])).await;
+    let group = group.body["response"]["id"].as_str().unwrap().to_string();
+
+    for content in ["um", "dois", "três"] {
+        let sent = call(&app, send(&ana_token, &direct, content)).await;
+        assert_eq!(sent.status, StatusCode::CREATED);
+        assert_eq!(sent.body["response"]["read_by"], json!([]));
+    }
+    call(&app, send(&cid_token, &group, "oi")).await;
+
+    let counts = call(&app, unread(&bob_token)).await;
+    assert_eq!(counts.body["response"]["messages"], 4);
+    assert_eq!(counts.body["response"]["conversations"], 2);
+    assert_eq!(
+        call(&app, unread(&ana_token)).await.body["response"]["messages"],
+        1
+    );
+
+    // As conversas começam pela mensagem mais recente.
+    let page = test::TestRequest::get()
+        .uri("/api/dm/conversations")
+        .insert_header(bearer(&bob_token));
+    let page = call(&app, page).await;
+    assert_eq!(page.body["response"][0]["id"], group.as_str());
+    assert_eq!(page.body["response"][0]["unread"], 1);
+    assert_eq!(page.body["response"][1]["unread"], 3);
+
+    let read = test::TestRequest::post()
+        .uri(&format!("/api/dm/conversations/{}/read", direct))
+        .insert_header(bearer(&bob_token));
+    let read = call(&app, read).await;
+    assert_eq!(read.status, StatusCode::OK);
+    assert_eq!(read.body["status"], "read");
+    assert_eq!(read.body["response"]["unread"], 0);
+    assert!(read.body["response"]["members"][1]["last_read_at"].is_string());
+
+    let counts = call(&app, unread(&bob_token)).await;
+    assert_eq!(counts.body["response"]["messages"], 1);
+    assert_eq!(counts.body["response"]["conversations"], 1);
+
+    // As mensagens lidas indicam quem as leu, paginadas da mais nova.
+    let history = test::TestRequest::get()
+        .uri(&format!(
+            "/api/dm/conversations/{}/messages?limit=2",
+            direct
+        ))
+        .insert_header(bearer(&ana_token));
+    let history = call(&app, history).await;
+    assert_eq!(history.status, StatusCode::OK);
+    assert_eq!(history.body["has_more"], true);
+    assert_eq!(history.body["response"][0]["content"], "três");
+    assert_eq!(history.body["response"][0]["read_by"], json!([bob]));
+
+    let cursor = history.body["next_cursor"].as_str().unwrap();
+    let rest = test::TestRequest::get()
+        .uri(&format!(
+            "/api/dm/conversations/{}/messages?limit=2&cursor={}",
+            direct, cursor
+        ))
+        .insert_header(bearer(&ana_token));
+    let rest = call(&app, rest).await;
+    assert_eq!(rest.body["has_more"], false);
+    assert_eq!(rest.body["response"][0]["content"], "um");
+}
@@ -0,0 +1,144 @@
+// Testes da extração das hashtags e das menções e das suas linhas do tempo.
+use actix_web::{http::StatusCode, test};
+use serde_json::json;
+
+use super::{bearer, call, post_message, register, signup, state};
+use crate::{create, entity, schema::EntityKind};
+
+#[actix_web::test]
+async fn entities_have_byte_offsets() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let bob = register(&app, "bob").await;
+
+    // "á" ocupa dois bytes, a menção desconhecida é descartada e
+    // "C#" e o e-mail não são entidades.
+    let content = "Olá #Rust e @bob, @ninguém. C# e ana@mail.com";
+    let id = post_message(&app, &access, content).await;
+
+    let message = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/one/{}", id)),
+    )
+    .await;
+    assert_eq!(
+        message.body["response"]["entities"],
+        json!([
+            { "kind": "hashtag", "text": "rust", "start": 5, "end": 10 },
+            { "kind": "mention", "text": "bob", "start": 13, "end": 17, "user_id": bob },
+        ])
+    );
+    assert_eq!(&content[5..10], "#Rust");
+    assert_eq!(&content[13..17], "@bob");
+}
+
+#[actix_web::test]
+async fn tag_timeline_ignores_case_and_symbol() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+
+    post_message(&app, &access, "#rust um").await;
+    let second = post_message(&app, &access, "dois #RUST").await;
+    post_message(&app, &access, "#go").await;
+
+    for tag in ["Rust", "%23rust"] {
+        let page = call(
+            &app,
+            test::TestRequest::get().uri(&format!("/api/tech/tag/{}", tag)),
+        )
+        .await;
+        assert_eq!(page.status, StatusCode::OK);
+        assert_eq!(page.body["response"].as_array().unwrap().len(), 2);
+        assert_eq!(page.body["response"][0]["id"], second.as_str());
+    }
+
+    // A edição substitui as hashtags da mensagem.
+    let edit = test::TestRequest::patch()
+        .uri(&format!("/api/tech/manage/{}", second))
+        .insert_header(bearer(&access))
+        .set_json(json!({ "content": "dois" }));
+    assert_eq!(call(&app, edit).await.status, StatusCode::OK);
+
+    let page = call(&app, test::TestRequest::get().uri("/api/tech/tag/rust")).await;
+    assert_eq!(page.body["response"].as_array().unwrap().len(), 1);
+
+    for tag in ["123", "a-b", &"a".repeat(101)] {
+        let invalid = test::TestRequest::get().uri(&format!("/api/tech/tag/{}", tag));
+        assert_eq!(call(&app, invalid).await.status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[actix_web::test]
+async fn mention_timeline_follows_edits_and_deletes() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let bob = register(&app, "bob").await;
+
+    let first = post_message(&app, &access, "oi @bob").await;
+    let second = post_message(&app, &access, "@bob, tudo bem?").await;
+    post_message(&app, &access, "sem menção").await;
+
+    let mentions = format!("/api/tech/mentions/{}", bob);
+    let page = call(&app, test::TestRequest::get().uri(&mentions)).await;
+    assert_eq!(page.status, StatusCode::OK);
+    assert_eq!(page.body["response"].as_array().unwrap().len(), 2);
+    assert_eq!(page.body["response"][0]["id"], second.as_str());
+
+    let edit = test::TestRequest::patch()
+        .uri(&format!("/api/tech/manage/{}", first))
+        .insert_header(bearer(&access))
+        .set_json(json!({ "content": "oi" }));
+    assert_eq!(call(&app, edit).await.status, StatusCode::OK);
+
+    let delete = test::TestRequest::delete()
+        .uri(&format!("/api/tech/manage/{}", second))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, delete).await.status, StatusCode::OK);
+
+    let page = call(&app, test::TestRequest::get().uri(&mentions)).await;
+    assert_eq!(page.body["response"], json!([]));
+
+    let unknown =
+        test::TestRequest::get().uri(&format!("/api/tech/mentions/{}", uuid::Uuid::new_v4()));
+    assert_eq!(call(&app, unknown).await.status, StatusCode::NOT_FOUND);
+
+    let invalid = test::TestRequest::get().uri("/api/tech/mentions/bob");
+    assert_eq!(call(&app, invalid).await.status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn extracted_offsets_are_byte_offsets() {
+    let entities = entity::extract("Olá #Ação, @bob. #123 C# ana@mail.com @-");
+
+    let found: Vec<_> = entities
+        .iter()
+        .map(|e| (e.kind, e.text.as_str(), e.start, e.end))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (EntityKind::Hashtag, "ação", 5, 12), // "á", "ç" e "ã" ocupam dois bytes.
+            (EntityKind::Mention, "bob", 14, 18), // O "." final é pontuação.
+        ]
+    );
+    assert!(entities.iter().all(|e| e.user_id.is_none()));
+}
+
+#[actix_web::test]
+async fn symbols_need_a_word_boundary() {
+    assert!(entity::extract("C#, ana@mail.com, ##dupla e @@dupla").is_empty());
+    assert_eq!(entity::extract("(#rust)")[0].text, "rust");
+}
+
+#[actix_web::test]
+async fn tags_are_checked_after_lowercasing() {
+    assert_eq!(entity::normalize_tag("#Ação"), Some("ação".to_string()));
+    assert_eq!(
+        entity::normalize_tag(&"A".repeat(entity::TAG_MAX)),
+        Some("a".repeat(entity::TAG_MAX))
+    );
+
+    // "İ" vira "i̇" em minúsculas, com um ponto combinante que não é aceito.
+    assert!(entity::normalize_tag("#İstanbul").is_none());
+    assert!(entity::extract("#İstanbul").is_empty());
+}
@@ -0,0 +1,160 @@
+// Testes das rotas de follows e da linha do tempo.
+use actix_web::{http::StatusCode, test};
+
+use super::{bearer, call, post_message, signup, state};
+use crate::create;
+
+#[actix_web::test]
+async fn follow_is_unique_per_user() {
+    let app = test::init_service(create::app(state())).await;
+    let (ana, access) = signup(&app, "ana").await;
+    let (bob, _) = signup(&app, "bob").await;
+
+    let anonymous = test::TestRequest::post().uri(&format!("/api/user/follow/{}", bob));
+    assert_eq!(call(&app, anonymous).await.status, StatusCode::UNAUTHORIZED);
+
+    let followed = call(
+        &app,
+        test::TestRequest::post()
+            .uri(&format!("/api/user/follow/{}", bob))
+            .insert_header(bearer(&access)),
+    )
+    .await;
+    assert_eq!(followed.status, StatusCode::CREATED);
+    assert_eq!(followed.body["response"]["name"], "bob");
+
+    let repeated = test::TestRequest::post()
+        .uri(&format!("/api/user/follow/{}", bob))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, repeated).await.status, StatusCode::CONFLICT);
+
+    let itself = test::TestRequest::post()
+        .uri(&format!("/api/user/follow/{}", ana))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, itself).await.status, StatusCode::BAD_REQUEST);
+
+    let missing = test::TestRequest::post()
+        .uri(&format!("/api/user/follow/{}", uuid::Uuid::new_v4()))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, missing).await.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn unfollow_removes_the_follow() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let (bob, _) = signup(&app, "bob").await;
+
+    let follow = test::TestRequest::post()
+        .uri(&format!("/api/user/follow/{}", bob))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, follow).await.status, StatusCode::CREATED);
+
+    let unfollowed = test::TestRequest::delete()
+        .uri(&format!("/api/user/follow/{}", bob))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, unfollowed).await.status, StatusCode::OK);
+
+    let again = test::TestRequest::delete()
+        .uri(&format!("/api/user/follow/{}", bob))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, again).await.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn follows_list_both_sides() {
+    let app = test::init_service(create::app(state())).await;
+    let (ana, access) = signup(&app, "ana").await;
+    let (bob, other) = signup(&app, "bob").await;
+    let (cid, _) = signup(&app, "cid").await;
+
+    for (token, id) in [(&access, &bob), (&access, &cid), (&other, &ana)] {
+        let follow = test::TestRequest::post()
+            .uri(&format!("/api/user/follow/{}", id))
+            .insert_header(bearer(token));
+        assert_eq!(call(&app, follow).await.status, StatusCode::CREATED);
+    }
+
+    let following = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/user/following/{}?limit=1", ana)),
+    )
+    .await;
+    assert_eq!(following.status, StatusCode::OK);
+    assert_eq!(following.body["response"][0]["name"], "cid");
+    assert_eq!(following.body["has_more"], true);
+
+    let cursor = following.body["next_cursor"].as_str().unwrap();
+    let rest = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/user/following/{}?cursor={}", ana, cursor)),
+    )
+    .await;
+    assert_eq!(rest.body["response"][0]["name"], "bob");
+    assert_eq!(rest.body["response"].as_array().unwrap().len(), 1);
+
+    let followers = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/user/followers/{}", ana)),
+    )
+    .await;
+    assert_eq!(followers.body["response"][0]["user_id"], bob);
+
+    let missing =
+        test::TestRequest::get().uri(&format!("/api/user/followers/{}", uuid::Uuid::new_v4()));
+    assert_eq!(call(&app, missing).await.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn feed_merges_the_followed_users() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let (bob, other) = signup(&app, "bob").await;
+    let (cid, third) = signup(&app, "cid").await;
+    let (_, stranger) = signup(&app, "dan").await;
+
+    for id in [&bob, &cid] {
+        let follow = test::TestRequest::post()
+            .uri(&format!("/api/user/follow/{}", id))
+            .insert_header(bearer(&access));
+        call(&app, follow).await;
+    }
+
+    post_message(&app, &other, "primeira").await;
+    post_message(&app, &stranger, "de fora").await;
+    post_message(&app, &third, "segunda").await;
+    post_message(&app, &access, "própria").await;
+    post_message(&app, &other, "terceira").await;
+
+    let anonymous = test::TestRequest::get().uri("/api/tech/feed");
+    assert_eq!(call(&app, anonymous).await.status, StatusCode::UNAUTHORIZED);
+
+    let first = call(
+        &app,
+        test::TestRequest::get()
+            .uri("/api/tech/feed?limit=2")
+            .insert_header(bearer(&access)),
+    )
+    .await;
+    assert_eq!(first.status, StatusCode::OK);
+    assert_eq!(first.body["response"][0]["content"], "terceira");
+    assert_eq!(first.body["response"][1]["content"], "segunda");
+    assert_eq!(first.body["has_more"], true);
+
+    let cursor = first.body["next_cursor"].as_str().unwrap();
+    let rest = call(
+        &app,
+        test::TestRequest::get()
+            .uri(&format!("/api/tech/feed?limit=2&cursor={}", cursor))
+            .insert_header(bearer(&access)),
+    )
+    .await;
+    let contents: Vec<_> = rest.body["response"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["content"].as_str().unwrap())
+        .collect();
+    assert_eq!(contents, ["primeira"]);
+    assert_eq!(rest.body["has_more"], false);
+}
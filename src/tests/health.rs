@@ -0,0 +1,34 @@
+// Testes das rotas de saúde.
+use actix_web::{http::StatusCode, test};
+use std::sync::atomic::Ordering;
+
+use super::{call, state};
+use crate::create;
+
+#[actix_web::test]
+async fn live_does_not_depend_on_the_database() {
+    let app = test::init_service(create::app(state())).await;
+
+    let live = call(&app, test::TestRequest::get().uri("/health/live")).await;
+    assert_eq!(live.status, StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn ready_reports_the_database_down() {
+    let app = test::init_service(create::app(state())).await;
+
+    let ready = call(&app, test::TestRequest::get().uri("/health/ready")).await;
+    assert_eq!(ready.status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(ready.body["response"]["database"]["status"], "down");
+}
+
+#[actix_web::test]
+async fn ready_fails_while_draining() {
+    let state = state();
+    state.draining.store(true, Ordering::SeqCst);
+    let app = test::init_service(create::app(state)).await;
+
+    let ready = call(&app, test::TestRequest::get().uri("/health/ready")).await;
+    assert_eq!(ready.status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(ready.body["response"]["server"]["status"], "down");
+}
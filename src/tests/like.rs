@@ -0,0 +1,98 @@
+// Testes das rotas de curtidas.
+use actix_web::{http::StatusCode, test};
+
+use super::{bearer, call, post_message, signup, state};
+use crate::create;
+
+#[actix_web::test]
+async fn like_is_unique_per_user() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let (_, other) = signup(&app, "bob").await;
+    let id = post_message(&app, &access, "olá").await;
+
+    let anonymous = call(
+        &app,
+        test::TestRequest::post().uri(&format!("/api/tech/like/{}", id)),
+    )
+    .await;
+    assert_eq!(anonymous.status, StatusCode::UNAUTHORIZED);
+
+    let liked = call(
+        &app,
+        test::TestRequest::post()
+            .uri(&format!("/api/tech/like/{}", id))
+            .insert_header(bearer(&other)),
+    )
+    .await;
+    assert_eq!(liked.status, StatusCode::CREATED);
+    assert_eq!(liked.body["response"]["likes"], 1);
+
+    let repeated = test::TestRequest::post()
+        .uri(&format!("/api/tech/like/{}", id))
+        .insert_header(bearer(&other));
+    assert_eq!(call(&app, repeated).await.status, StatusCode::CONFLICT);
+
+    let missing = test::TestRequest::post()
+        .uri(&format!("/api/tech/like/{}", uuid::Uuid::new_v4()))
+        .insert_header(bearer(&other));
+    assert_eq!(call(&app, missing).await.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn unlike_removes_the_like() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let id = post_message(&app, &access, "olá").await;
+
+    let like = test::TestRequest::post()
+        .uri(&format!("/api/tech/like/{}", id))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, like).await.status, StatusCode::CREATED);
+
+    let unliked = call(
+        &app,
+        test::TestRequest::delete()
+            .uri(&format!("/api/tech/like/{}", id))
+            .insert_header(bearer(&access)),
+    )
+    .await;
+    assert_eq!(unliked.status, StatusCode::OK);
+    assert_eq!(unliked.body["response"]["likes"], 0);
+
+    let again = test::TestRequest::delete()
+        .uri(&format!("/api/tech/like/{}", id))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, again).await.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn likes_list_the_users() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let (bob, other) = signup(&app, "bob").await;
+    let id = post_message(&app, &access, "olá").await;
+
+    let like = test::TestRequest::post()
+        .uri(&format!("/api/tech/like/{}", id))
+        .insert_header(bearer(&other));
+    call(&app, like).await;
+
+    let likes = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/likes/{}", id)),
+    )
+    .await;
+    assert_eq!(likes.status, StatusCode::OK);
+    let users = likes.body["response"].as_array().unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0]["user_id"], bob.as_str());
+    assert_eq!(users[0]["name"], "bob");
+
+    let missing = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/likes/{}", uuid::Uuid::new_v4())),
+    )
+    .await;
+    assert_eq!(missing.status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,369 @@
+// Testes das rotas de mensagens: publicação, pesquisa, edição, remoção e feed.
+use actix_web::{
+    http::{header, StatusCode},
+    test,
+};
+use serde_json::json;
+
+use super::{bearer, call, post_message, signup, state};
+use crate::create;
+
+#[actix_web::test]
+async fn post_message_requires_authentication() {
+    let app = test::init_service(create::app(state())).await;
+    let (id, access) = signup(&app, "ana").await;
+
+    let anonymous = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/tech/manage")
+            .set_json(json!({ "content": "olá" })),
+    )
+    .await;
+    assert_eq!(anonymous.status, StatusCode::UNAUTHORIZED);
+
+    let invalid = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/tech/manage")
+            .insert_header(bearer("invalid"))
+            .set_json(json!({ "content": "olá" })),
+    )
+    .await;
+    assert_eq!(invalid.status, StatusCode::UNAUTHORIZED);
+
+    let created = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/tech/manage")
+            .insert_header(bearer(&access))
+            .set_json(json!({ "content": "olá" })),
+    )
+    .await;
+    assert_eq!(created.status, StatusCode::CREATED);
+    assert_eq!(created.body["response"]["user_id"], id.as_str());
+    assert_eq!(created.body["response"]["likes"], 0);
+
+    // O token de um usuário removido referencia um usuário inexistente.
+    let delete = test::TestRequest::delete()
+        .uri("/api/user/manage")
+        .insert_header(bearer(&access));
+    call(&app, delete).await;
+
+    let orphan = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/tech/manage")
+            .insert_header(bearer(&access))
+            .set_json(json!({ "content": "olá" })),
+    )
+    .await;
+    assert_eq!(orphan.status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[actix_web::test]
+async fn all_messages_are_paginated() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    for n in 0..3 {
+        post_message(&app, &access, &format!("mensagem {}", n)).await;
+    }
+
+    let first = call(&app, test::TestRequest::get().uri("/api/tech/all?limit=2")).await;
+    assert_eq!(first.status, StatusCode::OK);
+    assert_eq!(first.body["response"].as_array().unwrap().len(), 2);
+    assert_eq!(first.body["has_more"], true);
+
+    let cursor = first.body["next_cursor"].as_str().unwrap();
+    let second = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/all?limit=2&cursor={}", cursor)),
+    )
+    .await;
+    let last = &second.body["response"][0]["id"];
+    assert_eq!(second.body["response"].as_array().unwrap().len(), 1);
+    assert_eq!(second.body["has_more"], false);
+    assert!(first.body["response"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|m| &m["id"] != last));
+
+    let invalid = call(&app, test::TestRequest::get().uri("/api/tech/all?limit=0")).await;
+    assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn one_message_is_found_by_id() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let id = post_message(&app, &access, "olá").await;
+
+    let found = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/one/{}", id)),
+    )
+    .await;
+    assert_eq!(found.status, StatusCode::OK);
+    assert_eq!(found.body["response"]["content"], "olá");
+
+    let missing = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/one/{}", uuid::Uuid::new_v4())),
+    )
+    .await;
+    assert_eq!(missing.status, StatusCode::NOT_FOUND);
+
+    let invalid = call(&app, test::TestRequest::get().uri("/api/tech/one/abc")).await;
+    assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn some_messages_are_found_by_content() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    post_message(&app, &access, "aprendendo rust").await;
+    post_message(&app, &access, "rust é rápido").await;
+    post_message(&app, &access, "Rust com maiúscula").await;
+    post_message(&app, &access, "bom dia").await;
+
+    let found = call(&app, test::TestRequest::get().uri("/api/tech/some/rust")).await;
+    assert_eq!(found.status, StatusCode::OK);
+    assert_eq!(found.body["response"].as_array().unwrap().len(), 2); // A busca diferencia maiúsculas.
+
+    // Os curingas do LIKE são tratados como texto.
+    let wildcard = call(&app, test::TestRequest::get().uri("/api/tech/some/%25")).await;
+    assert_eq!(wildcard.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn search_ranks_and_highlights_messages() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    post_message(&app, &access, "rust rust e mais rust").await;
+    post_message(&app, &access, "gosto de rust").await;
+    post_message(&app, &access, "rust sem go").await;
+    post_message(&app, &access, "só go").await;
+
+    let found = call(
+        &app,
+        test::TestRequest::get().uri("/api/tech/search?q=rust%20-go"),
+    )
+    .await;
+    assert_eq!(found.status, StatusCode::OK);
+    let results = found.body["response"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["message"]["content"], "rust rust e mais rust");
+    assert!(results[0]["snippet"]
+        .as_str()
+        .unwrap()
+        .contains("<mark>rust</mark>"));
+
+    let empty = call(
+        &app,
+        test::TestRequest::get().uri("/api/tech/search?q=%22%22"),
+    )
+    .await;
+    assert_eq!(empty.status, StatusCode::BAD_REQUEST);
+
+    let missing = call(&app, test::TestRequest::get().uri("/api/tech/search")).await;
+    assert_eq!(missing.status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn messages_are_listed_by_user() {
+    let app = test::init_service(create::app(state())).await;
+    let (ana, access) = signup(&app, "ana").await;
+    let (bob, other) = signup(&app, "bob").await;
+    post_message(&app, &access, "da ana").await;
+    post_message(&app, &other, "do bob").await;
+
+    let found = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/from/{}", ana)),
+    )
+    .await;
+    assert_eq!(found.status, StatusCode::OK);
+    let messages = found.body["response"].as_array().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0]["content"], "da ana");
+
+    let delete = test::TestRequest::delete()
+        .uri("/api/user/manage")
+        .insert_header(bearer(&other));
+    call(&app, delete).await;
+
+    let missing = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/from/{}", bob)),
+    )
+    .await;
+    assert_eq!(missing.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn only_the_author_edits_a_message() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let (_, other) = signup(&app, "bob").await;
+    let id = post_message(&app, &access, "primeira versão").await;
+
+    let forbidden = call(
+        &app,
+        test::TestRequest::patch()
+            .uri(&format!("/api/tech/manage/{}", id))
+            .insert_header(bearer(&other))
+            .set_json(json!({ "content": "invasão" })),
+    )
+    .await;
+    assert_eq!(forbidden.status, StatusCode::FORBIDDEN);
+
+    let edited = call(
+        &app,
+        test::TestRequest::patch()
+            .uri(&format!("/api/tech/manage/{}", id))
+            .insert_header(bearer(&access))
+            .set_json(json!({ "content": "segunda versão" })),
+    )
+    .await;
+    assert_eq!(edited.status, StatusCode::OK);
+    assert_eq!(edited.body["response"]["content"], "segunda versão");
+    assert!(edited.body["response"]["edited_at"].is_string());
+
+    let missing = call(
+        &app,
+        test::TestRequest::patch()
+            .uri(&format!("/api/tech/manage/{}", uuid::Uuid::new_v4()))
+            .insert_header(bearer(&access))
+            .set_json(json!({ "content": "nada" })),
+    )
+    .await;
+    assert_eq!(missing.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn history_keeps_the_replaced_contents() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let id = post_message(&app, &access, "v1").await;
+
+    for content in ["v2", "v3"] {
+        let edit = test::TestRequest::patch()
+            .uri(&format!("/api/tech/manage/{}", id))
+            .insert_header(bearer(&access))
+            .set_json(json!({ "content": content }));
+        assert_eq!(call(&app, edit).await.status, StatusCode::OK);
+    }
+
+    let history = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/history/{}", id)),
+    )
+    .await;
+    assert_eq!(history.status, StatusCode::OK);
+    let revisions = history.body["response"].as_array().unwrap();
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0]["content"], "v2");
+    assert_eq!(revisions[1]["content"], "v1");
+
+    let missing = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/history/{}", uuid::Uuid::new_v4())),
+    )
+    .await;
+    assert_eq!(missing.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn only_the_author_deletes_a_message() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let (_, other) = signup(&app, "bob").await;
+    let id = post_message(&app, &access, "olá").await;
+
+    let forbidden = test::TestRequest::delete()
+        .uri(&format!("/api/tech/manage/{}", id))
+        .insert_header(bearer(&other));
+    assert_eq!(call(&app, forbidden).await.status, StatusCode::FORBIDDEN);
+
+    let delete = test::TestRequest::delete()
+        .uri(&format!("/api/tech/manage/{}", id))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, delete).await.status, StatusCode::OK);
+
+    let again = test::TestRequest::delete()
+        .uri(&format!("/api/tech/manage/{}", id))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, again).await.status, StatusCode::NOT_FOUND);
+
+    let invalid = test::TestRequest::delete()
+        .uri("/api/tech/manage/abc")
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, invalid).await.status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn feed_upgrades_to_websocket() {
+    let app = test::init_service(create::app(state())).await;
+
+    // A resposta do handshake mantém o corpo aberto, por isso só o status é lido.
+    let handshake = test::TestRequest::get()
+        .uri("/api/tech/ws?global=true")
+        .insert_header((header::CONNECTION, "upgrade"))
+        .insert_header((header::UPGRADE, "websocket"))
+        .insert_header((header::SEC_WEBSOCKET_VERSION, "13"))
+        .insert_header((header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ=="))
+        .to_request();
+    let res = test::call_service(&app, handshake).await;
+    assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
+
+    let plain = call(&app, test::TestRequest::get().uri("/api/tech/ws")).await;
+    assert_eq!(plain.status, StatusCode::BAD_REQUEST);
+
+    let mut disabled = state();
+    disabled.feed = None;
+    let app = test::init_service(create::app(disabled)).await;
+    let res = call(&app, test::TestRequest::get().uri("/api/tech/ws")).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn invalid_contents_are_rejected() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let id = post_message(&app, &access, "primeira versão").await;
+
+    let long = "a".repeat(crate::service::message::CONTENT_MAX + 1);
+    for content in ["", "   ", long.as_str()] {
+        let created = call(
+            &app,
+            test::TestRequest::post()
+                .uri("/api/tech/manage")
+                .insert_header(bearer(&access))
+                .set_json(json!({ "content": content })),
+        )
+        .await;
+        assert_eq!(created.status, StatusCode::BAD_REQUEST);
+
+        let edited = call(
+            &app,
+            test::TestRequest::patch()
+                .uri(&format!("/api/tech/manage/{}", id))
+                .insert_header(bearer(&access))
+                .set_json(json!({ "content": content })),
+        )
+        .await;
+        assert_eq!(edited.status, StatusCode::BAD_REQUEST);
+    }
+
+    // A mensagem respondida não pode ser alterada pela edição.
+    let reparented = call(
+        &app,
+        test::TestRequest::patch()
+            .uri(&format!("/api/tech/manage/{}", id))
+            .insert_header(bearer(&access))
+            .set_json(json!({ "content": "outra", "parent_id": uuid::Uuid::new_v4() })),
+    )
+    .await;
+    assert_eq!(reparented.status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,26 @@
+// Testes da rota de métricas.
+use actix_web::{http::StatusCode, test};
+
+use super::{call, signup, state};
+use crate::create;
+
+#[actix_web::test]
+async fn metrics_count_requests_and_events() {
+    let app = test::init_service(create::app(state())).await;
+    signup(&app, "ana").await;
+
+    let metrics = call(&app, test::TestRequest::get().uri("/metrics")).await;
+    assert_eq!(metrics.status, StatusCode::OK);
+    assert!(metrics.text.contains("http_requests_total"));
+    assert!(metrics.text.contains("users_created_total 1"));
+}
+
+#[actix_web::test]
+async fn metrics_can_be_disabled() {
+    let mut state = state();
+    state.metrics_enabled = false;
+    let app = test::init_service(create::app(state)).await;
+
+    let metrics = call(&app, test::TestRequest::get().uri("/metrics")).await;
+    assert_eq!(metrics.status, StatusCode::NOT_FOUND);
+}
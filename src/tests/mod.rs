@@ -0,0 +1,164 @@
+// Testes das rotas registradas em service::config, executados com o
+// repositório em memória e sem um banco de dados.
+mod config;
+mod conversation;
+mod entity;
+mod follow;
+mod health;
+mod like;
+mod message;
+mod metrics;
+mod notification;
+mod openapi;
+mod pagination;
+mod password;
+mod rate_limit;
+mod search;
+mod stream;
+mod thread;
+mod token;
+mod user;
+
+use actix::Actor;
+use actix_web::{
+    body::MessageBody,
+    dev::{Service, ServiceResponse},
+    http::{
+        header::{HeaderMap, AUTHORIZATION},
+        StatusCode,
+    },
+    test, Error,
+};
+use serde_json::{json, Value};
+use sqlx::postgres::PgPoolOptions;
+use std::{
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
+
+use crate::{feed, repository, AppState};
+
+// Banco de dados inexistente: as rotas usam apenas os repositórios,
+// e a prontidão deve indicar o banco de dados indisponível.
+const UNREACHABLE_DATABASE: &str = "postgres://postgres@127.0.0.1:1/global";
+
+// Resposta lida por inteiro.
+pub struct Reply {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Value, // Null quando o corpo não é JSON.
+    pub text: String,
+}
+
+// Método para criar o estado com um repositório em memória vazio.
+pub fn state() -> AppState {
+    let repository = Arc::new(repository::MemoryRepository::new());
+
+    AppState {
+        db: PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(200))
+            .connect_lazy(UNREACHABLE_DATABASE)
+            .expect("the database url is valid"),
+        users: repository.clone(),
+        messages: repository.clone(),
+        conversations: repository,
+        hasher: crate::password::Hasher::new(8, 1, 1).expect("the test costs are valid"), // Custos mínimos para testes rápidos.
+        tokens: crate::token::Signer::new("test-secret", 900, 3600),
+        search_language: crate::search::Language::Portuguese,
+        feed: Some(feed::Hub::default().start()),
+        metrics: crate::metrics::Metrics::new().expect("the metrics are registered once"),
+        metrics_enabled: true,
+        draining: Arc::new(AtomicBool::new(false)),
+        limiter: None,
+    }
+}
+
+// Método para enviar a requisição e ler a resposta inteira.
+pub async fn call(
+    app: &impl Service<actix_http::Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    req: test::TestRequest,
+) -> Reply {
+    let res = test::call_service(app, req.to_request()).await;
+    let status = res.status();
+    let headers = res.headers().clone();
+    let bytes = test::read_body(res).await;
+
+    Reply {
+        status,
+        headers,
+        body: serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        text: String::from_utf8_lossy(&bytes).to_string(),
+    }
+}
+
+// Cabeçalho de autenticação com o token de acesso.
+pub fn bearer(token: &str) -> (actix_web::http::header::HeaderName, String) {
+    (AUTHORIZATION, format!("Bearer {}", token))
+}
+
+// Método para cadastrar um usuário com a senha padrão dos testes, retornando o id.
+pub async fn register(
+    app: &impl Service<actix_http::Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    name: &str,
+) -> String {
+    let reply = call(
+        app,
+        test::TestRequest::post()
+            .uri("/api/user/manage")
+            .set_json(json!({ "name": name, "password": "12345678" })),
+    )
+    .await;
+    assert_eq!(reply.status, StatusCode::CREATED, "{}", reply.text);
+
+    reply.body["response"]["id"].as_str().unwrap().to_string()
+}
+
+// Método para iniciar uma sessão, retornando os tokens de acesso e de atualização.
+pub async fn login(
+    app: &impl Service<actix_http::Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    name: &str,
+) -> (String, String) {
+    let reply = call(
+        app,
+        test::TestRequest::post()
+            .uri("/api/user/login")
+            .set_json(json!({ "name": name, "password": "12345678" })),
+    )
+    .await;
+    assert_eq!(reply.status, StatusCode::OK, "{}", reply.text);
+
+    let session = &reply.body["response"];
+    (
+        session["access_token"].as_str().unwrap().to_string(),
+        session["refresh_token"].as_str().unwrap().to_string(),
+    )
+}
+
+// Método para cadastrar e autenticar um usuário, retornando o id e o token de acesso.
+pub async fn signup(
+    app: &impl Service<actix_http::Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    name: &str,
+) -> (String, String) {
+    let id = register(app, name).await;
+    let (access, _) = login(app, name).await;
+    (id, access)
+}
+
+// Método para publicar uma mensagem, retornando o id.
+pub async fn post_message(
+    app: &impl Service<actix_http::Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    token: &str,
+    content: &str,
+) -> String {
+    let reply = call(
+        app,
+        test::TestRequest::post()
+            .uri("/api/tech/manage")
+            .insert_header(bearer(token))
+            .set_json(json!({ "content": content })),
+    )
+    .await;
+    assert_eq!(reply.status, StatusCode::CREATED, "{}", reply.text);
+
+    reply.body["response"]["id"].as_str().unwrap().to_string()
+}
@@ -0,0 +1,198 @@
+// Testes das notificações, dos grupos, da leitura e das preferências.
+use actix_web::{http::StatusCode, test};
+use serde_json::json;
+
+use super::{bearer, call, post_message, signup, state};
+use crate::create;
+
+// Método para obter as notificações do usuário.
+fn notifications(token: &str, query: &str) -> test::TestRequest {
+    test::TestRequest::get()
+        .uri(&format!("/api/user/notifications{}", query))
+        .insert_header(bearer(token))
+}
+
+#[actix_web::test]
+async fn likes_are_grouped_and_marked_read() {
+    let app = test::init_service(create::app(state())).await;
+    let (ana, access) = signup(&app, "ana").await;
+    let message = post_message(&app, &access, "olá").await;
+
+    let mut tokens = Vec::new();
+    for name in ["bob", "cid", "dan"] {
+        let (_, token) = signup(&app, name).await;
+        let like = test::TestRequest::post()
+            .uri(&format!("/api/tech/like/{}", message))
+            .insert_header(bearer(&token));
+        assert_eq!(call(&app, like).await.status, StatusCode::CREATED);
+        tokens.push(token);
+    }
+
+    // A curtida da própria mensagem não gera notificação.
+    let like = test::TestRequest::post()
+        .uri(&format!("/api/tech/like/{}", message))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, like).await.status, StatusCode::CREATED);
+
+    let follow = test::TestRequest::post()
+        .uri(&format!("/api/user/follow/{}", ana))
+        .insert_header(bearer(&tokens[0]));
+    assert_eq!(call(&app, follow).await.status, StatusCode::CREATED);
+
+    let page = call(&app, notifications(&access, "")).await;
+    assert_eq!(page.status, StatusCode::OK);
+    let groups = page.body["response"].as_array().unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0]["kind"], "follow");
+    assert_eq!(groups[0]["summary"], "bob followed you");
+    assert_eq!(groups[1]["kind"], "like");
+    assert_eq!(groups[1]["message_id"], message.as_str());
+    assert_eq!(groups[1]["summary"], "3 people liked your message");
+    assert_eq!(groups[1]["actor_count"], 3);
+    assert_eq!(groups[1]["actors"][0]["name"], "dan");
+    assert_eq!(groups[1]["read"], false);
+
+    // A notificação de outro usuário não pode ser marcada.
+    let likes = groups[1]["id"].as_str().unwrap();
+    let other = test::TestRequest::post()
+        .uri(&format!("/api/user/notifications/read/{}", likes))
+        .insert_header(bearer(&tokens[0]));
+    assert_eq!(call(&app, other).await.status, StatusCode::NOT_FOUND);
+
+    let read = test::TestRequest::post()
+        .uri(&format!("/api/user/notifications/read/{}", likes))
+        .insert_header(bearer(&access));
+    let read = call(&app, read).await;
+    assert_eq!(read.status, StatusCode::OK);
+    assert_eq!(read.body["response"], 3);
+
+    let unread = call(&app, notifications(&access, "?unread=true")).await;
+    assert_eq!(unread.body["response"].as_array().unwrap().len(), 1);
+    assert_eq!(unread.body["response"][0]["kind"], "follow");
+
+    // Remover a curtida remove a notificação do grupo.
+    let unlike = test::TestRequest::delete()
+        .uri(&format!("/api/tech/like/{}", message))
+        .insert_header(bearer(&tokens[1]));
+    assert_eq!(call(&app, unlike).await.status, StatusCode::OK);
+
+    let page = call(&app, notifications(&access, "?limit=1")).await;
+    assert_eq!(page.body["has_more"], true);
+    let cursor = page.body["next_cursor"].as_str().unwrap();
+    let page = call(&app, notifications(&access, &format!("?cursor={}", cursor))).await;
+    assert_eq!(
+        page.body["response"][0]["summary"],
+        "2 people liked your message"
+    );
+    assert_eq!(page.body["response"][0]["read"], true);
+
+    let all = test::TestRequest::post()
+        .uri("/api/user/notifications/read")
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, all).await.body["response"], 1);
+
+    let unread = call(&app, notifications(&access, "?unread=true")).await;
+    assert_eq!(unread.body["response"], json!([]));
+
+    let invalid = test::TestRequest::post()
+        .uri("/api/user/notifications/read/1")
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, invalid).await.status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn replies_and_mentions_follow_their_messages() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let (_, other) = signup(&app, "bob").await;
+    let (_, mentioned) = signup(&app, "cid").await;
+
+    let message = post_message(&app, &access, "olá").await;
+    let reply = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/tech/manage")
+            .insert_header(bearer(&other))
+            .set_json(json!({ "content": "oi @cid e @ana", "parent_id": message })),
+    )
+    .await;
+    assert_eq!(reply.status, StatusCode::CREATED);
+    let reply = reply.body["response"]["id"].as_str().unwrap().to_string();
+
+    // A autora da mensagem recebe a resposta e a menção.
+    let page = call(&app, notifications(&access, "")).await;
+    assert_eq!(page.body["response"].as_array().unwrap().len(), 2);
+    assert_eq!(page.body["response"][0]["kind"], "mention");
+    assert_eq!(page.body["response"][1]["kind"], "reply");
+    assert_eq!(page.body["response"][1]["message_id"], message.as_str());
+    assert_eq!(
+        page.body["response"][1]["summary"],
+        "bob replied to your message"
+    );
+
+    let page = call(&app, notifications(&mentioned, "")).await;
+    assert_eq!(page.body["response"][0]["summary"], "bob mentioned you");
+    assert_eq!(page.body["response"][0]["message_id"], reply.as_str());
+
+    // A edição mantém a menção que continua e remove a retirada.
+    let edit = test::TestRequest::patch()
+        .uri(&format!("/api/tech/manage/{}", reply))
+        .insert_header(bearer(&other))
+        .set_json(json!({ "content": "oi @ana" }));
+    assert_eq!(call(&app, edit).await.status, StatusCode::OK);
+
+    let page = call(&app, notifications(&mentioned, "")).await;
+    assert_eq!(page.body["response"], json!([]));
+    let page = call(&app, notifications(&access, "")).await;
+    assert_eq!(page.body["response"].as_array().unwrap().len(), 2);
+
+    let delete = test::TestRequest::delete()
+        .uri(&format!("/api/tech/manage/{}", reply))
+        .insert_header(bearer(&other));
+    assert_eq!(call(&app, delete).await.status, StatusCode::OK);
+
+    let page = call(&app, notifications(&access, "")).await;
+    assert_eq!(page.body["response"], json!([]));
+
+    let anonymous = test::TestRequest::get().uri("/api/user/notifications");
+    assert_eq!(call(&app, anonymous).await.status, StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn preferences_choose_the_recorded_kinds() {
+    let app = test::init_service(create::app(state())).await;
+    let (ana, access) = signup(&app, "ana").await;
+    let (_, other) = signup(&app, "bob").await;
+
+    let preferences = test::TestRequest::get()
+        .uri("/api/user/notifications/preferences")
+        .insert_header(bearer(&access));
+    let preferences = call(&app, preferences).await;
+    assert_eq!(
+        preferences.body["response"],
+        json!({ "likes": true, "replies": true, "mentions": true, "follows": true })
+    );
+
+    let update = test::TestRequest::patch()
+        .uri("/api/user/notifications/preferences")
+        .insert_header(bearer(&access))
+        .set_json(json!({ "likes": false }));
+    let update = call(&app, update).await;
+    assert_eq!(update.status, StatusCode::OK);
+    assert_eq!(update.body["response"]["likes"], false);
+    assert_eq!(update.body["response"]["follows"], true);
+
+    let message = post_message(&app, &access, "olá").await;
+    let like = test::TestRequest::post()
+        .uri(&format!("/api/tech/like/{}", message))
+        .insert_header(bearer(&other));
+    assert_eq!(call(&app, like).await.status, StatusCode::CREATED);
+    let follow = test::TestRequest::post()
+        .uri(&format!("/api/user/follow/{}", ana))
+        .insert_header(bearer(&other));
+    assert_eq!(call(&app, follow).await.status, StatusCode::CREATED);
+
+    let page = call(&app, notifications(&access, "")).await;
+    assert_eq!(page.body["response"].as_array().unwrap().len(), 1);
+    assert_eq!(page.body["response"][0]["kind"], "follow");
+}
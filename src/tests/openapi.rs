@@ -0,0 +1,442 @@
+// Testes do documento OpenAPI: as rotas documentadas precisam ser as
+// registradas, e os schemas precisam corresponder aos tipos serializados.
+use actix_web::{http::StatusCode, test, web, HttpResponse};
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use sqlx::types::Json;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+use super::{call, state};
+use crate::{
+    create, entity, model, openapi, pagination, response, schema,
+    service::{self, conversation, message, thread, user},
+};
+
+// Método para obter o documento como JSON, como é servido.
+fn document() -> Value {
+    serde_json::to_value(openapi::document()).unwrap()
+}
+
+// Método para obter as rotas do documento, com o método em maiúsculas.
+fn documented_routes(document: &Value) -> BTreeSet<(String, String)> {
+    let mut routes = BTreeSet::new();
+    for (path, item) in document["paths"].as_object().unwrap() {
+        for method in item.as_object().unwrap().keys() {
+            routes.insert((method.to_uppercase(), path.clone()));
+        }
+    }
+    routes
+}
+
+// Método para coletar as referências "$ref" do documento.
+fn references(value: &Value, found: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                found.insert(reference.clone());
+            }
+            map.values().for_each(|value| references(value, found));
+        }
+        Value::Array(values) => values.iter().for_each(|value| references(value, found)),
+        _ => {}
+    }
+}
+
+// Método para comparar as chaves serializadas com as propriedades do schema.
+fn assert_matches(name: &str, value: impl Serialize) {
+    let document = document();
+    let schema = &document["components"]["schemas"][name];
+    let value = serde_json::to_value(value).unwrap();
+
+    let serialized: BTreeSet<&String> = value.as_object().unwrap().keys().collect();
+    let properties: BTreeSet<&String> = schema["properties"].as_object().unwrap().keys().collect();
+    let required: BTreeSet<&str> = schema["required"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|key| key.as_str().unwrap())
+        .collect();
+
+    // As propriedades opcionais podem ser omitidas na serialização.
+    assert!(
+        serialized.is_subset(&properties),
+        "{} serializes {:?}, documented {:?}",
+        name,
+        serialized,
+        properties
+    );
+    assert!(
+        required.iter().all(|key| value.get(key).is_some()),
+        "{} doesn't serialize every required property {:?}",
+        name,
+        required
+    );
+}
+
+// Método para verificar que o exemplo do schema é aceito pelo modelo.
+fn assert_accepts<T: DeserializeOwned>(name: &str) {
+    let example = document()["components"]["schemas"][name]["example"].clone();
+    assert!(
+        serde_json::from_value::<T>(example).is_ok(),
+        "{} rejects its example",
+        name
+    );
+}
+
+#[actix_web::test]
+async fn documented_routes_are_the_registered_ones() {
+    let registered: BTreeSet<(String, String)> = service::scopes()
+        .iter()
+        .flat_map(|scope| scope.routes())
+        .map(|route| (route.method.to_string(), route.path.clone()))
+        .collect();
+
+    assert_eq!(documented_routes(&document()), registered);
+}
+
+#[actix_web::test]
+async fn every_documented_route_is_registered() {
+    // Requisições sem rota correspondente chegam ao serviço padrão.
+    let app = test::init_service(
+        create::app(state())
+            .default_service(web::to(|| async { HttpResponse::ImATeapot().finish() })),
+    )
+    .await;
+
+    let document = document();
+    let mut ids = BTreeSet::new();
+    for (method, path) in documented_routes(&document) {
+        let id = &document["paths"][&path][method.to_lowercase()]["operationId"];
+        assert!(ids.insert(id.to_string()), "{} is repeated", id);
+
+        let mut uri = path.clone();
+        while let (Some(start), Some(end)) = (uri.find('{'), uri.find('}')) {
+            uri.replace_range(start..=end, &Uuid::new_v4().to_string());
+        }
+
+        // Apenas o status é lido, já que a conexão Server-Sent Events não termina.
+        let req = test::TestRequest::default()
+            .method(method.parse().unwrap())
+            .uri(&uri);
+        let res = test::call_service(&app, req.to_request()).await;
+        assert_ne!(
+            res.status(),
+            StatusCode::IM_A_TEAPOT,
+            "{} {} is not registered",
+            method,
+            path
+        );
+    }
+}
+
+#[actix_web::test]
+async fn schemas_match_the_serialized_types() {
+    let now = Utc::now();
+    let message = schema::Message {
+        id: Uuid::new_v4(),
+        likes: 0,
+        user_id: Uuid::new_v4(),
+        content: "olá".to_string(),
+        sended_at: now,
+        edited_at: None,
+        parent_id: None,
+        reply_count: 0,
+        deleted_at: None,
+        entities: Json(vec![schema::Entity {
+            kind: schema::EntityKind::Mention,
+            text: "ana".to_string(),
+            start: 0,
+            end: 4,
+            user_id: Some(Uuid::new_v4()),
+        }]),
+    };
+
+    assert_matches(
+        "Success_Vec_Message",
+        response::Success::page("success", vec![message.clone()], None),
+    );
+    assert_matches(
+        "Success_Vec_Message",
+        response::Success::page(
+            "success",
+            Vec::<schema::Message>::new(),
+            Some("cursor".to_string()),
+        ),
+    );
+    assert_matches("Success_i64", response::Success::new("success", 1));
+    assert_matches("ServerError", response::ServerError::new("not found", "!"));
+    assert_matches(
+        "User",
+        response::User {
+            id: Uuid::new_v4(),
+            name: "ana".to_string(),
+            created_at: now,
+            message_count: 0,
+        },
+    );
+    assert_matches(
+        "Profile",
+        response::Profile {
+            id: Uuid::new_v4(),
+            name: "ana".to_string(),
+            display_name: None,
+            bio: None,
+            avatar_url: None,
+            created_at: now,
+            message_count: 0,
+            likes_given: 0,
+            likes_received: 0,
+        },
+    );
+    assert_matches(
+        "Session",
+        response::Session {
+            token_type: "Bearer".to_string(),
+            access_token: String::new(),
+            expires_in: 0,
+            refresh_token: String::new(),
+            refresh_expires_in: 0,
+        },
+    );
+    assert_matches("Message", message.clone());
+    assert_matches("Entity", message.entities[0].clone());
+    assert_matches(
+        "MessageRevision",
+        schema::MessageRevision {
+            id: Uuid::new_v4(),
+            message_id: message.id,
+            content: "olá".to_string(),
+            written_at: now,
+            replaced_at: now,
+        },
+    );
+    assert_matches(
+        "Like",
+        response::Like {
+            user_id: Uuid::new_v4(),
+            name: "ana".to_string(),
+            liked_at: now,
+        },
+    );
+    assert_matches(
+        "Follow",
+        response::Follow {
+            user_id: Uuid::new_v4(),
+            name: "ana".to_string(),
+            followed_at: now,
+        },
+    );
+    assert_matches(
+        "Notification",
+        response::Notification::new(
+            Uuid::new_v4(),
+            schema::NotificationKind::Like,
+            Some(message.id),
+            vec![response::Actor {
+                user_id: Uuid::new_v4(),
+                name: "ana".to_string(),
+            }],
+            1,
+            false,
+            now,
+        ),
+    );
+    assert_matches(
+        "Actor",
+        response::Actor {
+            user_id: Uuid::new_v4(),
+            name: "ana".to_string(),
+        },
+    );
+    assert_matches(
+        "NotificationPreferences",
+        response::NotificationPreferences::default(),
+    );
+    let member = response::Member {
+        user_id: Uuid::new_v4(),
+        name: "ana".to_string(),
+        last_read_at: None,
+    };
+    assert_matches(
+        "Conversation",
+        response::Conversation {
+            id: Uuid::new_v4(),
+            direct: true,
+            members: vec![member.clone()],
+            created_at: now,
+            last_message_at: now,
+            unread: 0,
+        },
+    );
+    assert_matches("Member", member);
+    assert_matches(
+        "DirectMessage",
+        response::DirectMessage {
+            id: Uuid::new_v4(),
+            conversation_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            content: "olá".to_string(),
+            sended_at: now,
+            read_by: vec![Uuid::new_v4()],
+        },
+    );
+    assert_matches(
+        "Unread",
+        response::Unread {
+            messages: 0,
+            conversations: 0,
+        },
+    );
+    assert_matches(
+        "SearchResult",
+        response::SearchResult {
+            message,
+            rank: 0.1,
+            snippet: String::new(),
+        },
+    );
+    assert_matches(
+        "Health",
+        response::Health {
+            server: response::Component::up(None),
+            database: response::Component::down("!"),
+            migrations: response::Component::unknown("!"),
+        },
+    );
+    assert_matches("Component", response::Component::up(Some(1.0)));
+    assert_matches("Component", response::Component::down("!"));
+
+    assert_accepts::<model::User>("UserModel");
+    assert_accepts::<model::UserUpdate>("UserUpdateModel");
+    assert_accepts::<model::Login>("LoginModel");
+    assert_accepts::<model::Refresh>("RefreshModel");
+    assert_accepts::<model::Message>("MessageModel");
+    assert_accepts::<model::MessageEdit>("MessageEditModel");
+    assert_accepts::<model::NotificationPreferences>("NotificationPreferencesModel");
+    assert_accepts::<model::Conversation>("ConversationModel");
+    assert_accepts::<model::DirectMessage>("DirectMessageModel");
+}
+
+#[actix_web::test]
+async fn document_and_ui_are_served() {
+    let app = test::init_service(create::app(state())).await;
+
+    let document = call(&app, test::TestRequest::get().uri("/api/openapi.json")).await;
+    assert_eq!(document.status, StatusCode::OK);
+    assert_eq!(document.body["openapi"], "3.1.0");
+
+    // Toda referência aponta para um schema existente.
+    let mut found = BTreeSet::new();
+    references(&document.body, &mut found);
+    for reference in found {
+        let name = reference.trim_start_matches("#/components/schemas/");
+        assert!(
+            document.body["components"]["schemas"].get(name).is_some(),
+            "{} doesn't exist",
+            reference
+        );
+    }
+
+    let ui = call(&app, test::TestRequest::get().uri("/api/docs")).await;
+    assert_eq!(ui.status, StatusCode::OK);
+    assert!(ui.text.contains("openapi.json"));
+}
+
+#[actix_web::test]
+async fn swagger_ui_is_served_from_the_binary() {
+    let app = test::init_service(create::app(state())).await;
+
+    // A página não carrega nada de fora do servidor.
+    let ui = call(&app, test::TestRequest::get().uri("/api/docs")).await;
+    assert!(!ui.text.contains("://"));
+
+    for (file, content_type) in [
+        ("swagger-ui.css", "text/css; charset=utf-8"),
+        ("swagger-ui-bundle.js", "text/javascript; charset=utf-8"),
+    ] {
+        assert!(ui.text.contains(&format!("\"swagger-ui/{}\"", file)));
+
+        let uri = format!("/api/swagger-ui/{}", file);
+        let asset = call(&app, test::TestRequest::get().uri(&uri)).await;
+        assert_eq!(asset.status, StatusCode::OK);
+        assert_eq!(asset.headers.get("content-type").unwrap(), content_type);
+        assert!(!asset.text.is_empty());
+    }
+
+    // A versão embutida é a indicada na constante.
+    let bundle = call(
+        &app,
+        test::TestRequest::get().uri("/api/swagger-ui/swagger-ui-bundle.js"),
+    )
+    .await;
+    assert!(bundle
+        .text
+        .contains(&format!("\"{}\"", service::docs::SWAGGER_UI_VERSION)));
+
+    for file in ["index.html", "..%2FCargo.toml", "swagger-ui.js"] {
+        let uri = format!("/api/swagger-ui/{}", file);
+        let missing = call(&app, test::TestRequest::get().uri(&uri)).await;
+        assert_eq!(missing.status, StatusCode::NOT_FOUND);
+    }
+}
+
+#[actix_web::test]
+async fn bounds_match_the_validation() {
+    let document = document();
+    let schemas = &document["components"]["schemas"];
+    let parameter = |path: &str, name: &str| {
+        document["paths"][path]["get"]["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|parameter| parameter["name"] == name)
+            .unwrap()["schema"]
+            .clone()
+    };
+
+    // Os limites do documento são literais nos atributos, então
+    // precisam acompanhar as constantes usadas na validação.
+    let content = |name: &str| schemas[name]["properties"]["content"]["maxLength"].clone();
+    assert_eq!(content("MessageModel"), message::CONTENT_MAX);
+    assert_eq!(content("MessageEditModel"), message::CONTENT_MAX);
+    assert_eq!(content("DirectMessageModel"), conversation::CONTENT_MAX);
+    assert_eq!(
+        schemas["ConversationModel"]["properties"]["user_ids"]["maxItems"],
+        conversation::MAX_MEMBERS - 1
+    );
+
+    let profile = &schemas["UserUpdateModel"]["properties"];
+    assert_eq!(profile["name"]["maxLength"], user::NAME_MAX);
+    assert_eq!(profile["display_name"]["maxLength"], user::DISPLAY_NAME_MAX);
+    assert_eq!(profile["bio"]["maxLength"], user::BIO_MAX);
+    assert_eq!(profile["avatar_url"]["maxLength"], user::AVATAR_URL_MAX);
+
+    assert_eq!(
+        schemas["Notification"]["properties"]["actors"]["maxItems"],
+        response::NOTIFICATION_ACTORS
+    );
+    assert_eq!(
+        parameter("/api/user/all", "limit")["maximum"],
+        pagination::MAX_LIMIT
+    );
+    assert_eq!(
+        parameter("/api/tech/thread/{id}", "depth")["maximum"],
+        thread::MAX_DEPTH
+    );
+    assert_eq!(
+        parameter("/api/tech/tag/{tag}", "tag")["maxLength"],
+        entity::TAG_MAX + 1
+    );
+    assert_eq!(
+        parameter("/api/tech/stream", "tag")["maxLength"],
+        entity::TAG_MAX + 1
+    );
+    assert!(
+        document["paths"]["/api/tech/thread/{id}"]["get"]["description"]
+            .as_str()
+            .unwrap()
+            .contains(&format!("up to {} replies", thread::NESTED_REPLIES))
+    );
+}
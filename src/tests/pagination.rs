@@ -0,0 +1,69 @@
+// Testes da validação dos parâmetros e dos cursores de paginação.
+use uuid::Uuid;
+
+use crate::{
+    model,
+    pagination::{finish, parse, DEFAULT_LIMIT, MAX_LIMIT},
+};
+
+// Método para montar os parâmetros de uma página.
+fn page(limit: Option<i64>, cursor: Option<&str>) -> model::Page {
+    model::Page {
+        limit,
+        cursor: cursor.map(str::to_string),
+    }
+}
+
+#[test]
+fn the_limit_is_bounded() {
+    let default = parse::<Uuid>(&page(None, None)).ok().unwrap();
+    assert_eq!(default.limit, DEFAULT_LIMIT);
+    assert!(default.after.is_none());
+
+    assert_eq!(parse::<Uuid>(&page(Some(1), None)).ok().unwrap().limit, 1);
+    assert_eq!(
+        parse::<Uuid>(&page(Some(MAX_LIMIT), None))
+            .ok()
+            .unwrap()
+            .limit,
+        MAX_LIMIT
+    );
+    assert!(parse::<Uuid>(&page(Some(0), None)).is_err());
+    assert!(parse::<Uuid>(&page(Some(MAX_LIMIT + 1), None)).is_err());
+}
+
+#[test]
+fn cursors_round_trip_through_finish_and_parse() {
+    let first = parse::<(String, Uuid)>(&page(Some(2), None)).ok().unwrap();
+    assert_eq!(first.fetch(), 3); // Uma linha a mais para saber se há outra página.
+
+    let ids = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+    let rows = vec![("ana", ids[0]), ("bob", ids[1]), ("cid", ids[2])];
+    let (rows, next) = finish(rows, &first, |(name, id)| (name.to_string(), *id));
+    assert_eq!(rows.len(), 2);
+
+    // O cursor aponta para o último item da página.
+    let second = parse::<(String, Uuid)>(&page(Some(2), next.as_deref()))
+        .ok()
+        .unwrap();
+    assert_eq!(second.after, Some(("bob".to_string(), ids[1])));
+
+    let (rows, next) = finish(vec![("cid", ids[2])], &second, |(name, id)| {
+        (name.to_string(), *id)
+    });
+    assert_eq!(rows.len(), 1);
+    assert!(next.is_none()); // Última página.
+}
+
+#[test]
+fn invalid_cursors_are_rejected() {
+    assert!(parse::<Uuid>(&page(None, Some("não é base64"))).is_err());
+
+    // Base64 válido, mas com uma chave de outro tipo.
+    let (_, cursor) = finish(
+        vec![1, 2],
+        &parse::<i64>(&page(Some(1), None)).ok().unwrap(),
+        |n| *n,
+    );
+    assert!(parse::<Uuid>(&page(None, cursor.as_deref())).is_err());
+}
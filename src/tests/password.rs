@@ -0,0 +1,57 @@
+// Testes da geração e da verificação dos hashes de senha.
+use crate::password::{Hasher, Verification};
+
+// Método para criar um Hasher com custos baixos, mantendo os testes rápidos.
+fn hasher(time_cost: u32) -> Hasher {
+    Hasher::new(8, time_cost, 1).unwrap()
+}
+
+#[test]
+fn hashed_passwords_are_verified() {
+    let hasher = hasher(1);
+    let hash = hasher.hash_sync("12345678").unwrap();
+
+    assert!(hash.starts_with("$argon2id$"));
+    assert!(hasher.verify_sync("12345678", &hash) == Verification::Valid);
+    assert!(hasher.verify_sync("87654321", &hash) == Verification::Invalid);
+}
+
+#[test]
+fn legacy_plain_text_passwords_are_outdated() {
+    let hasher = hasher(1);
+
+    assert!(hasher.verify_sync("12345678", "12345678") == Verification::Outdated);
+    assert!(hasher.verify_sync("12345678", "1234567") == Verification::Invalid);
+    assert!(hasher.verify_sync("", "12345678") == Verification::Invalid);
+}
+
+#[test]
+fn hashes_with_old_costs_are_outdated() {
+    let hash = hasher(1).hash_sync("12345678").unwrap();
+
+    // A senha confere, mas o hash precisa ser refeito com os custos atuais.
+    assert!(hasher(2).verify_sync("12345678", &hash) == Verification::Outdated);
+    assert!(hasher(2).verify_sync("87654321", &hash) == Verification::Invalid);
+}
+
+#[test]
+fn corrupted_hashes_never_authenticate() {
+    let hasher = hasher(1);
+
+    assert!(hasher.verify_sync("12345678", "$argon2id$corrompido") == Verification::Invalid);
+    assert!(
+        hasher.verify_sync("$argon2id$corrompido", "$argon2id$corrompido") == Verification::Invalid
+    );
+}
+
+#[test]
+fn dummy_hash_has_the_current_costs() {
+    let hasher = hasher(2);
+
+    // O hash precisa ser válido para que a verificação calcule o argon2
+    // e leve o mesmo tempo de uma senha incorreta de um usuário existente.
+    assert!(argon2::PasswordHash::new(hasher.dummy()).is_ok());
+    assert!(hasher.dummy().starts_with("$argon2id$v=19$m=8,t=2,p=1$"));
+    assert!(hasher.verify_sync("12345678", hasher.dummy()) == Verification::Invalid);
+    assert!(hasher.verify_sync("", hasher.dummy()) == Verification::Invalid);
+}
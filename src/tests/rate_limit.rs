@@ -0,0 +1,165 @@
+// Testes do limite de requisições aplicado às rotas da api.
+use actix_web::{
+    http::{header::RETRY_AFTER, StatusCode},
+    test,
+};
+use serde_json::json;
+use std::net::SocketAddr;
+
+use super::{bearer, call, login, register, state};
+use crate::{config, create, rate_limit, token};
+
+// Método para criar o estado com limites curtos para o escopo de autenticação.
+fn limited() -> crate::AppState {
+    let mut state = state();
+    state.limiter = Some(rate_limit::Limiter::new(config::RateLimit {
+        auth: "10/60".parse().unwrap(),
+        user: "100/60".parse().unwrap(),
+        tech: "2/60".parse().unwrap(),
+        trust_proxy: false,
+        failures: 2,
+        backoff: 60,
+        backoff_max: 600,
+    }));
+    state
+}
+
+#[actix_web::test]
+async fn requests_over_the_limit_are_rejected() {
+    let app = test::init_service(create::app(limited())).await;
+    let peer: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+
+    for remaining in ["1", "0"] {
+        let res = call(
+            &app,
+            test::TestRequest::get()
+                .uri("/api/tech/all")
+                .peer_addr(peer),
+        )
+        .await;
+        assert_eq!(res.status, StatusCode::OK);
+        assert_eq!(res.headers.get("ratelimit-remaining").unwrap(), remaining);
+    }
+
+    let limited = call(
+        &app,
+        test::TestRequest::get()
+            .uri("/api/tech/all")
+            .peer_addr(peer),
+    )
+    .await;
+    assert_eq!(limited.status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(limited.headers.contains_key(RETRY_AFTER));
+
+    // Outro endereço possui o próprio balde, e as rotas de saúde não são limitadas.
+    let other: SocketAddr = "10.0.0.2:4000".parse().unwrap();
+    let res = call(
+        &app,
+        test::TestRequest::get()
+            .uri("/api/tech/all")
+            .peer_addr(other),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK);
+
+    let live = call(
+        &app,
+        test::TestRequest::get().uri("/health/live").peer_addr(peer),
+    )
+    .await;
+    assert_eq!(live.status, StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn failed_logins_block_the_address() {
+    let app = test::init_service(create::app(limited())).await;
+    let peer: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+    register(&app, "ana").await;
+
+    let wrong = || {
+        test::TestRequest::post()
+            .uri("/api/user/login")
+            .peer_addr(peer)
+            .set_json(json!({ "name": "ana", "password": "87654321" }))
+    };
+    assert_eq!(call(&app, wrong()).await.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(call(&app, wrong()).await.status, StatusCode::UNAUTHORIZED);
+
+    // As falhas toleradas não impedem a autenticação.
+    let right = || {
+        test::TestRequest::post()
+            .uri("/api/user/login")
+            .peer_addr(peer)
+            .set_json(json!({ "name": "ana", "password": "12345678" }))
+    };
+    assert_eq!(call(&app, right()).await.status, StatusCode::OK);
+
+    // A falha seguinte bloqueia o endereço, mesmo para a senha correta.
+    assert_eq!(call(&app, wrong()).await.status, StatusCode::UNAUTHORIZED);
+    let locked = call(&app, right()).await;
+    assert_eq!(locked.status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(locked.headers.contains_key(RETRY_AFTER));
+}
+
+#[actix_web::test]
+async fn wrong_current_passwords_block_the_address() {
+    let app = test::init_service(create::app(limited())).await;
+    let peer: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+    register(&app, "ana").await;
+    let (token, _) = login(&app, "ana").await;
+
+    let change = |current: &str| {
+        test::TestRequest::patch()
+            .uri("/api/user/manage")
+            .peer_addr(peer)
+            .insert_header(bearer(&token))
+            .set_json(json!({ "password": "abcdefgh", "current_password": current }))
+    };
+
+    // A senha atual incorreta conta como uma falha de autenticação.
+    for _ in 0..3 {
+        assert_eq!(
+            call(&app, change("87654321")).await.status,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    let locked = call(&app, change("12345678")).await;
+    assert_eq!(locked.status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(locked.headers.contains_key(RETRY_AFTER));
+}
+
+#[actix_web::test]
+async fn expired_tokens_do_not_block_the_address() {
+    let app = test::init_service(create::app(limited())).await;
+    let peer: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+    let ana = register(&app, "ana").await;
+
+    // Token assinado com o segredo dos testes, mas já expirado.
+    let expired = token::Signer::new("test-secret", -1, 3600)
+        .access(ana.parse().unwrap())
+        .unwrap();
+
+    for _ in 0..5 {
+        let res = call(
+            &app,
+            test::TestRequest::get()
+                .uri("/api/user/notifications")
+                .peer_addr(peer)
+                .insert_header(bearer(&expired)),
+        )
+        .await;
+        assert_eq!(res.status, StatusCode::UNAUTHORIZED);
+    }
+
+    // O endereço continua autenticando normalmente.
+    let res = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/user/login")
+            .peer_addr(peer)
+            .set_json(json!({ "name": "ana", "password": "12345678" })),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK);
+}
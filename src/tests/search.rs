@@ -0,0 +1,31 @@
+// Testes da conversão da busca do usuário em uma consulta de to_tsquery.
+use crate::search::to_tsquery;
+
+#[test]
+fn words_are_required_together() {
+    assert_eq!(to_tsquery("Rust  actix").as_deref(), Some("(rust & actix)"));
+}
+
+#[test]
+fn phrases_prefixes_and_negations_are_supported() {
+    assert_eq!(
+        to_tsquery("\"servidor web\" asyn* -java").as_deref(),
+        Some("((servidor <-> web) & asyn:* & !java)")
+    );
+}
+
+#[test]
+fn alternatives_are_split_by_or() {
+    assert_eq!(
+        to_tsquery("rust OR go or -java").as_deref(),
+        Some("(rust) | (go) | (!java)")
+    );
+    assert_eq!(to_tsquery("or rust or").as_deref(), Some("(rust)"));
+}
+
+#[test]
+fn query_operators_are_discarded() {
+    assert_eq!(to_tsquery("a&b|!c:").as_deref(), Some("((a <-> b <-> c))"));
+    assert_eq!(to_tsquery("ação").as_deref(), Some("(ação)"));
+    assert_eq!(to_tsquery("  & | ! \"\" -"), None);
+}
@@ -0,0 +1,202 @@
+// Testes da conexão Server-Sent Events: os eventos, os filtros e a retomada.
+use actix_web::{
+    body::MessageBody,
+    dev::ServiceResponse,
+    http::{header, StatusCode},
+    rt::time::timeout,
+    test,
+};
+use serde_json::Value;
+use std::{future::poll_fn, pin::Pin, time::Duration};
+
+use super::{bearer, call, post_message, signup, state};
+use crate::create;
+
+// Evento lido da conexão.
+struct Event {
+    id: u64,
+    kind: String,
+    data: Value,
+}
+
+// Corpo da conexão aberta, lido evento a evento.
+struct Stream(Pin<Box<dyn MessageBody<Error = Box<dyn std::error::Error>>>>);
+
+impl Stream {
+    fn new(res: ServiceResponse<impl MessageBody + 'static>) -> Self {
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+        Self(Box::pin(res.into_body().boxed()))
+    }
+
+    // Método para ler o próximo evento, ignorando o retry e os comentários.
+    async fn next(&mut self) -> Option<Event> {
+        loop {
+            let chunk = timeout(
+                Duration::from_millis(500),
+                poll_fn(|cx| self.0.as_mut().poll_next(cx)),
+            )
+            .await
+            .ok()??
+            .unwrap();
+            let frame = std::str::from_utf8(&chunk).unwrap();
+
+            let field = |name: &str| {
+                frame
+                    .lines()
+                    .find_map(|line| line.strip_prefix(&format!("{}: ", name)))
+                    .map(str::to_string)
+            };
+            if let (Some(id), Some(kind), Some(data)) = (field("id"), field("event"), field("data"))
+            {
+                return Some(Event {
+                    id: id.parse().unwrap(),
+                    kind,
+                    data: serde_json::from_str(&data).unwrap(),
+                });
+            }
+        }
+    }
+}
+
+#[actix_web::test]
+async fn stream_delivers_filtered_events() {
+    let app = test::init_service(create::app(state())).await;
+    let (ana, ana_token) = signup(&app, "ana").await;
+    let (_, bob_token) = signup(&app, "bob").await;
+
+    let open = |uri: &str| test::TestRequest::get().uri(uri).to_request();
+    let mut all = Stream::new(test::call_service(&app, open("/api/tech/stream")).await);
+    let mut by_ana = Stream::new(
+        test::call_service(&app, open(&format!("/api/tech/stream?user_id={}", ana))).await,
+    );
+    let mut by_tag =
+        Stream::new(test::call_service(&app, open("/api/tech/stream?tag=%23Rust")).await);
+
+    let tagged = post_message(&app, &bob_token, "aprendendo #rust").await;
+    let plain = post_message(&app, &ana_token, "olá").await;
+
+    let like = test::TestRequest::post()
+        .uri(&format!("/api/tech/like/{}", tagged))
+        .insert_header(bearer(&ana_token));
+    assert_eq!(call(&app, like).await.status, StatusCode::CREATED);
+    let delete = test::TestRequest::delete()
+        .uri(&format!("/api/tech/manage/{}", plain))
+        .insert_header(bearer(&ana_token));
+    assert_eq!(call(&app, delete).await.status, StatusCode::OK);
+
+    // Todos os eventos, com ids crescentes.
+    let mut events = Vec::new();
+    for _ in 0..4 {
+        events.push(all.next().await.unwrap());
+    }
+    let kinds: Vec<&str> = events.iter().map(|e| e.kind.as_str()).collect();
+    assert_eq!(kinds, ["message", "message", "like", "delete"]);
+    assert!(events.windows(2).all(|pair| pair[0].id < pair[1].id));
+    assert_eq!(events[0].data["message"]["id"], tagged.as_str());
+    assert_eq!(events[2].data["likes"], 1);
+    assert_eq!(events[2].data["user_id"], ana.as_str());
+    assert_eq!(events[3].data["message_id"], plain.as_str());
+
+    // Os eventos das mensagens da autora.
+    let event = by_ana.next().await.unwrap();
+    assert_eq!(event.kind, "message");
+    assert_eq!(event.data["message"]["id"], plain.as_str());
+    assert_eq!(by_ana.next().await.unwrap().kind, "delete");
+    assert!(by_ana.next().await.is_none());
+
+    // Os eventos das mensagens com a hashtag, mesmo os de outros usuários.
+    let event = by_tag.next().await.unwrap();
+    assert_eq!(event.data["message"]["id"], tagged.as_str());
+    assert_eq!(by_tag.next().await.unwrap().kind, "like");
+    assert!(by_tag.next().await.is_none());
+
+    let invalid = call(
+        &app,
+        test::TestRequest::get().uri("/api/tech/stream?tag=123"),
+    )
+    .await;
+    assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+
+    let mut disabled = state();
+    disabled.feed = None;
+    let app = test::init_service(create::app(disabled)).await;
+    let res = call(&app, test::TestRequest::get().uri("/api/tech/stream")).await;
+    assert_eq!(res.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn stream_resumes_from_the_last_event_id() {
+    let app = test::init_service(create::app(state())).await;
+    let (ana, ana_token) = signup(&app, "ana").await;
+    let (_, bob_token) = signup(&app, "bob").await;
+
+    let mut first = Stream::new(
+        test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri("/api/tech/stream")
+                .to_request(),
+        )
+        .await,
+    );
+    post_message(&app, &ana_token, "um").await;
+    let seen = first.next().await.unwrap();
+    drop(first);
+
+    // Eventos publicados com o cliente desconectado.
+    let missed = post_message(&app, &bob_token, "dois").await;
+    let last = post_message(&app, &ana_token, "três").await;
+
+    let resume = test::TestRequest::get()
+        .uri("/api/tech/stream")
+        .insert_header(("Last-Event-ID", seen.id.to_string()))
+        .to_request();
+    let mut resumed = Stream::new(test::call_service(&app, resume).await);
+    let replayed = resumed.next().await.unwrap();
+    assert_eq!(replayed.data["message"]["id"], missed.as_str());
+    assert_eq!(replayed.id, seen.id + 1);
+    assert_eq!(
+        resumed.next().await.unwrap().data["message"]["id"],
+        last.as_str()
+    );
+
+    // Os eventos novos seguem os reenviados.
+    let live = post_message(&app, &bob_token, "quatro").await;
+    assert_eq!(
+        resumed.next().await.unwrap().data["message"]["id"],
+        live.as_str()
+    );
+
+    // A retomada também aplica os filtros.
+    let filtered = test::TestRequest::get()
+        .uri(&format!(
+            "/api/tech/stream?user_id={}&last_event_id={}",
+            ana, seen.id
+        ))
+        .to_request();
+    let mut filtered = Stream::new(test::call_service(&app, filtered).await);
+    assert_eq!(
+        filtered.next().await.unwrap().data["message"]["id"],
+        last.as_str()
+    );
+    assert!(filtered.next().await.is_none());
+
+    // Um id anterior à janela de retomada pede que o cliente recarregue.
+    let stale = test::TestRequest::get()
+        .uri("/api/tech/stream")
+        .insert_header(("Last-Event-ID", "1"))
+        .to_request();
+    let mut stale = Stream::new(test::call_service(&app, stale).await);
+    let reset = stale.next().await.unwrap();
+    assert_eq!(reset.kind, "reset");
+    assert_eq!(reset.id, seen.id + 3);
+
+    let invalid = test::TestRequest::get()
+        .uri("/api/tech/stream")
+        .insert_header(("Last-Event-ID", "abc"));
+    assert_eq!(call(&app, invalid).await.status, StatusCode::BAD_REQUEST);
+}
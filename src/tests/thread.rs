@@ -0,0 +1,156 @@
+// Testes das respostas e da rota de conversas.
+use actix_http::Request;
+use actix_web::{
+    body::MessageBody,
+    dev::{Service, ServiceResponse},
+    http::StatusCode,
+    test, Error,
+};
+use serde_json::json;
+
+use super::{bearer, call, post_message, signup, state};
+use crate::create;
+
+// Método para responder a uma mensagem, retornando o id da resposta.
+async fn reply(
+    app: &impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    token: &str,
+    parent_id: &str,
+    content: &str,
+) -> String {
+    let res = call(
+        app,
+        test::TestRequest::post()
+            .uri("/api/tech/manage")
+            .insert_header(bearer(token))
+            .set_json(json!({ "content": content, "parent_id": parent_id })),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::CREATED, "{}", res.text);
+    assert_eq!(res.body["response"]["parent_id"], parent_id);
+
+    res.body["response"]["id"].as_str().unwrap().to_string()
+}
+
+#[actix_web::test]
+async fn replies_form_a_tree() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let (_, other) = signup(&app, "bob").await;
+
+    let root = post_message(&app, &access, "raiz").await;
+    let first = reply(&app, &other, &root, "primeira").await;
+    reply(&app, &access, &root, "segunda").await;
+    reply(&app, &access, &first, "resposta da primeira").await;
+
+    let thread = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/thread/{}", root)),
+    )
+    .await;
+    assert_eq!(thread.status, StatusCode::OK);
+
+    let tree = &thread.body["response"];
+    assert_eq!(tree["content"], "raiz");
+    assert_eq!(tree["reply_count"], 2);
+    assert_eq!(tree["replies"][0]["content"], "primeira");
+    assert_eq!(tree["replies"][0]["reply_count"], 1);
+    assert_eq!(
+        tree["replies"][0]["replies"][0]["content"],
+        "resposta da primeira"
+    );
+    assert_eq!(tree["replies"][1]["content"], "segunda");
+
+    let missing = test::TestRequest::post()
+        .uri("/api/tech/manage")
+        .insert_header(bearer(&access))
+        .set_json(json!({ "content": "?", "parent_id": uuid::Uuid::new_v4() }));
+    assert_eq!(call(&app, missing).await.status, StatusCode::NOT_FOUND);
+
+    let unknown =
+        test::TestRequest::get().uri(&format!("/api/tech/thread/{}", uuid::Uuid::new_v4()));
+    assert_eq!(call(&app, unknown).await.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn thread_is_limited_and_paged() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+
+    let root = post_message(&app, &access, "raiz").await;
+    let first = reply(&app, &access, &root, "primeira").await;
+    reply(&app, &access, &root, "segunda").await;
+    reply(&app, &access, &first, "resposta da primeira").await;
+
+    // Abaixo da profundidade as respostas aparecem apenas no contador.
+    let shallow = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/thread/{}?depth=1&limit=1", root)),
+    )
+    .await;
+    let replies = shallow.body["response"]["replies"].as_array().unwrap();
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0]["content"], "primeira");
+    assert_eq!(replies[0]["reply_count"], 1);
+    assert_eq!(replies[0]["replies"], json!([]));
+    assert_eq!(shallow.body["has_more"], true);
+
+    let cursor = shallow.body["next_cursor"].as_str().unwrap();
+    let rest = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/thread/{}?cursor={}", root, cursor)),
+    )
+    .await;
+    assert_eq!(rest.body["response"]["replies"][0]["content"], "segunda");
+    assert_eq!(rest.body["has_more"], false);
+
+    for depth in ["0", "11"] {
+        let invalid =
+            test::TestRequest::get().uri(&format!("/api/tech/thread/{}?depth={}", root, depth));
+        assert_eq!(call(&app, invalid).await.status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[actix_web::test]
+async fn deleted_parent_stays_as_a_tombstone() {
+    let app = test::init_service(create::app(state())).await;
+    let (_, access) = signup(&app, "ana").await;
+    let (_, other) = signup(&app, "bob").await;
+
+    let root = post_message(&app, &access, "raiz").await;
+    let answer = reply(&app, &other, &root, "resposta").await;
+
+    let delete = test::TestRequest::delete()
+        .uri(&format!("/api/tech/manage/{}", root))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, delete).await.status, StatusCode::OK);
+
+    let thread = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/tech/thread/{}", root)),
+    )
+    .await;
+    let tree = &thread.body["response"];
+    assert_eq!(tree["content"], "");
+    assert!(tree["deleted_at"].is_string());
+    assert_eq!(tree["replies"][0]["id"], answer.as_str());
+
+    // O tombstone não aparece fora da conversa nem aceita alterações.
+    let one = test::TestRequest::get().uri(&format!("/api/tech/one/{}", root));
+    assert_eq!(call(&app, one).await.status, StatusCode::NOT_FOUND);
+    let all = call(&app, test::TestRequest::get().uri("/api/tech/all")).await;
+    assert_eq!(all.body["response"].as_array().unwrap().len(), 1);
+    let again = test::TestRequest::delete()
+        .uri(&format!("/api/tech/manage/{}", root))
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, again).await.status, StatusCode::NOT_FOUND);
+
+    // Sem respostas o tombstone é removido.
+    let delete = test::TestRequest::delete()
+        .uri(&format!("/api/tech/manage/{}", answer))
+        .insert_header(bearer(&other));
+    assert_eq!(call(&app, delete).await.status, StatusCode::OK);
+
+    let gone = test::TestRequest::get().uri(&format!("/api/tech/thread/{}", root));
+    assert_eq!(call(&app, gone).await.status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,52 @@
+// Testes da assinatura dos tokens de acesso e dos tokens de atualização.
+use uuid::Uuid;
+
+use crate::token::{hash_refresh, Signer};
+
+#[test]
+fn access_tokens_carry_the_user_id() {
+    let signer = Signer::new("secret", 900, 3600);
+    let user_id = Uuid::new_v4();
+    let token = signer.access(user_id).unwrap();
+
+    assert_eq!(signer.verify(&token), Some(user_id));
+    assert_eq!(signer.access_ttl(), 900);
+    assert_eq!(signer.refresh_ttl(), 3600);
+}
+
+#[test]
+fn invalid_access_tokens_are_rejected() {
+    let signer = Signer::new("secret", 900, 3600);
+    let token = signer.access(Uuid::new_v4()).unwrap();
+
+    // Assinado com outro segredo.
+    assert_eq!(Signer::new("other", 900, 3600).verify(&token), None);
+
+    // Conteúdo alterado depois da assinatura.
+    let mut tampered = token.clone();
+    tampered.insert(tampered.find('.').unwrap() + 1, 'x');
+    assert_eq!(signer.verify(&tampered), None);
+
+    assert_eq!(signer.verify("not.a.token"), None);
+}
+
+#[test]
+fn expired_access_tokens_are_rejected() {
+    // A expiração já passou no momento da emissão, e não há tolerância.
+    let signer = Signer::new("secret", -1, 3600);
+    let token = signer.access(Uuid::new_v4()).unwrap();
+
+    assert_eq!(signer.verify(&token), None);
+}
+
+#[test]
+fn refresh_tokens_are_stored_as_hashes() {
+    let signer = Signer::new("secret", 900, 3600);
+    let first = signer.refresh();
+    let second = signer.refresh();
+
+    assert_ne!(first.token, second.token);
+    assert_ne!(first.hash, first.token);
+    assert_eq!(first.hash, hash_refresh(&first.token));
+    assert!(first.expires_at > chrono::Utc::now());
+}
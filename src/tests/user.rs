@@ -0,0 +1,411 @@
+// Testes das rotas de usuário: cadastro, pesquisa, sessões e remoção.
+use actix_web::{http::StatusCode, test};
+use serde_json::json;
+
+use super::{bearer, call, login, post_message, register, signup, state};
+use crate::create;
+
+#[actix_web::test]
+async fn add_user_rejects_invalid_and_repeated_names() {
+    let app = test::init_service(create::app(state())).await;
+    register(&app, "ana").await;
+
+    let repeated = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/user/manage")
+            .set_json(json!({ "name": "ana", "password": "12345678" })),
+    )
+    .await;
+    assert_eq!(repeated.status, StatusCode::CONFLICT);
+
+    let short = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/user/manage")
+            .set_json(json!({ "name": "bob", "password": "1234567" })),
+    )
+    .await;
+    assert_eq!(short.status, StatusCode::NOT_ACCEPTABLE);
+}
+
+#[actix_web::test]
+async fn all_users_are_paginated_by_name() {
+    let app = test::init_service(create::app(state())).await;
+    for name in ["cid", "ana", "bob"] {
+        register(&app, name).await;
+    }
+
+    let first = call(&app, test::TestRequest::get().uri("/api/user/all?limit=2")).await;
+    assert_eq!(first.status, StatusCode::OK);
+    assert_eq!(first.body["response"][0]["name"], "ana");
+    assert_eq!(first.body["response"][1]["name"], "bob");
+    assert_eq!(first.body["has_more"], true);
+
+    let cursor = first.body["next_cursor"].as_str().unwrap();
+    let second = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/user/all?limit=2&cursor={}", cursor)),
+    )
+    .await;
+    assert_eq!(second.status, StatusCode::OK);
+    assert_eq!(second.body["response"].as_array().unwrap().len(), 1);
+    assert_eq!(second.body["response"][0]["name"], "cid");
+    assert_eq!(second.body["has_more"], false);
+
+    let invalid = call(&app, test::TestRequest::get().uri("/api/user/all?cursor=x")).await;
+    assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn one_user_is_found_by_id() {
+    let app = test::init_service(create::app(state())).await;
+    let id = register(&app, "ana").await;
+
+    let found = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/user/one/{}", id)),
+    )
+    .await;
+    assert_eq!(found.status, StatusCode::OK);
+    assert_eq!(found.body["response"]["name"], "ana");
+    assert!(found.body["response"].get("password").is_none());
+
+    let missing = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/user/one/{}", uuid::Uuid::new_v4())),
+    )
+    .await;
+    assert_eq!(missing.status, StatusCode::NOT_FOUND);
+
+    let invalid = call(&app, test::TestRequest::get().uri("/api/user/one/abc")).await;
+    assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn some_users_are_found_by_name() {
+    let app = test::init_service(create::app(state())).await;
+    for name in ["mariana", "ana", "bob"] {
+        register(&app, name).await;
+    }
+
+    let found = call(&app, test::TestRequest::get().uri("/api/user/some/ana")).await;
+    assert_eq!(found.status, StatusCode::OK);
+    assert_eq!(found.body["response"].as_array().unwrap().len(), 2);
+
+    let missing = call(&app, test::TestRequest::get().uri("/api/user/some/zed")).await;
+    assert_eq!(missing.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn login_requires_the_right_password() {
+    let app = test::init_service(create::app(state())).await;
+    register(&app, "ana").await;
+
+    let (access, refresh) = login(&app, "ana").await;
+    assert!(!access.is_empty() && !refresh.is_empty());
+
+    let wrong = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/user/login")
+            .set_json(json!({ "name": "ana", "password": "87654321" })),
+    )
+    .await;
+    assert_eq!(wrong.status, StatusCode::UNAUTHORIZED);
+
+    let missing = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/user/login")
+            .set_json(json!({ "name": "bob", "password": "12345678" })),
+    )
+    .await;
+    assert_eq!(missing.status, StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn refresh_rotates_the_token_and_revokes_reused_ones() {
+    let app = test::init_service(create::app(state())).await;
+    register(&app, "ana").await;
+    let (_, first) = login(&app, "ana").await;
+    let (_, other) = login(&app, "ana").await;
+
+    let refreshed = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/user/refresh")
+            .set_json(json!({ "refresh_token": first })),
+    )
+    .await;
+    assert_eq!(refreshed.status, StatusCode::OK);
+    let second = refreshed.body["response"]["refresh_token"]
+        .as_str()
+        .unwrap();
+    assert_ne!(second, first);
+
+    // Reapresentar o token já utilizado revoga todas as sessões do usuário.
+    let reused = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/user/refresh")
+            .set_json(json!({ "refresh_token": first })),
+    )
+    .await;
+    assert_eq!(reused.status, StatusCode::UNAUTHORIZED);
+
+    for token in [second, other.as_str()] {
+        let revoked = call(
+            &app,
+            test::TestRequest::post()
+                .uri("/api/user/refresh")
+                .set_json(json!({ "refresh_token": token })),
+        )
+        .await;
+        assert_eq!(revoked.status, StatusCode::UNAUTHORIZED);
+    }
+}
+
+#[actix_web::test]
+async fn logout_revokes_the_refresh_token() {
+    let app = test::init_service(create::app(state())).await;
+    register(&app, "ana").await;
+    let (access, refresh) = login(&app, "ana").await;
+
+    let anonymous = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/user/logout")
+            .set_json(json!({ "refresh_token": refresh })),
+    )
+    .await;
+    assert_eq!(anonymous.status, StatusCode::UNAUTHORIZED);
+
+    let logout = test::TestRequest::post()
+        .uri("/api/user/logout")
+        .insert_header(bearer(&access))
+        .set_json(json!({ "refresh_token": refresh }));
+    assert_eq!(call(&app, logout).await.status, StatusCode::OK);
+
+    let again = test::TestRequest::post()
+        .uri("/api/user/logout")
+        .insert_header(bearer(&access))
+        .set_json(json!({ "refresh_token": refresh }));
+    assert_eq!(call(&app, again).await.status, StatusCode::NOT_FOUND);
+
+    let refreshed = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/user/refresh")
+            .set_json(json!({ "refresh_token": refresh })),
+    )
+    .await;
+    assert_eq!(refreshed.status, StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn delete_user_removes_the_messages() {
+    let app = test::init_service(create::app(state())).await;
+    let (id, access) = signup(&app, "ana").await;
+    post_message(&app, &access, "primeira mensagem").await;
+
+    let anonymous = call(&app, test::TestRequest::delete().uri("/api/user/manage")).await;
+    assert_eq!(anonymous.status, StatusCode::UNAUTHORIZED);
+
+    let delete = test::TestRequest::delete()
+        .uri("/api/user/manage")
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, delete).await.status, StatusCode::OK);
+
+    let messages = call(&app, test::TestRequest::get().uri("/api/tech/all")).await;
+    assert_eq!(messages.body["response"].as_array().unwrap().len(), 0);
+
+    let user = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/user/one/{}", id)),
+    )
+    .await;
+    assert_eq!(user.status, StatusCode::NOT_FOUND);
+
+    // O token de acesso continua válido até expirar, mas o usuário não existe mais.
+    let again = test::TestRequest::delete()
+        .uri("/api/user/manage")
+        .insert_header(bearer(&access));
+    assert_eq!(call(&app, again).await.status, StatusCode::NOT_FOUND);
+}
+
+#[actix_web::test]
+async fn patch_user_updates_the_profile() {
+    let app = test::init_service(create::app(state())).await;
+    register(&app, "bob").await;
+    let (id, access) = signup(&app, "ana").await;
+
+    let updated = call(
+        &app,
+        test::TestRequest::patch()
+            .uri("/api/user/manage")
+            .insert_header(bearer(&access))
+            .set_json(json!({
+                "name": "ana_maria",
+                "display_name": "Ana Maria",
+                "bio": "Rustacean",
+                "avatar_url": "https://example.com/ana.png",
+            })),
+    )
+    .await;
+    assert_eq!(updated.status, StatusCode::OK, "{}", updated.text);
+    assert_eq!(updated.body["response"]["id"], id.as_str());
+    assert_eq!(updated.body["response"]["name"], "ana_maria");
+    assert_eq!(updated.body["response"]["display_name"], "Ana Maria");
+
+    // Os campos ausentes são mantidos e um texto vazio remove o campo.
+    let cleared = call(
+        &app,
+        test::TestRequest::patch()
+            .uri("/api/user/manage")
+            .insert_header(bearer(&access))
+            .set_json(json!({ "avatar_url": "" })),
+    )
+    .await;
+    assert_eq!(cleared.status, StatusCode::OK);
+    assert!(cleared.body["response"]["avatar_url"].is_null());
+    assert_eq!(cleared.body["response"]["bio"], "Rustacean");
+
+    login(&app, "ana_maria").await;
+
+    let repeated = call(
+        &app,
+        test::TestRequest::patch()
+            .uri("/api/user/manage")
+            .insert_header(bearer(&access))
+            .set_json(json!({ "name": "bob" })),
+    )
+    .await;
+    assert_eq!(repeated.status, StatusCode::CONFLICT);
+
+    for invalid in [
+        json!({ "name": "" }),
+        json!({ "display_name": "a".repeat(65) }),
+        json!({ "bio": "a".repeat(281) }),
+        json!({ "avatar_url": "javascript:alert(1)" }),
+    ] {
+        let rejected = call(
+            &app,
+            test::TestRequest::patch()
+                .uri("/api/user/manage")
+                .insert_header(bearer(&access))
+                .set_json(invalid),
+        )
+        .await;
+        assert_eq!(rejected.status, StatusCode::NOT_ACCEPTABLE);
+    }
+
+    let anonymous = call(
+        &app,
+        test::TestRequest::patch()
+            .uri("/api/user/manage")
+            .set_json(json!({ "bio": "" })),
+    )
+    .await;
+    assert_eq!(anonymous.status, StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn password_change_requires_the_current_password() {
+    let app = test::init_service(create::app(state())).await;
+    register(&app, "ana").await;
+    let (access, refresh) = login(&app, "ana").await;
+
+    let change = |body: serde_json::Value| {
+        test::TestRequest::patch()
+            .uri("/api/user/manage")
+            .insert_header(bearer(&access))
+            .set_json(body)
+    };
+
+    let missing = call(&app, change(json!({ "password": "abcdefgh" }))).await;
+    assert_eq!(missing.status, StatusCode::BAD_REQUEST);
+
+    let wrong = call(
+        &app,
+        change(json!({ "password": "abcdefgh", "current_password": "87654321" })),
+    )
+    .await;
+    assert_eq!(wrong.status, StatusCode::UNAUTHORIZED);
+
+    let short = call(
+        &app,
+        change(json!({ "password": "abc", "current_password": "12345678" })),
+    )
+    .await;
+    assert_eq!(short.status, StatusCode::NOT_ACCEPTABLE);
+
+    let changed = call(
+        &app,
+        change(json!({ "password": "abcdefgh", "current_password": "12345678" })),
+    )
+    .await;
+    assert_eq!(changed.status, StatusCode::OK);
+
+    // A troca de senha encerra as sessões existentes.
+    let refreshed = call(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/user/refresh")
+            .set_json(json!({ "refresh_token": refresh })),
+    )
+    .await;
+    assert_eq!(refreshed.status, StatusCode::UNAUTHORIZED);
+
+    for (password, status) in [
+        ("12345678", StatusCode::UNAUTHORIZED),
+        ("abcdefgh", StatusCode::OK),
+    ] {
+        let login = call(
+            &app,
+            test::TestRequest::post()
+                .uri("/api/user/login")
+                .set_json(json!({ "name": "ana", "password": password })),
+        )
+        .await;
+        assert_eq!(login.status, status);
+    }
+}
+
+#[actix_web::test]
+async fn profile_counts_messages_and_likes() {
+    let app = test::init_service(create::app(state())).await;
+    let (ana, access) = signup(&app, "ana").await;
+    let (_, other) = signup(&app, "bob").await;
+    let first = post_message(&app, &access, "primeira").await;
+    post_message(&app, &access, "segunda").await;
+
+    for (token, message) in [(&other, &first), (&access, &first)] {
+        let like = test::TestRequest::post()
+            .uri(&format!("/api/tech/like/{}", message))
+            .insert_header(bearer(token));
+        assert_eq!(call(&app, like).await.status, StatusCode::CREATED);
+    }
+
+    let profile = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/user/profile/{}", ana)),
+    )
+    .await;
+    assert_eq!(profile.status, StatusCode::OK);
+    assert_eq!(profile.body["response"]["name"], "ana");
+    assert_eq!(profile.body["response"]["message_count"], 2);
+    assert_eq!(profile.body["response"]["likes_given"], 1);
+    assert_eq!(profile.body["response"]["likes_received"], 2);
+    assert!(profile.body["response"]["display_name"].is_null());
+
+    let missing = call(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/user/profile/{}", uuid::Uuid::new_v4())),
+    )
+    .await;
+    assert_eq!(missing.status, StatusCode::NOT_FOUND);
+
+    let invalid = call(&app, test::TestRequest::get().uri("/api/user/profile/abc")).await;
+    assert_eq!(invalid.status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,126 @@
+use actix_web::rt;
+use rustls::{
+    crypto::{ring, CryptoProvider},
+    server::{ClientHello, ResolvesServerCert},
+    sign::CertifiedKey,
+    ServerConfig,
+};
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+use tracing::{info, warn};
+
+// Certificado e chave privada atuais, recarregados quando os arquivos
+// mudam. As novas conexões passam a usar o novo certificado sem reiniciar
+// o servidor, enquanto as conexões abertas mantêm o anterior.
+#[derive(Debug)]
+pub struct Certificate {
+    cert_path: String,
+    key_path: String,
+    provider: Arc<CryptoProvider>,
+    current: RwLock<Arc<CertifiedKey>>,
+    modified: RwLock<Option<(SystemTime, SystemTime)>>, // Datas de modificação do último carregamento.
+}
+
+impl Certificate {
+    // Método para carregar o certificado e a chave privada em PEM.
+    pub fn load(cert_path: &str, key_path: &str) -> Result<Arc<Self>, String> {
+        let provider = Arc::new(ring::default_provider());
+        let modified = modified(cert_path, key_path);
+        let current = read(cert_path, key_path, &provider)?;
+
+        Ok(Arc::new(Self {
+            cert_path: cert_path.to_string(),
+            key_path: key_path.to_string(),
+            provider,
+            current: RwLock::new(Arc::new(current)),
+            modified: RwLock::new(modified),
+        }))
+    }
+
+    // Método para criar a configuração do rustls que utiliza o certificado atual.
+    pub fn server_config(self: &Arc<Self>) -> Result<ServerConfig, String> {
+        let config = ServerConfig::builder_with_provider(self.provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|err| err.to_string())?
+            .with_no_client_auth()
+            .with_cert_resolver(self.clone());
+
+        Ok(config) // Retornando a configuração.
+    }
+
+    // Método para recarregar os arquivos quando a data de modificação mudar.
+    // Um arquivo inválido é ignorado, mantendo o certificado anterior.
+    fn reload(&self) {
+        let modified = modified(&self.cert_path, &self.key_path);
+        if modified.is_none() || modified == *self.modified.read().unwrap() {
+            return;
+        }
+
+        match read(&self.cert_path, &self.key_path, &self.provider) {
+            Ok(certified) => {
+                *self.current.write().unwrap() = Arc::new(certified);
+                *self.modified.write().unwrap() = modified;
+                info!(cert = %self.cert_path, "TLS certificate reloaded"); // Propagando um log de sucesso.
+            }
+            Err(err) => {
+                // Registrando a data para não repetir o aviso até a próxima mudança.
+                *self.modified.write().unwrap() = modified;
+                warn!(cert = %self.cert_path, "TLS reload failed: {}", err); // Propagando um log de aviso.
+            }
+        }
+    }
+
+    // Método para verificar os arquivos periodicamente.
+    pub fn watch(self: Arc<Self>, interval: Duration) {
+        rt::spawn(async move {
+            let mut interval = rt::time::interval(interval);
+            interval.tick().await; // O primeiro tick é imediato.
+            loop {
+                interval.tick().await;
+                self.reload();
+            }
+        });
+    }
+}
+
+impl ResolvesServerCert for Certificate {
+    fn resolve(&self, _client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        Some(self.current.read().unwrap().clone())
+    }
+}
+
+// Método para ler as datas de modificação do certificado e da chave.
+fn modified(cert_path: &str, key_path: &str) -> Option<(SystemTime, SystemTime)> {
+    let cert = fs::metadata(cert_path).and_then(|m| m.modified()).ok()?;
+    let key = fs::metadata(key_path).and_then(|m| m.modified()).ok()?;
+    Some((cert, key))
+}
+
+// Método para ler a cadeia de certificados e a chave privada,
+// verificando se a chave corresponde ao certificado.
+fn read(
+    cert_path: &str,
+    key_path: &str,
+    provider: &CryptoProvider,
+) -> Result<CertifiedKey, String> {
+    let mut reader =
+        BufReader::new(File::open(cert_path).map_err(|err| format!("{}: {}", cert_path, err))?);
+    let certs = rustls_pemfile::certs(&mut reader)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("{}: {}", cert_path, err))?;
+    if certs.is_empty() {
+        return Err(format!("{}: no certificate found", cert_path));
+    }
+
+    let mut reader =
+        BufReader::new(File::open(key_path).map_err(|err| format!("{}: {}", key_path, err))?);
+    let key = rustls_pemfile::private_key(&mut reader)
+        .map_err(|err| format!("{}: {}", key_path, err))?
+        .ok_or_else(|| format!("{}: no private key found", key_path))?;
+
+    CertifiedKey::from_der(certs, key, provider).map_err(|err| format!("{}: {}", key_path, err))
+}
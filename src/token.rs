@@ -0,0 +1,98 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+// Conteúdo assinado dentro do token de acesso.
+#[derive(Serialize, Deserialize)]
+struct Claims {
+    sub: Uuid, // Id do usuário autenticado.
+    iat: i64,  // Momento da emissão (segundos desde a época Unix).
+    exp: i64,  // Momento da expiração (segundos desde a época Unix).
+}
+
+// Token de atualização recém gerado, junto ao valor
+// que deve ser armazenado no banco de dados.
+pub struct RefreshToken {
+    pub token: String,
+    pub hash: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+// Estrutura responsável por assinar e validar os tokens
+// de acesso e gerar os tokens de atualização.
+#[derive(Clone)]
+pub struct Signer {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    access_ttl: Duration,
+    refresh_ttl: Duration,
+}
+
+impl Signer {
+    // Cria um Signer a partir do segredo HMAC e da duração
+    // em segundos dos tokens de acesso e de atualização.
+    pub fn new(secret: &str, access_ttl: i64, refresh_ttl: i64) -> Self {
+        Self {
+            encoding: EncodingKey::from_secret(secret.as_bytes()),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            access_ttl: Duration::seconds(access_ttl),
+            refresh_ttl: Duration::seconds(refresh_ttl),
+        }
+    }
+
+    // Duração do token de acesso em segundos.
+    pub fn access_ttl(&self) -> i64 {
+        self.access_ttl.num_seconds()
+    }
+
+    // Duração do token de atualização em segundos.
+    pub fn refresh_ttl(&self) -> i64 {
+        self.refresh_ttl.num_seconds()
+    }
+
+    // Assina um token de acesso para o usuário.
+    pub fn access(&self, user_id: Uuid) -> Result<String, jsonwebtoken::errors::Error> {
+        let now = Utc::now();
+        let claims = Claims {
+            sub: user_id,
+            iat: now.timestamp(),
+            exp: (now + self.access_ttl).timestamp(),
+        };
+
+        encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)
+    }
+
+    // Valida a assinatura e a expiração do token de acesso,
+    // retornando o id do usuário.
+    pub fn verify(&self, token: &str) -> Option<Uuid> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0; // Sem tolerância após a expiração.
+
+        decode::<Claims>(token, &self.decoding, &validation)
+            .ok()
+            .map(|data| data.claims.sub)
+    }
+
+    // Gera um token de atualização aleatório e opaco.
+    pub fn refresh(&self) -> RefreshToken {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let token = URL_SAFE_NO_PAD.encode(bytes);
+
+        RefreshToken {
+            hash: hash_refresh(&token),
+            token,
+            expires_at: Utc::now() + self.refresh_ttl,
+        }
+    }
+}
+
+// Calcula o valor armazenado de um token de atualização,
+// evitando guardar o token utilizável no banco de dados.
+pub fn hash_refresh(token: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(token.as_bytes()))
+}